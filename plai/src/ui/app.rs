@@ -1,5 +1,7 @@
 use leptos::*;
 use leptos_meta::*;
+use leptos_plausible::components::{provide_plausible, PageView};
+use leptos_plausible::Plausible;
 use leptos_router::*;
 
 use crate::ui::game::GameView;
@@ -8,6 +10,10 @@ use crate::ui::startup::Setup;
 #[component]
 pub fn App() -> impl IntoView {
     provide_meta_context();
+    provide_plausible(Plausible::new_private(
+        "get.plai.cards",
+        "https://frumentarii.8vi.cat",
+    ));
 
     let players: RwSignal<Vec<(i32, RwSignal<String>)>> = create_rw_signal(vec![
         (0, create_rw_signal("Player 1".to_string())),
@@ -16,6 +22,7 @@ pub fn App() -> impl IntoView {
 
     view! {
         <Router>
+            <PageView/>
             <Routes>
                 <Route path="" view=move || view! { <Setup players=players/> }/>
                 <Route path="plai" view=move || view! { <GameView players=players/> }/>
@@ -0,0 +1,548 @@
+use leptos::ev::{keydown, load, visibilitychange};
+use wasm_bindgen::JsCast;
+use web_sys::{window, Element, Event, HtmlAnchorElement, MouseEvent};
+
+use leptos::*;
+use leptos_meta::Meta;
+use leptos_use::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use crate::Plausible;
+
+use leptos::html::{Div, Input};
+use leptos::logging::{debug_warn, log};
+use leptos_router::A as ARouter;
+use leptos_use::use_active_element;
+
+use super::event::{EventBuilder, PropValue};
+use super::experiments::{
+    use_experiment_props, use_experiments, Experiment, ExperimentCtx, Experiments,
+};
+use super::tracker::{ConsoleTracker, MemoryTracker, TrackerKind};
+
+const DOMAIN_META: &str = "plausible-domain";
+const URL_META: &str = "plausible-url";
+const TRACKER_META: &str = "plausible-tracker";
+/// Event of [`EndPage`]
+const ENDPAGE: &str = "endpage";
+
+/// Where the events are sent to.
+///
+/// The server provides them as context and the browser reads them from the
+/// `<meta>` tags rendered by [`PlausibleMeta`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlausibleSettings {
+    /// Domain of the site in plausible
+    pub domain: String,
+    /// Plausible instance, `https://plausible.io` if you are not self-hosting
+    pub instance_url: String,
+    /// Where the events of the browser end up. Only `plausible` sends them.
+    pub tracker: TrackerKind,
+    /// Reverse proxies in front of the server. Only their `X-Forwarded-For`
+    /// is passed on to plausible, the one of anyone else could be forged.
+    /// Not sent to the browser.
+    pub trusted_proxies: Vec<IpAddr>,
+    /// User agent of the events sent by the server itself, like
+    /// `your-app/1.0`
+    pub server_agent: String,
+}
+
+impl Default for PlausibleSettings {
+    fn default() -> Self {
+        Self {
+            domain: String::new(),
+            instance_url: "https://plausible.io".into(),
+            tracker: TrackerKind::default(),
+            trusted_proxies: vec![],
+            server_agent: concat!("leptos-plausible/", env!("CARGO_PKG_VERSION")).into(),
+        }
+    }
+}
+
+impl PlausibleSettings {
+    fn current() -> Self {
+        #[cfg(feature = "ssr")]
+        let settings = use_context::<Self>();
+        #[cfg(not(feature = "ssr"))]
+        let settings = Self::from_document();
+
+        settings.unwrap_or_default()
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    fn from_document() -> Option<Self> {
+        let doc = document();
+        let content = |name: &str| {
+            doc.query_selector(&format!("meta[name=\"{name}\"]"))
+                .ok()
+                .flatten()
+                .and_then(|m| m.get_attribute("content"))
+        };
+        Some(Self {
+            domain: content(DOMAIN_META)?,
+            instance_url: content(URL_META)?,
+            tracker: content(TRACKER_META)
+                .and_then(|t| t.parse().ok())
+                .unwrap_or_default(),
+            ..Self::default()
+        })
+    }
+}
+
+/// Sets the plausible context. It should usually be somewhere near the
+/// root of your application (Similar to the `<Router />` component).
+///
+/// Events go through the server, see [`EVENTS_PATH`](super::EVENTS_PATH),
+/// unless the settings pick another [`TrackerKind`]. A [`MemoryTracker`] is
+/// provided as context too.
+///
+/// Apps without a server, like client side rendered ones, use
+/// [`provide_plausible`] instead.
+pub fn provide_plausible_context() {
+    let settings = PlausibleSettings::current();
+    let tracking = match settings.tracker {
+        TrackerKind::Plausible => Plausible::proxied(&settings.domain),
+        TrackerKind::Console => Plausible::with_tracker(&settings.domain, ConsoleTracker),
+        TrackerKind::Memory => {
+            let memory = MemoryTracker::default();
+            provide_context(memory.clone());
+            Plausible::with_tracker(&settings.domain, memory)
+        }
+    };
+    provide_context(settings);
+    provide_plausible(tracking);
+}
+
+/// Sets `plausible` as the plausible context, so the components send their
+/// events with it
+///
+/// ```
+/// # use leptos_plausible::components::provide_plausible;
+/// # use leptos_plausible::Plausible;
+/// # let runtime = leptos::create_runtime();
+/// provide_plausible(Plausible::new_private("your_domain", "https://your_plausible_instance.com"));
+/// # runtime.dispose();
+/// ```
+pub fn provide_plausible(plausible: Plausible) {
+    provide_context(plausible);
+}
+
+/// Passes the [`PlausibleSettings`] of the server to the browser
+#[component]
+pub fn PlausibleMeta() -> impl IntoView {
+    let settings = use_context::<PlausibleSettings>().unwrap_or_default();
+    view! {
+        <Meta name=DOMAIN_META content=settings.domain/>
+        <Meta name=URL_META content=settings.instance_url/>
+        <Meta name=TRACKER_META content=settings.tracker.as_str()/>
+    }
+}
+
+/// Retrieves plausible context
+#[must_use]
+pub fn expect_plausible_context() -> Plausible {
+    expect_context::<Plausible>()
+}
+
+/// Sends an event if the user focused on an item with an ID starting with `plausible-`
+///
+/// It will send an `active_element` event with the property `data-id` with the value
+/// without `plausible-`
+///
+/// For example `plausible-email` becomes `email`
+pub fn track_active_elements() {
+    let id_with_event = use_active_plausible_id();
+
+    create_effect(move |_| {
+        if let Some(id) = id_with_event.get() {
+            expect_plausible_context()
+                .event("active_element")
+                .prop("id", id.into())
+                .send_local();
+        }
+    });
+}
+
+/// `data-id` of the focused element without `plausible-`, if it starts with it
+#[must_use]
+pub fn use_active_plausible_id() -> Memo<Option<String>> {
+    let active_element = use_active_element();
+
+    create_memo(move |_| {
+        active_element.get().and_then(|el| {
+            el.dataset()
+                .get("id")
+                .filter(|id| id.starts_with("plausible-"))
+                .map(|id| id.replace("plausible-", ""))
+        })
+    })
+}
+
+/// Track a standard page view event.
+#[must_use]
+#[component]
+pub fn PageView() -> impl IntoView {
+    let el = create_node_ref::<Div>();
+    let plausible = expect_plausible_context();
+
+    send_once_visible(use_element_visibility(el).into(), move || {
+        pageview_event(&plausible).send_local();
+    });
+
+    view! { <div node_ref=el></div> }
+}
+
+/// Send a custom event when a given part of a webpage is within
+/// the viewport of the user.
+///
+/// Use it to track when somenone has "viewed"/reached a given part of
+/// your website. `props` are added to the event.
+#[must_use]
+#[component]
+pub fn TrackElement(
+    #[prop(into)] name: String,
+    #[prop(into, default = false)] allow_duplicates: bool,
+    #[prop(optional)] props: HashMap<String, PropValue>,
+) -> impl IntoView {
+    let el = create_node_ref::<Div>();
+    let plausible = expect_plausible_context();
+
+    send_once_visible(use_element_visibility(el).into(), move || {
+        element_event(&plausible, &name, props.clone()).send_local();
+    });
+
+    view! { <div node_ref=el></div> }
+}
+
+/// Send and `endpage` event. Similar to page view but to know when a
+/// visitor reached the end of the page.
+#[must_use]
+#[component]
+pub fn EndPage() -> impl IntoView {
+    view! { <TrackElement name=ENDPAGE/> }
+}
+
+/// Substitute for `<a>` and `<A>` that tracks the links to plausible.
+///
+/// It renders a [`leptos_router::A`], so internal links keep the client side
+/// navigation of the router, and forwards any other attribute to it, like
+/// `attr:rel="noopener"`. Clicks are not changed: opening the link in a new
+/// tab with a modifier key or the middle button works as usual and is tracked
+/// too. The event is sent with `navigator.sendBeacon`, so it survives leaving
+/// the page.
+#[must_use]
+#[component]
+pub fn A(
+    #[prop(into)] href: String,
+    #[prop(optional, into)] target: Option<String>,
+    #[prop(optional, into)] class: Option<AttributeValue>,
+    #[prop(optional, into)] id: Option<String>,
+    #[prop(attrs)] mut attributes: Vec<(&'static str, Attribute)>,
+    /// The nodes or elements to be shown inside the link.
+    children: Children,
+) -> impl IntoView {
+    // There is no context in the click handler
+    let exps = use_experiments();
+    let plausible = expect_plausible_context();
+
+    let track = move |ev: MouseEvent| {
+        if !tracks_click(ev.button()) {
+            return;
+        }
+        let anchor = ev
+            .target()
+            .and_then(|t| t.dyn_into::<Element>().ok())
+            .and_then(|el| el.closest("a").ok().flatten())
+            .and_then(|a| a.dyn_into::<HtmlAnchorElement>().ok());
+        if let Some(anchor) = anchor {
+            link_event(&plausible, &anchor.href(), exps).send_beacon();
+        }
+    };
+
+    let extra = [("id", id), ("target", target)];
+    for (name, value) in extra {
+        if let Some(value) = value {
+            attributes.push((name, Attribute::String(value.into())));
+        }
+    }
+
+    view! {
+      <ARouter
+        href=href
+        class=class
+        attributes=attributes
+        on:click=track.clone()
+        on:auxclick=track
+      >
+        {children()}
+      </ARouter>
+    }
+}
+
+/// Calls `send` the first time `is_visible`.
+///
+/// Isomorphic so the components can be mounted in tests, the server never
+/// sees anything visible.
+fn send_once_visible(is_visible: Signal<bool>, send: impl Fn() + 'static) {
+    let triggered = create_rw_signal(false);
+
+    create_isomorphic_effect(move |_| {
+        if is_visible.get() && !triggered.get_untracked() {
+            triggered.set(true);
+            send();
+        }
+    });
+}
+
+// Events of the components, apart from them so they can be checked without
+// a browser
+
+fn pageview_event(plausible: &Plausible) -> EventBuilder {
+    plausible.pageview()
+}
+
+fn element_event(
+    plausible: &Plausible,
+    name: &str,
+    props: HashMap<String, PropValue>,
+) -> EventBuilder {
+    let event = plausible.event(name);
+    if props.is_empty() {
+        event
+    } else {
+        event.props(props)
+    }
+}
+
+fn link_event(plausible: &Plausible, url: &str, exps: Option<Experiments>) -> EventBuilder {
+    plausible.link_click(url).set_experiments(exps)
+}
+
+/// Whether a click of `button` opens the link: the main one, with or without
+/// modifier keys, or the middle one with `auxclick`
+const fn tracks_click(button: i16) -> bool {
+    matches!(button, 0 | 1)
+}
+
+// Events carry the page they come from only in the browser
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use axum::http::Request;
+    use leptos::{
+        create_runtime, create_rw_signal, provide_context, view, IntoView, Signal, SignalSet,
+    };
+    use leptos_router::{Router, RouterIntegrationContext, ServerIntegration};
+    use rstest::rstest;
+    use std::collections::HashMap;
+    use std::time::Duration;
+    use tokio::task::{yield_now, LocalSet};
+
+    use super::{
+        element_event, link_event, pageview_event, provide_plausible, send_once_visible,
+        tracks_click, EndPage, PageView, TrackElement, A, ENDPAGE,
+    };
+    use crate::experiments::{
+        provide_experiments, use_experiments, Experiment, Variant, EXPERIMENT_PROP, EXPERIMENT_VIEW,
+    };
+    use crate::tracker::MemoryTracker;
+    use crate::{Plausible, PlausiblePayload, PropValue};
+
+    /// Provides a plausible that keeps its events in the returned tracker
+    fn provide_memory_plausible() -> MemoryTracker {
+        let memory = MemoryTracker::default();
+        provide_plausible(Plausible::with_tracker("get.plai.cards", memory.clone()));
+        memory
+    }
+
+    /// The event sent by `send`
+    fn sent(send: impl FnOnce(&Plausible)) -> PlausiblePayload {
+        let runtime = create_runtime();
+        let memory = MemoryTracker::default();
+        send(&Plausible::with_tracker("get.plai.cards", memory.clone()));
+        runtime.dispose();
+
+        let mut events = memory.events();
+        assert_eq!(events.len(), 1, "One event is sent");
+        events.remove(0)
+    }
+
+    #[rstest]
+    fn page_view_sends_a_pageview() {
+        let event = sent(|p| pageview_event(p).send_local());
+
+        assert_eq!(event.name, "pageview");
+        assert_eq!(event.domain, "get.plai.cards");
+        assert!(event.props.is_none());
+    }
+
+    #[rstest]
+    #[case::track_element("Newsletter")]
+    #[case::end_page(ENDPAGE)]
+    fn seen_elements_send_their_name(#[case] name: &str) {
+        let event = sent(|p| element_event(p, name, HashMap::new()).send_local());
+
+        assert_eq!(event.name, name);
+        assert!(event.props.is_none());
+    }
+
+    #[rstest]
+    fn experiment_views_say_which_experiment() {
+        let props = HashMap::from([(EXPERIMENT_PROP.to_string(), "HeroText".into())]);
+
+        let event = sent(|p| element_event(p, EXPERIMENT_VIEW, props).send_local());
+
+        assert_eq!(event.name, "ExperimentView");
+        let props = event.props.expect("Experiment view has props");
+        assert!(matches!(&props["experiment"], PropValue::String(e) if e == "HeroText"));
+    }
+
+    #[rstest]
+    fn visible_elements_send_once() {
+        let runtime = create_runtime();
+        let memory = provide_memory_plausible();
+        let is_visible = create_rw_signal(false);
+        let plausible = super::expect_plausible_context();
+
+        send_once_visible(Signal::from(is_visible), move || {
+            element_event(&plausible, "Newsletter", HashMap::new()).send_local();
+        });
+        assert!(memory.events().is_empty(), "Nothing sent before it is seen");
+
+        is_visible.set(true);
+        is_visible.set(false);
+        is_visible.set(true);
+        runtime.dispose();
+
+        let events = memory.events();
+        assert_eq!(events.len(), 1, "Seen twice, sent once");
+        assert_eq!(events[0].name, "Newsletter");
+    }
+
+    #[rstest]
+    fn mounted_components_wait_to_be_seen() {
+        let runtime = create_runtime();
+        let memory = provide_memory_plausible();
+
+        let html = view! {
+            <PageView/>
+            <TrackElement name="Newsletter"/>
+            <EndPage/>
+        }
+        .into_view()
+        .render_to_string();
+        runtime.dispose();
+
+        assert_eq!(html.matches("<div").count(), 3, "{html}");
+        assert!(memory.events().is_empty(), "The server never sees them");
+    }
+
+    #[rstest]
+    fn mounted_links_keep_their_attributes() {
+        let runtime = create_runtime();
+        let memory = provide_memory_plausible();
+        provide_context(RouterIntegrationContext::new(ServerIntegration {
+            path: "https://get.plai.cards/".into(),
+        }));
+
+        let html = view! {
+            <Router>
+                <A href="https://github.com/plaicards" target="_blank" id="github" attr:rel="noopener">
+                    "GitHub"
+                </A>
+            </Router>
+        }
+        .into_view()
+        .render_to_string();
+        runtime.dispose();
+
+        for attr in [
+            "href=\"https://github.com/plaicards\"",
+            "target=\"_blank\"",
+            "id=\"github\"",
+            "rel=\"noopener\"",
+        ] {
+            assert!(html.contains(attr), "{attr} in {html}");
+        }
+        assert!(memory.events().is_empty(), "Only clicks are tracked");
+    }
+
+    /// Links carry the variant of every experiment, forced ones too
+    #[tokio::test]
+    async fn links_send_the_experiments() {
+        let event = LocalSet::new()
+            .run_until(async {
+                let runtime = create_runtime();
+                let memory = MemoryTracker::default();
+                let plausible = Plausible::with_tracker("get.plai.cards", memory.clone());
+                let (request, ()) = Request::get("/?exp_HeroText=ChatGpt")
+                    .body(())
+                    .expect("Valid request")
+                    .into_parts();
+                provide_context(request);
+                provide_experiments([
+                    Experiment::new(
+                        "HeroText",
+                        [Variant::new("Human", 1), Variant::new("ChatGpt", 1)],
+                    ),
+                    Experiment::new("Cta", [Variant::new("Join", 1)]),
+                ]);
+                let exps = use_experiments().expect("Experiments are provided");
+                // Variants are assigned in a resource
+                tokio::time::timeout(Duration::from_secs(1), async {
+                    while exps.to_plausible().len() < 3 {
+                        yield_now().await;
+                    }
+                })
+                .await
+                .expect("Variants are assigned");
+
+                link_event(&plausible, "https://github.com/plaicards", Some(exps)).send_local();
+                runtime.dispose();
+
+                let mut events = memory.events();
+                assert_eq!(events.len(), 1, "One event is sent");
+                events.remove(0)
+            })
+            .await;
+
+        let props = event.props.expect("Link has props");
+        assert!(
+            matches!(&props["url"], PropValue::String(url) if url == "https://github.com/plaicards")
+        );
+        assert!(matches!(&props["exp_HeroText"], PropValue::String(v) if v == "ChatGpt"));
+        assert!(matches!(
+            props["exp_HeroText_forced"],
+            PropValue::Bool(true)
+        ));
+        assert!(matches!(&props["exp_Cta"], PropValue::String(v) if v == "Join"));
+    }
+
+    #[rstest]
+    fn links_send_their_url() {
+        let event = sent(|p| link_event(p, "https://github.com/plaicards", None).send_local());
+
+        assert_eq!(event.name, "Outbound Link: Click");
+        let props = event.props.expect("Link has props");
+        assert!(
+            matches!(&props["url"], PropValue::String(url) if url == "https://github.com/plaicards")
+        );
+    }
+
+    #[rstest]
+    fn beacons_reach_any_tracker() {
+        let event = sent(|p| link_event(p, "/how-to-plai", None).send_beacon());
+
+        assert_eq!(event.name, "Outbound Link: Click");
+    }
+
+    #[rstest]
+    #[case::main(0, true)]
+    #[case::middle(1, true)]
+    #[case::context_menu(2, false)]
+    fn only_clicks_that_open_the_link_are_tracked(#[case] button: i16, #[case] tracked: bool) {
+        assert_eq!(tracks_click(button), tracked);
+    }
+}
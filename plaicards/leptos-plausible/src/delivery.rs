@@ -0,0 +1,430 @@
+//! What leaves the browser and when.
+//!
+//! Events follow the rules of the official plausible script: nothing is sent
+//! from `localhost` or `file://` pages, when the visitor asks not to be
+//! tracked or when `localStorage.plausible_ignore` is `"true"`. A pageview of
+//! the page that was just counted, like after a re-render, is skipped.
+//!
+//! The rest wait in a bounded [`Outbox`] and are sent in batches. Events that
+//! fail, for example while offline, are sent again later and the oldest are
+//! dropped if too many pile up. Events of a page that is being left go with
+//! `navigator.sendBeacon` instead, which the browser sends after the page is
+//! gone.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Events kept while they cannot be sent
+pub const OUTBOX_CAPACITY: usize = 100;
+/// Events sent in a row every time the outbox is flushed
+const BATCH: usize = 10;
+/// First wait before sending failed events again. It doubles on each failure.
+const FIRST_RETRY: Duration = Duration::from_secs(1);
+const LAST_RETRY: Duration = Duration::from_secs(60);
+/// `localStorage` key that turns tracking off in a browser
+const IGNORE_KEY: &str = "plausible_ignore";
+
+/// Why an event is not sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Skip {
+    Localhost,
+    File,
+    DoNotTrack,
+    Ignored,
+    SamePage,
+}
+
+/// What the browser tells about the page and the visitor
+#[derive(Debug, Clone, Default)]
+pub struct Environment {
+    /// `location.hostname`
+    pub hostname: String,
+    /// `location.protocol`, like `https:`
+    pub protocol: String,
+    /// `navigator.doNotTrack`
+    pub do_not_track: Option<String>,
+    /// `localStorage.plausible_ignore`
+    pub ignore: Option<String>,
+}
+
+impl Environment {
+    /// Why nothing should be sent from here, if anything
+    #[must_use]
+    pub fn skip(&self) -> Option<Skip> {
+        if self.ignore.as_deref() == Some("true") {
+            Some(Skip::Ignored)
+        } else if self.protocol == "file:" {
+            Some(Skip::File)
+        } else if is_local(&self.hostname) {
+            Some(Skip::Localhost)
+        } else if matches!(self.do_not_track.as_deref(), Some("1" | "yes")) {
+            Some(Skip::DoNotTrack)
+        } else {
+            None
+        }
+    }
+}
+
+/// `localhost`, `127.*` or `[::1]`, as the official script checks them
+fn is_local(hostname: &str) -> bool {
+    let loopback_v4 = || {
+        let parts: Vec<&str> = hostname.split('.').collect();
+        (2..=4).contains(&parts.len())
+            && parts[0] == "127"
+            && parts
+                .iter()
+                .all(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()))
+    };
+    matches!(hostname, "localhost" | "[::1]" | "[::]") || loopback_v4()
+}
+
+/// Pageviews already counted, so re-renders and in-page changes of a
+/// single page app are not counted twice
+#[derive(Debug, Default)]
+pub struct Pageviews {
+    last: Option<String>,
+}
+
+impl Pageviews {
+    /// Whether `path` is a different page than the last one counted.
+    /// Remembers it.
+    pub fn is_new(&mut self, path: &str) -> bool {
+        if self.last.as_deref() == Some(path) {
+            return false;
+        }
+        self.last = Some(path.into());
+        true
+    }
+}
+
+/// Bounded queue of events waiting to be sent
+#[derive(Debug)]
+pub struct Outbox<T> {
+    pending: VecDeque<T>,
+    capacity: usize,
+    /// Flushes that failed in a row
+    failures: u32,
+}
+
+impl<T> Outbox<T> {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            pending: VecDeque::new(),
+            capacity,
+            failures: 0,
+        }
+    }
+
+    /// Queues `event`, dropping the oldest one if full. Returns whether one
+    /// was dropped.
+    pub fn push(&mut self, event: T) -> bool {
+        self.pending.push_back(event);
+        self.trim()
+    }
+
+    /// Next events to send, oldest first
+    pub fn batch(&mut self) -> Vec<T> {
+        let n = self.pending.len().min(BATCH);
+        self.pending.drain(..n).collect()
+    }
+
+    /// Every event of the last batch was sent
+    pub fn sent(&mut self) {
+        self.failures = 0;
+    }
+
+    /// Puts back the events of a batch that could not be sent, before the
+    /// ones queued meanwhile. Returns how long to wait before trying again.
+    pub fn retry(&mut self, unsent: Vec<T>) -> Duration {
+        for event in unsent.into_iter().rev() {
+            self.pending.push_front(event);
+        }
+        self.trim();
+        let wait = FIRST_RETRY.saturating_mul(2_u32.saturating_pow(self.failures));
+        self.failures = self.failures.saturating_add(1);
+        wait.min(LAST_RETRY)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    fn trim(&mut self) -> bool {
+        let excess = self.pending.len().saturating_sub(self.capacity);
+        self.pending.drain(..excess);
+        excess > 0
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+pub use self::browser::{beacon, enqueue};
+
+/// The outbox of the page and the requests that empty it
+#[cfg(not(feature = "ssr"))]
+mod browser {
+    use gloo_net::http::Request;
+    use leptos::logging::debug_warn;
+    use leptos::{set_timeout, spawn_local, window};
+    use std::cell::RefCell;
+    use wasm_bindgen::JsValue;
+
+    use super::{Environment, Outbox, Pageviews, Skip, IGNORE_KEY, OUTBOX_CAPACITY};
+    use crate::event::PlausiblePayload;
+
+    struct Pending {
+        endpoint: String,
+        body: PlausiblePayload,
+    }
+
+    struct State {
+        outbox: Outbox<Pending>,
+        pageviews: Pageviews,
+        /// A flush is running or waiting to retry
+        flushing: bool,
+    }
+
+    thread_local! {
+        static STATE: RefCell<State> = RefCell::new(State {
+            outbox: Outbox::new(OUTBOX_CAPACITY),
+            pageviews: Pageviews::default(),
+            flushing: false,
+        });
+    }
+
+    impl Environment {
+        fn current() -> Self {
+            let window = window();
+            let location = window.location();
+            let ignore = window
+                .local_storage()
+                .ok()
+                .flatten()
+                .and_then(|s| s.get_item(IGNORE_KEY).ok().flatten());
+            Self {
+                hostname: location.hostname().unwrap_or_default(),
+                protocol: location.protocol().unwrap_or_default(),
+                do_not_track: Some(window.navigator().do_not_track()),
+                ignore,
+            }
+        }
+    }
+
+    /// Whether `body` should not be sent, logging why
+    fn skipped(body: &PlausiblePayload) -> bool {
+        let skip = Environment::current().skip().or_else(|| {
+            let path = window().location().pathname().unwrap_or_default();
+            let is_new =
+                body.name != "pageview" || STATE.with_borrow_mut(|s| s.pageviews.is_new(&path));
+            (!is_new).then_some(Skip::SamePage)
+        });
+        if let Some(skip) = skip {
+            debug_warn!("Plausible event `{}` not sent: {skip:?}", body.name);
+        }
+        skip.is_some()
+    }
+
+    /// Hands an event for `endpoint` to the browser right away. It is queued
+    /// like the rest if the browser does not take it.
+    pub fn beacon(endpoint: String, body: PlausiblePayload) {
+        if skipped(&body) {
+            return;
+        }
+        let sent = serde_json::to_string(&body).is_ok_and(|json| {
+            window()
+                .navigator()
+                .send_beacon_with_opt_str(&endpoint, Some(&json))
+                .unwrap_or_default()
+        });
+        if !sent {
+            push(endpoint, body);
+        }
+    }
+
+    /// Queues an event for `endpoint` unless it should not be sent
+    pub fn enqueue(endpoint: String, body: PlausiblePayload) {
+        if !skipped(&body) {
+            push(endpoint, body);
+        }
+    }
+
+    fn push(endpoint: String, body: PlausiblePayload) {
+        let start = STATE.with_borrow_mut(|s| {
+            if s.outbox.push(Pending { endpoint, body }) {
+                debug_warn!("Plausible outbox is full. Dropped the oldest event");
+            }
+            !std::mem::replace(&mut s.flushing, true)
+        });
+        if start {
+            spawn_local(flush());
+        }
+    }
+
+    /// Sends batches until the outbox is empty or a request fails
+    async fn flush() {
+        loop {
+            let batch = STATE.with_borrow_mut(|s| s.outbox.batch());
+            if batch.is_empty() {
+                STATE.with_borrow_mut(|s| s.flushing = false);
+                return;
+            }
+
+            let mut events = batch.into_iter();
+            let mut unsent = vec![];
+            while let Some(event) = events.next() {
+                if !post(&event).await {
+                    unsent.push(event);
+                    unsent.extend(events);
+                    break;
+                }
+            }
+
+            if unsent.is_empty() {
+                STATE.with_borrow_mut(|s| s.outbox.sent());
+            } else {
+                let wait = STATE.with_borrow_mut(|s| s.outbox.retry(unsent));
+                set_timeout(|| spawn_local(flush()), wait);
+                return;
+            }
+        }
+    }
+
+    /// Whether `event` is done with. Events rejected by the server are too,
+    /// sending them again would not help.
+    async fn post(event: &Pending) -> bool {
+        if !window().navigator().on_line() {
+            return false;
+        }
+        // FIXME this from_serde should work but returns JSValue(Object(...)) which fails
+        //let body = JsValue::from_serde(&self.body).expect("ERR serializing");
+        let Ok(body) = serde_json::to_string(&event.body) else {
+            return true;
+        };
+
+        // Without CORS the content type is dropped. The proxy does not need it
+        let request = Request::post(&event.endpoint)
+            .referrer_policy(web_sys::ReferrerPolicy::StrictOriginWhenCrossOrigin)
+            .mode(web_sys::RequestMode::NoCors)
+            .header("Cache-Control", "no-cache")
+            .header("Content-Type", "application/json")
+            .body(JsValue::from_str(&body));
+        let Ok(request) = request else {
+            return true;
+        };
+        // Opaque responses of `no-cors` requests have status 0
+        request.send().await.is_ok_and(|r| r.status() < 500)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use std::time::Duration;
+
+    use super::{is_local, Environment, Outbox, Pageviews, Skip};
+
+    #[rstest]
+    #[case("localhost", true)]
+    #[case("127.0.0.1", true)]
+    #[case("127.1", true)]
+    #[case("[::1]", true)]
+    #[case("127.example.com", false)]
+    #[case("get.plai.cards", false)]
+    #[case("10.0.0.1", false)]
+    fn local_hosts(#[case] hostname: &str, #[case] expected: bool) {
+        assert_eq!(is_local(hostname), expected);
+    }
+
+    fn env(hostname: &str, protocol: &str, dnt: Option<&str>, ignore: Option<&str>) -> Environment {
+        Environment {
+            hostname: hostname.into(),
+            protocol: protocol.into(),
+            do_not_track: dnt.map(Into::into),
+            ignore: ignore.map(Into::into),
+        }
+    }
+
+    #[rstest]
+    #[case::public(env("get.plai.cards", "https:", Some("0"), None), None)]
+    #[case::unspecified_dnt(
+        env("get.plai.cards", "https:", Some("unspecified"), Some("false")),
+        None
+    )]
+    #[case::localhost(env("localhost", "http:", None, None), Some(Skip::Localhost))]
+    #[case::file(env("", "file:", None, None), Some(Skip::File))]
+    #[case::do_not_track(
+        env("get.plai.cards", "https:", Some("1"), None),
+        Some(Skip::DoNotTrack)
+    )]
+    #[case::ignored(
+        env("get.plai.cards", "https:", None, Some("true")),
+        Some(Skip::Ignored)
+    )]
+    fn exclusions(#[case] env: Environment, #[case] expected: Option<Skip>) {
+        assert_eq!(env.skip(), expected);
+    }
+
+    #[rstest]
+    fn pageviews_of_the_same_page_are_counted_once() {
+        let mut pageviews = Pageviews::default();
+
+        assert!(pageviews.is_new("/"));
+        assert!(!pageviews.is_new("/"));
+        assert!(pageviews.is_new("/cards"));
+        assert!(pageviews.is_new("/"), "Coming back is a new pageview");
+    }
+
+    #[rstest]
+    fn full_outbox_drops_the_oldest() {
+        let mut outbox = Outbox::new(3);
+
+        let dropped: Vec<bool> = (0..5).map(|i| outbox.push(i)).collect();
+
+        assert_eq!(dropped, [false, false, false, true, true]);
+        assert_eq!(outbox.batch(), [2, 3, 4]);
+        assert!(outbox.is_empty());
+    }
+
+    #[rstest]
+    fn events_are_sent_in_batches() {
+        let mut outbox = Outbox::new(100);
+        for i in 0..25 {
+            outbox.push(i);
+        }
+
+        assert_eq!(outbox.batch().len(), 10);
+        assert_eq!(outbox.batch().len(), 10);
+        assert_eq!(outbox.batch(), (20..25).collect::<Vec<_>>());
+    }
+
+    #[rstest]
+    fn unsent_events_go_first_and_wait_longer_each_time() {
+        let mut outbox = Outbox::new(100);
+        outbox.push(1);
+        outbox.push(2);
+        let batch = outbox.batch();
+        outbox.push(3);
+
+        let waits: Vec<Duration> = (0..8)
+            .map(|_| {
+                let unsent = outbox.batch();
+                outbox.retry(unsent)
+            })
+            .collect();
+        assert_eq!(waits[..3], [1, 2, 4].map(Duration::from_secs));
+        assert_eq!(waits[7], Duration::from_secs(60));
+
+        outbox.retry(batch);
+        assert_eq!(outbox.batch(), [1, 2, 3]);
+        outbox.sent();
+        outbox.push(4);
+        let unsent = outbox.batch();
+        assert_eq!(outbox.retry(unsent), Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,270 @@
+//! How far visitors get into a page: scroll depth, time on page and the steps
+//! of forms they fill.
+//!
+//! ```
+//! # use leptos::*;
+//! # use leptos_plausible::engagement::{track_form_funnel, Engagement, ScrollDepth};
+//! # let runtime = create_runtime();
+//! # let _ = || {
+//! let submitted = track_form_funnel("newsletter", &["email", "name"]);
+//!
+//! view! {
+//!     <ScrollDepth/>
+//!     <Engagement heartbeat_secs=30/>
+//!     <input data-id="plausible-email"/>
+//!     <input data-id="plausible-name"/>
+//!     <button on:click=move |_| submitted()>"Subscribe"</button>
+//! }
+//! # ;
+//! # };
+//! # runtime.dispose();
+//! ```
+use leptos::*;
+use leptos_use::{use_document_visibility, use_interval_fn, use_window_scroll, utils::Pausable};
+use std::collections::HashSet;
+use web_sys::VisibilityState;
+
+use super::components::{expect_plausible_context, use_active_plausible_id};
+use super::event::EventBuilder;
+use super::Plausible;
+
+/// Event of [`ScrollDepth`]
+pub const SCROLL_DEPTH: &str = "scroll_depth";
+/// Event of [`Engagement`]
+pub const ENGAGEMENT: &str = "engagement";
+/// Percentages of the page tracked by [`ScrollDepth`]
+pub const MILESTONES: [u8; 4] = [25, 50, 75, 100];
+
+/// Sends a `scroll_depth` event with the `depth` property the first time the
+/// visitor scrolls past 25, 50, 75 and 100% of the page.
+#[must_use]
+#[component]
+pub fn ScrollDepth() -> impl IntoView {
+    let (_, y) = use_window_scroll();
+    let reached = store_value(0_u8);
+    let plausible = expect_plausible_context();
+
+    create_effect(move |_| {
+        let Some(depth) = page_depth(y.get()) else {
+            return;
+        };
+        for milestone in milestones(depth, reached.get_value()) {
+            scroll_event(&plausible, milestone).send_local();
+            reached.set_value(milestone);
+        }
+    });
+}
+
+/// Seconds between engagement events. `0` would send them nonstop.
+const fn heartbeat(secs: u64) -> u64 {
+    if secs == 0 {
+        1
+    } else {
+        secs
+    }
+}
+
+/// Percentage of the page seen when scrolled to `y`
+fn page_depth(y: f64) -> Option<f64> {
+    let height = f64::from(document().document_element()?.scroll_height());
+    let viewport = window().inner_height().ok()?.as_f64()?;
+    (height > 0.0).then(|| (y + viewport) / height * 100.0)
+}
+
+/// Milestones above `reached` and up to `depth`
+fn milestones(depth: f64, reached: u8) -> impl Iterator<Item = u8> {
+    MILESTONES
+        .into_iter()
+        .filter(move |m| *m > reached && f64::from(*m) <= depth.ceil())
+}
+
+/// Sends an `engagement` event every `heartbeat_secs` the page is visible,
+/// with the `seconds` the visitor has seen it so far. It stops while the tab
+/// is hidden. Heartbeats are at least a second apart.
+#[must_use]
+#[component]
+pub fn Engagement(#[prop(default = 15)] heartbeat_secs: u64) -> impl IntoView {
+    let heartbeat_secs = heartbeat(heartbeat_secs);
+    let visibility = use_document_visibility();
+    let seconds = store_value(0_u64);
+    let plausible = expect_plausible_context();
+
+    let Pausable { pause, resume, .. } = use_interval_fn(
+        move || {
+            seconds.update_value(|s| *s += heartbeat_secs);
+            engagement_event(&plausible, seconds.get_value()).send_local();
+        },
+        heartbeat_secs * 1000,
+    );
+
+    create_effect(move |_| {
+        if visibility.get() == VisibilityState::Hidden {
+            pause();
+        } else {
+            resume();
+        }
+    });
+}
+
+/// Sends an event the first time each of the `steps` of `form` gets the
+/// focus, so they can make a funnel in plausible. Steps are the `data-id` of
+/// the fields without `plausible-`, like in
+/// [`track_active_elements`](super::components::track_active_elements).
+///
+/// Events are called `<form>: <step>` so each one can be a goal, with the
+/// `form`, `step` and its `position` as properties. Call the returned function
+/// when the form is sent, for the `<form>: submit` step.
+pub fn track_form_funnel(form: &str, steps: &[&str]) -> impl Fn() + Clone {
+    let funnel = store_value(Funnel::new(form, steps));
+    let active = use_active_plausible_id();
+    let plausible = expect_plausible_context();
+
+    let focused = plausible.clone();
+    create_effect(move |_| {
+        let Some(id) = active.get() else {
+            return;
+        };
+        if let Some(step) = funnel.try_update_value(|f| f.reach(&id)).flatten() {
+            funnel.with_value(|f| f.event(&focused, &step)).send_local();
+        }
+    });
+
+    move || {
+        if let Some(step) = funnel.try_update_value(|f| f.reach(SUBMIT)).flatten() {
+            funnel
+                .with_value(|f| f.event(&plausible, &step))
+                .send_local();
+        }
+    }
+}
+
+/// Last step of every funnel
+const SUBMIT: &str = "submit";
+
+/// Steps of a form reached so far
+#[derive(Debug, Clone)]
+struct Funnel {
+    form: String,
+    steps: Vec<String>,
+    reached: HashSet<String>,
+}
+
+impl Funnel {
+    fn new(form: &str, steps: &[&str]) -> Self {
+        Self {
+            form: form.into(),
+            steps: steps
+                .iter()
+                .copied()
+                .chain([SUBMIT])
+                .map(Into::into)
+                .collect(),
+            reached: HashSet::new(),
+        }
+    }
+
+    /// The step of `id`, the first time it is reached
+    fn reach(&mut self, id: &str) -> Option<String> {
+        let is_step = self.steps.iter().any(|s| s == id);
+        (is_step && self.reached.insert(id.into())).then(|| id.into())
+    }
+
+    fn event(&self, plausible: &Plausible, step: &str) -> EventBuilder {
+        let position = self
+            .steps
+            .iter()
+            .position(|s| s == step)
+            .unwrap_or_default()
+            + 1;
+        plausible
+            .event(&format!("{}: {step}", self.form))
+            .prop("form", self.form.as_str().into())
+            .prop("step", step.into())
+            .prop(
+                "position",
+                u64::try_from(position).unwrap_or_default().into(),
+            )
+    }
+}
+
+// Events of the components, apart from them so they can be checked without
+// a browser
+
+fn scroll_event(plausible: &Plausible, depth: u8) -> EventBuilder {
+    plausible.event(SCROLL_DEPTH).prop("depth", depth.into())
+}
+
+fn engagement_event(plausible: &Plausible, seconds: u64) -> EventBuilder {
+    plausible.event(ENGAGEMENT).prop("seconds", seconds.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use leptos::create_runtime;
+    use rstest::rstest;
+
+    use super::{
+        engagement_event, heartbeat, milestones, scroll_event, Funnel, ENGAGEMENT, SCROLL_DEPTH,
+    };
+    use crate::tracker::MemoryTracker;
+    use crate::{Plausible, PlausiblePayload, PropValue};
+
+    #[rstest]
+    #[case::top_of_a_long_page(10.0, 0, &[])]
+    #[case::first_screen(40.0, 0, &[25])]
+    #[case::skipped_some(80.0, 25, &[50, 75])]
+    #[case::bottom(99.6, 75, &[100])]
+    #[case::already_sent(60.0, 50, &[])]
+    fn milestones_are_sent_once(#[case] depth: f64, #[case] reached: u8, #[case] expected: &[u8]) {
+        assert_eq!(milestones(depth, reached).collect::<Vec<_>>(), expected);
+    }
+
+    #[rstest]
+    #[case::zero(0, 1)]
+    #[case::one(1, 1)]
+    #[case::default(15, 15)]
+    fn heartbeats_are_at_least_a_second_apart(#[case] requested: u64, #[case] expected: u64) {
+        assert_eq!(heartbeat(requested), expected);
+    }
+
+    #[rstest]
+    fn funnel_steps_are_reached_once() {
+        let mut funnel = Funnel::new("newsletter", &["email", "name"]);
+
+        assert_eq!(funnel.reach("email").as_deref(), Some("email"));
+        assert_eq!(funnel.reach("email"), None);
+        assert_eq!(funnel.reach("search"), None);
+        assert_eq!(funnel.reach("submit").as_deref(), Some("submit"));
+    }
+
+    // Events carry the page they come from only in the browser
+    #[cfg(feature = "ssr")]
+    #[rstest]
+    fn events_carry_their_measure() {
+        let runtime = create_runtime();
+        let memory = MemoryTracker::default();
+        let plausible = Plausible::with_tracker("get.plai.cards", memory.clone());
+        let funnel = Funnel::new("newsletter", &["email", "name"]);
+
+        scroll_event(&plausible, 50).send_local();
+        engagement_event(&plausible, 30).send_local();
+        funnel.event(&plausible, "name").send_local();
+        runtime.dispose();
+
+        let events = memory.events();
+        let [scroll, engagement, step] = &events[..] else {
+            panic!("Three events expected: {events:?}");
+        };
+        let prop =
+            |e: &PlausiblePayload, name: &str| e.props.as_ref().and_then(|p| p.get(name).cloned());
+        assert_eq!(scroll.name, SCROLL_DEPTH);
+        assert!(matches!(prop(scroll, "depth"), Some(PropValue::U8(50))));
+        assert_eq!(engagement.name, ENGAGEMENT);
+        assert!(matches!(
+            prop(engagement, "seconds"),
+            Some(PropValue::U64(30))
+        ));
+        assert_eq!(step.name, "newsletter: name");
+        assert!(matches!(prop(step, "position"), Some(PropValue::U64(2))));
+    }
+}
@@ -6,15 +6,17 @@ use gloo_utils::format::JsValueSerdeExt;
 use leptos::{self, document, logging::debug_warn, spawn_local, window};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use wasm_bindgen::JsValue;
 use web_sys::Navigator;
 
-use super::experiments::{use_experiment_props, Experiment, ExperimentCtx};
+use super::experiments::{use_experiment_props, Experiments};
+use super::tracker::{Endpoint, Tracker};
 
 /// Main intro class handling Plausible events API.
 ///
 /// ```
-/// # use plaicards::web::plausible::Plausible;
+/// # use leptos_plausible::Plausible;
 /// # use std::collections::HashMap;
 /// # async fn no_run() {
 /// let p = Plausible::new("your_domain");
@@ -36,20 +38,40 @@ use super::experiments::{use_experiment_props, Experiment, ExperimentCtx};
 /// Or if you use a self-hosted instance or a proxy url:
 ///
 /// ```
-/// # use plaicards::web::plausible::Plausible;
+/// # use leptos_plausible::Plausible;
 /// # async fn no_run() {
 /// let p = Plausible::new_private("your_domain", "https://your_plausible_instance.com");
 /// # }
 /// ```
 ///
+/// Or through the server of the app, see [`EVENTS_PATH`]:
+///
+/// ```
+/// # use leptos_plausible::Plausible;
+/// let p = Plausible::proxied("your_domain");
+/// ```
+///
+/// Or somewhere else, like memory in tests:
+///
+/// ```
+/// # use leptos_plausible::Plausible;
+/// # use leptos_plausible::tracker::MemoryTracker;
+/// let memory = MemoryTracker::default();
+/// let p = Plausible::with_tracker("your_domain", memory.clone());
+/// ```
+///
 #[derive(Clone, Debug)]
 pub struct Plausible {
     /// This domain name you used when you added your site to your Plausible account
     domain: String,
-    /// Plausibe url for custom instances. By `new()` constructor sets it as `https://plausible.io`
-    plausible_url: String,
+    /// Where events end up. By `new()` constructor they are posted to `https://plausible.io/api/event`
+    tracker: Arc<dyn Tracker>,
 }
 
+/// Path of the server that forwards the events to plausible, so ad blockers
+/// do not drop them and plausible gets the real IP of the visitor
+pub const EVENTS_PATH: &str = "/events";
+
 impl Plausible {
     // Handy methods
     //
@@ -73,79 +95,64 @@ impl Plausible {
 impl Plausible {
     #[must_use]
     pub fn new(domain: &str) -> Self {
-        Self {
-            domain: domain.into(),
-            plausible_url: "https://plausible.io".into(),
-        }
+        Self::new_private(domain, "https://plausible.io")
     }
 
     #[must_use]
     pub fn new_private(domain: &str, instance_url: &str) -> Self {
+        Self::with_tracker(domain, Endpoint::new(&format!("{instance_url}/api/event")))
+    }
+
+    /// Sends the events to [`EVENTS_PATH`] in the same origin
+    #[must_use]
+    pub fn proxied(domain: &str) -> Self {
+        Self::with_tracker(domain, Endpoint::new(EVENTS_PATH))
+    }
+
+    /// Hands the events to `tracker`
+    #[must_use]
+    pub fn with_tracker(domain: &str, tracker: impl Tracker + 'static) -> Self {
         Self {
             domain: domain.into(),
-            plausible_url: instance_url.into(),
+            tracker: Arc::new(tracker),
         }
     }
 
-    fn default_url() -> String {
-        window()
-            .location()
-            .href()
-            .expect("ERROR with plausible event: url")
-    }
-
     fn build_event(&self, name: EventName) -> EventBuilder {
-        let header = PlausibleHeader {
-            user_agent: window()
-                .navigator()
-                .user_agent()
-                .unwrap_or_else(|_| "ERROR".into()),
-            // FIXME this is wrong
-            x_forwarded_for: window()
-                .location()
-                .href()
-                .expect("Error with plausible event"),
-        };
-        let referrer = document().referrer();
+        let (url, referrer) = page();
         let body = PlausiblePayload {
             name: name.into(),
-            url: Self::default_url(),
+            url,
             domain: self.domain.clone(),
-            referrer: if referrer.is_empty() {
-                None
-            } else {
-                Some(referrer)
-            },
+            referrer,
             props: None,
             revenue: None,
             screen_width: None,
         };
 
         EventBuilder {
-            header,
+            tracker: self.tracker.clone(),
             body,
-            plausible_url: self.plausible_url.clone(),
         }
         .experiments()
     }
 }
 
-// From https://github.com/goddtriffin/plausible-rs/ under MIT license
-#[derive(Debug, Clone)]
-#[allow(clippy::module_name_repetitions)]
-struct PlausibleHeader {
-    pub user_agent: String,
-    pub x_forwarded_for: String,
+/// URL and referrer of the page
+#[cfg(not(feature = "ssr"))]
+fn page() -> (String, Option<String>) {
+    let url = window()
+        .location()
+        .href()
+        .expect("ERROR with plausible event: url");
+    let referrer = Some(document().referrer()).filter(|r| !r.is_empty());
+    (url, referrer)
 }
 
-impl PlausibleHeader {
-    #[must_use]
-    pub const fn new(user_agent: String, x_forwarded_for: String) -> Self {
-        Self {
-            user_agent,
-            x_forwarded_for,
-        }
-    }
+/// There is no page while rendering in the server
+#[cfg(feature = "ssr")]
+const fn page() -> (String, Option<String>) {
+    (String::new(), None)
 }
 
 /// Revenue data for this event.
@@ -170,9 +177,10 @@ impl RevenueValue {
     }
 }
 
+/// Body of the plausible events API
 // From https://github.com/goddtriffin/plausible-rs/ under MIT license
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct PlausiblePayload {
+pub struct PlausiblePayload {
     /// Name of the event
     pub name: String,
     /// Domain name of the site in plausible
@@ -220,8 +228,7 @@ impl Into<String> for EventName {
 #[derive(Debug, Clone)]
 #[allow(clippy::module_name_repetitions)]
 pub struct EventBuilder {
-    plausible_url: String,
-    header: PlausibleHeader,
+    tracker: Arc<dyn Tracker>,
     body: PlausiblePayload,
 }
 
@@ -260,18 +267,19 @@ impl EventBuilder {
         self
     }
 
-    /// Adds experiment properties. See [`ExperimentCtx`]
+    /// Adds the variants of every experiment in context. See [`Experiments`]
     ///
-    /// WARNING: Does not run well inside `spawn_local` or `on:` functions
-    // FIXME
+    /// WARNING: There is no context inside `spawn_local` or `on:` functions.
+    /// Keep [`use_experiments`](super::experiments::use_experiments) from the component and use [`Self::set_experiments`]
     pub fn experiments(self) -> Self {
         match use_experiment_props() {
             Some(props) => self.props(props),
             None => self,
         }
     }
-    pub fn set_experiment(self, experiment: Option<ExperimentCtx>) -> Self {
-        match experiment {
+
+    pub fn set_experiments(self, experiments: Option<Experiments>) -> Self {
+        match experiments {
             Some(e) => self.props(e.to_plausible()),
             None => self,
         }
@@ -283,40 +291,44 @@ impl EventBuilder {
     /// You can send just `/user` to avoid sending sensitive data and improve
     /// Top Pages statistics.
     pub fn url(&mut self, url: &str) -> &mut Self {
-        let url_with_params = format!(
+        #[cfg(not(feature = "ssr"))]
+        let url = &format!(
             "{url}{}",
             window().location().search().expect("ERR with plausible")
         );
-        self.body.url = url_with_params;
+        self.body.url = url.into();
         self
     }
 
+    /// Hands the event to the tracker of the [`Plausible`] that built it.
+    /// In the server only a [`MemoryTracker`](super::tracker::MemoryTracker)
+    /// or a [`ConsoleTracker`](super::tracker::ConsoleTracker) get them, the
+    /// server sends its own events with [`Analytics`](super::ssr::Analytics)
+    #[allow(clippy::unused_async)]
     pub async fn send(self) {
-        // TODO disable sending event if localhost like done in the official script
-        // TODO don't send pageview event if already visited before (window.history)
-        // FIXME this from_serde should work but returns JSValue(Object(...)) which fails
-        //let body = JsValue::from_serde(&self.body).expect("ERR serializing");
-        let body = JsValue::from_str(&serde_json::to_string(&self.body).expect("ERR"));
-
-        let resp = Request::post(&format!("{}/api/event", self.plausible_url))
-            .referrer_policy(web_sys::ReferrerPolicy::StrictOriginWhenCrossOrigin)
-            .mode(web_sys::RequestMode::NoCors)
-            .header("Cache-Control", "no-cache")
-            .header("Content-Type", "application/json")
-            .body(body);
-
-        let resp = resp.expect("Error building the body").send().await;
+        self.send_local();
     }
 
-    /// Creates a `spawn_local` thread and sends the event.
+    /// Sends the event without waiting.
     ///
-    /// Use this function instead of [`send`] for simplicity
-    /// unless you want to do more things in the local thread.
+    /// Use this function instead of [`send`] for simplicity.
+    /// Events are queued and sent in the background anyway.
     pub fn send_local(self) {
         debug_warn!("Preparing plausible event: `{:?}`", &self);
-        spawn_local(async move {
-            self.send().await;
-        });
+        self.tracker.track(self.body);
+    }
+
+    /// Sends the event with `navigator.sendBeacon`, so it is not lost if the
+    /// page is left right after, like when a link is followed
+    pub fn send_beacon(self) {
+        debug_warn!("Preparing plausible beacon: `{:?}`", &self);
+        self.tracker.beacon(self.body);
+    }
+}
+
+impl Tracker for Plausible {
+    fn track(&self, event: PlausiblePayload) {
+        self.tracker.track(event);
     }
 }
 
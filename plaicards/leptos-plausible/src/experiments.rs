@@ -0,0 +1,662 @@
+//! Support for using AB tests (experiments) where the system randomly selects
+//! a given Variant to show the user.
+//!
+//!
+//!
+use std::collections::btree_map::IntoValues;
+use std::collections::HashMap;
+
+use leptos::html::{div, P};
+use leptos::logging::{debug_warn, log};
+use rand::distributions::WeightedIndex;
+/// Run A/B experiments and evaluate what are the best changes for your website.
+///
+use rand::prelude::*;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use super::components::TrackElement;
+use super::event::PropValue;
+
+/// Represents the variant of an experiment with its custom name and weight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Variant {
+    pub name: String,
+    pub weight: u16,
+}
+
+impl Variant {
+    #[must_use]
+    pub fn new(name: &str, weight: u16) -> Self {
+        Self {
+            name: name.into(),
+            weight,
+        }
+    }
+}
+
+/// Cookie with the variant assigned to a visitor, followed by the name of
+/// the experiment
+const COOKIE_PREFIX: &str = "plai_exp_";
+/// Query parameter forcing a variant, followed by the name of the experiment.
+/// For QA: `/?exp_HeroText=ChatGpt`
+const PARAM_PREFIX: &str = "exp_";
+/// Visitors keep their variant for a year
+const COOKIE_MAX_AGE_SECS: u32 = 365 * 24 * 60 * 60;
+/// Event of [`ExperimentView`]
+pub const EXPERIMENT_VIEW: &str = "ExperimentView";
+/// Prop of the [`EXPERIMENT_VIEW`] event with the name of the experiment on
+/// screen. The `exp_*` props have the variants of every experiment, seen or
+/// not.
+pub const EXPERIMENT_PROP: &str = "experiment";
+
+/// Define a new weighted A/B/n test
+///
+/// This class is used to define the odds and pass it around
+/// as context so it gets propagated from (ideally) the top of the
+/// structure.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "ExperimentDef")]
+pub struct Experiment {
+    pub name: String,
+    variants: Vec<Variant>,
+    /// Selected variant
+    pub selected: usize,
+    /// The variant comes from the URL, not from chance. It is not kept.
+    pub forced: bool,
+}
+
+/// Fields of an [`Experiment`] before they are checked
+#[derive(Deserialize)]
+struct ExperimentDef {
+    name: String,
+    variants: Vec<Variant>,
+    selected: usize,
+    forced: bool,
+}
+
+impl TryFrom<ExperimentDef> for Experiment {
+    type Error = String;
+
+    fn try_from(def: ExperimentDef) -> core::result::Result<Self, Self::Error> {
+        if !def.variants.iter().any(|v| v.weight > 0) {
+            return Err(format!(
+                "Experiment {} needs a variant with weight",
+                def.name
+            ));
+        }
+        if def.selected >= def.variants.len() {
+            return Err(format!(
+                "Experiment {} has no variant {}",
+                def.name, def.selected
+            ));
+        }
+        Ok(Self {
+            name: def.name,
+            variants: def.variants,
+            selected: def.selected,
+            forced: def.forced,
+        })
+    }
+}
+
+impl Experiment {
+    /// # Panics
+    ///
+    /// If no variant has weight, so none can be chosen
+    #[must_use]
+    pub fn new(name: &str, variants: impl IntoIterator<Item = Variant>) -> Self {
+        let variants: Vec<Variant> = variants.into_iter().collect();
+        assert!(
+            variants.iter().any(|v| v.weight > 0),
+            "Experiment {name} needs a variant with weight"
+        );
+        Self {
+            variants,
+            name: name.into(),
+            selected: 0,
+            forced: false,
+        }
+    }
+
+    #[must_use]
+    pub fn variants(&self) -> &[Variant] {
+        &self.variants
+    }
+
+    /// Choose the variant to show given the weights.
+    ///
+    /// It's separated from the init since this needs to happen in a leptos' [`local_resource`]
+    /// to avoud hydration bugs
+    pub fn choose(&mut self) {
+        // Using RNG in SSR will cause hydration bugs unless it's within a `local_resource`
+        let weights = self.variants.iter().map(|v| u32::from(v.weight));
+        let Ok(dist) = WeightedIndex::new(weights) else {
+            debug_warn!(
+                "Experiment {} has no weights. Showing its first variant",
+                self.name
+            );
+            self.selected = 0;
+            return;
+        };
+        let mut rng = thread_rng();
+        self.selected = dist.sample(&mut rng);
+    }
+
+    /// Selects the variant called `name`. Returns `false` if there is none.
+    pub fn select(&mut self, name: &str) -> bool {
+        match self.variants.iter().position(|v| v.name == name) {
+            Some(i) => {
+                self.selected = i;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Picks the variant of a visitor: the `forced` one for QA, else the one
+    /// `stored` from a previous visit, else one by chance. Names that are not
+    /// variants of the experiment anymore are ignored.
+    pub fn assign(&mut self, forced: Option<&str>, stored: Option<&str>) {
+        self.forced = forced.is_some_and(|name| self.select(name));
+        if self.forced || stored.is_some_and(|name| self.select(name)) {
+            return;
+        }
+        self.choose();
+    }
+
+    /// Returns the choosen variant
+    #[must_use]
+    pub fn variant(&self) -> &Variant {
+        &self.variants[self.selected]
+    }
+
+    /// Name of the cookie that keeps the variant of the visitor
+    #[must_use]
+    pub fn cookie_name(&self) -> String {
+        format!("{COOKIE_PREFIX}{}", self.name)
+    }
+
+    /// Name of the query parameter that forces a variant
+    #[must_use]
+    pub fn param_name(&self) -> String {
+        format!("{PARAM_PREFIX}{}", self.name)
+    }
+
+    /// Props of the events seen with this experiment. Views of a variant
+    /// forced for QA are marked so they can be left out of the results.
+    #[must_use]
+    pub fn to_plausible(&self) -> HashMap<String, PropValue> {
+        let mut props = HashMap::from([(
+            format!("exp_{}", self.name),
+            self.variant().name.clone().into(),
+        )]);
+        if self.forced {
+            props.insert(format!("exp_{}_forced", self.name), true.into());
+        }
+        props
+    }
+
+    /// `document.cookie` value that keeps the selected variant
+    #[must_use]
+    pub fn cookie(&self) -> String {
+        format!(
+            "{}={}; Path=/; Max-Age={COOKIE_MAX_AGE_SECS}; SameSite=Lax",
+            self.cookie_name(),
+            self.variant().name
+        )
+    }
+}
+
+impl Default for Experiment {
+    /// Inits a default experiment with A and B variants with the same weight.
+    fn default() -> Self {
+        Self::new("Experiment", [Variant::new("A", 1), Variant::new("B", 1)])
+    }
+}
+
+/// Value of `name` in `key=value` pairs, like a query string (`&`) or a
+/// cookie header (`;`)
+fn find_value<'a>(pairs: &'a str, separator: char, name: &str) -> Option<&'a str> {
+    pairs
+        .split(separator)
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// Query string and cookies of the request being rendered
+#[cfg(feature = "ssr")]
+fn query_and_cookies() -> (String, String) {
+    use axum::http::{header::COOKIE, request::Parts};
+
+    let Some(parts) = use_context::<Parts>() else {
+        return (String::new(), String::new());
+    };
+    let query = parts.uri.query().unwrap_or_default().to_string();
+    let cookies: Vec<&str> = parts
+        .headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|c| c.to_str().ok())
+        .collect();
+    (query, cookies.join("; "))
+}
+
+/// Query string and cookies of the page
+#[cfg(not(feature = "ssr"))]
+fn query_and_cookies() -> (String, String) {
+    use wasm_bindgen::JsCast;
+    use web_sys::HtmlDocument;
+
+    let query = window().location().search().unwrap_or_default();
+    let cookies = document()
+        .dyn_ref::<HtmlDocument>()
+        .and_then(|d| d.cookie().ok())
+        .unwrap_or_default();
+    (query.trim_start_matches('?').to_string(), cookies)
+}
+
+/// Keeps the assignment for the next visits
+#[cfg(not(feature = "ssr"))]
+fn remember(exp: &Experiment) {
+    use wasm_bindgen::JsCast;
+    use web_sys::HtmlDocument;
+
+    if let Some(doc) = document().dyn_ref::<HtmlDocument>() {
+        if doc.set_cookie(&exp.cookie()).is_err() {
+            debug_warn!("Cannot keep the variant of experiment {}", exp.name);
+        }
+    }
+}
+
+/// Effects only run in the browser
+#[cfg(feature = "ssr")]
+const fn remember(_exp: &Experiment) {}
+
+/// Assigns a variant of `exp` to the visitor, sticky across visits.
+///
+/// The server reads the assignment from the cookie of the request, so the
+/// rendered page already shows the right variant, and the browser writes the
+/// cookie once the page is loaded. A variant forced with the `exp_<name>`
+/// query parameter wins and is never written.
+#[must_use]
+pub fn create_experiment(exp: Experiment) -> ExperimentCtx {
+    let exp = store_value(exp);
+    let assigned = create_resource(
+        || (),
+        move |()| {
+            // The request is only in the context while the fetcher is called
+            let (query, cookies) = query_and_cookies();
+            let mut e = exp.get_value();
+            e.assign(
+                find_value(&query, '&', &e.param_name()),
+                find_value(&cookies, ';', &e.cookie_name()),
+            );
+            async move { e }
+        },
+    );
+    create_effect(move |_| {
+        if let Some(e) = assigned.get().filter(|e| !e.forced) {
+            remember(&e);
+        }
+    });
+    ExperimentCtx(assigned)
+}
+
+/// Experiments running in the app, by name.
+///
+/// Provide it once at the top with [`provide_experiments`] so every
+/// [`ExperimentView`] finds its experiment and every plausible event carries
+/// the variants of all of them.
+#[derive(Copy, Clone, Debug)]
+pub struct Experiments(StoredValue<HashMap<String, ExperimentCtx>>);
+
+impl Experiments {
+    /// Starts `exp`, replacing any experiment with the same name
+    pub fn register(&self, exp: Experiment) -> ExperimentCtx {
+        let name = exp.name.clone();
+        let ctx = create_experiment(exp);
+        self.0.update_value(|exps| {
+            exps.insert(name, ctx);
+        });
+        ctx
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<ExperimentCtx> {
+        self.0.with_value(|exps| exps.get(name).copied())
+    }
+
+    /// Props with the variant of every experiment already assigned
+    #[must_use]
+    pub fn to_plausible(&self) -> HashMap<String, PropValue> {
+        self.0.with_value(|exps| {
+            exps.values()
+                .flat_map(ExperimentCtx::to_plausible)
+                .collect()
+        })
+    }
+}
+
+/// Starts `experiments` and provides them as context, see [`Experiments`].
+/// It should usually be near the root of your application.
+pub fn provide_experiments(experiments: impl IntoIterator<Item = Experiment>) {
+    let registry = Experiments(store_value(HashMap::new()));
+    for exp in experiments {
+        registry.register(exp);
+    }
+    provide_context(registry);
+}
+
+/// A component that will show the choosen variant of the experiment called
+/// `name`, registered with [`provide_experiments`].
+///
+/// `variants` has a view for each variant of the experiment, in the same
+/// order. It will always send an event called `ExperimentView`, with the
+/// name of the experiment in the `experiment` prop, when any of the variants
+/// appears in the viewport (see
+/// [`leptos_use::use_element_visibility`](https://leptos-use.rs/elements/use_element_visibility.html)).
+/// The first view is shown if there is no such experiment.
+///
+/// ```rust
+/// # use leptos::*;
+/// # use leptos_plausible::experiments::{provide_experiments, Variant, Experiment, ExperimentView};
+/// # let runtime = create_runtime();
+/// # // create_runtime fails when trying to use create_resource
+/// # if false {
+///
+/// #[component]
+/// fn ComponentA() -> impl IntoView {
+///     view! { <div> "I'm A"</div> }
+/// }
+/// #[component]
+/// fn ComponentB() -> impl IntoView {
+///     view! { <div> "I'm B"</div> }
+/// }
+/// #[component]
+/// fn ComponentC() -> impl IntoView {
+///     view! { <div> "I'm C"</div> }
+/// }
+///
+/// provide_experiments([Experiment::new(
+///     "Experiment",
+///     [Variant::new("A", 1), Variant::new("B", 8), Variant::new("C", 1)],
+/// )]);
+///
+/// view! {
+///   <ExperimentView
+///     name="Experiment"
+///     variants=vec![ComponentA.into(), ComponentB.into(), ComponentC.into()]
+///   />
+/// }
+/// # ;
+/// # }
+/// # runtime.dispose();
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "trace", skip_all)
+)]
+#[must_use]
+#[component]
+pub fn ExperimentView(#[prop(into)] name: String, variants: Vec<ViewFn>) -> impl IntoView {
+    let exp = use_experiment(&name);
+    if exp.is_none() {
+        debug_warn!("Experiment {name} is not registered");
+    }
+
+    // Store the views so we can "Copy" its references within other components
+    let views = store_value(variants);
+    let seen = HashMap::from([(EXPERIMENT_PROP.to_string(), PropValue::from(name))]);
+
+    view! {
+      <Suspense fallback=|| ()>
+        <TrackElement name=EXPERIMENT_VIEW props=seen/>
+        {move || {
+            let selected = exp.and_then(|e| e.0.get()).map_or(0, |e| e.selected);
+            views.with_value(|v| v.get(selected).map(ViewFn::run))
+        }}
+      </Suspense>
+    }
+}
+
+/// Retrieve the experiments from leptos context.
+///
+/// Keep the result instead of calling it from event handlers, where there is
+/// no context.
+#[must_use]
+pub fn use_experiments() -> Option<Experiments> {
+    use_context::<Experiments>()
+}
+
+/// Retrieve the experiment called `name` from leptos context.
+#[must_use]
+pub fn use_experiment(name: &str) -> Option<ExperimentCtx> {
+    use_experiments().and_then(|exps| exps.get(name))
+}
+
+#[must_use]
+pub fn use_experiment_props() -> Option<HashMap<String, PropValue>> {
+    use_experiments().map(|e| e.to_plausible())
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct ExperimentCtx(pub Resource<(), Experiment>);
+
+impl ExperimentCtx {
+    /// Props of the assigned variant, see [`Experiment::to_plausible`]
+    pub fn to_plausible(&self) -> HashMap<String, PropValue> {
+        self.0.with(|e| {
+            e.as_ref()
+                .map_or_else(HashMap::new, Experiment::to_plausible)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::rstest;
+
+    #[component]
+    fn ComponentA() -> impl IntoView {
+        view! { <div>"I'm A"</div> }
+    }
+    #[component]
+    fn ComponentB() -> impl IntoView {
+        view! { <div>"I'm B"</div> }
+    }
+    #[component]
+    fn ComponentC() -> impl IntoView {
+        view! { <div>"I'm C"</div> }
+    }
+
+    #[test]
+    fn build_variant() {
+        let v = Variant::new("A", 3);
+
+        assert_eq!(v.name, "A");
+        assert_eq!(v.weight, 3);
+    }
+
+    #[rstest]
+    #[case::no_weight(
+        r#"{"name":"E","variants":[{"name":"A","weight":0}],"selected":0,"forced":false}"#
+    )]
+    #[case::no_variants(r#"{"name":"E","variants":[],"selected":0,"forced":false}"#)]
+    #[case::selected_out_of_range(
+        r#"{"name":"E","variants":[{"name":"A","weight":1}],"selected":1,"forced":false}"#
+    )]
+    fn invalid_experiments_do_not_deserialize(#[case] json: &str) {
+        assert!(serde_json::from_str::<Experiment>(json).is_err());
+    }
+
+    #[test]
+    fn choosing_without_weights_shows_the_first_variant() {
+        let mut e = Experiment {
+            name: "Experiment".into(),
+            variants: vec![Variant::new("A", 0), Variant::new("B", 0)],
+            selected: 1,
+            forced: false,
+        };
+
+        e.choose();
+
+        assert_eq!(e.variant().name, "A");
+    }
+
+    #[test]
+    fn experiments_survive_a_roundtrip() {
+        let mut e = Experiment::default();
+        e.select("B");
+
+        let json = serde_json::to_string(&e).expect("Serializable");
+        let back: Experiment = serde_json::from_str(&json).expect("Valid experiment");
+
+        assert_eq!(back.variant().name, "B");
+    }
+
+    #[test]
+    fn weighted_experiments() {
+        let mut e = Experiment::new(
+            "Experiment",
+            [
+                Variant::new("A", 1),
+                Variant::new("B", 8),
+                Variant::new("C", 1),
+            ],
+        );
+
+        let choices: Vec<usize> = (0..1000)
+            .map(|_| {
+                e.choose();
+                e.selected
+            })
+            .collect();
+
+        let count = |i: usize| choices.iter().filter(|v| **v == i).count();
+
+        assert!(
+            (50..=150).contains(&count(0)),
+            "Weights do not seem to work"
+        );
+        assert!(
+            (700..=900).contains(&count(1)),
+            "Weights do not seem to work"
+        );
+        assert!(
+            (50..=150).contains(&count(2)),
+            "Weights do not seem to work"
+        );
+    }
+
+    #[test]
+    fn variants_without_weight_are_never_chosen() {
+        let mut e = Experiment::new("Experiment", [Variant::new("A", 0), Variant::new("B", 1)]);
+
+        for _ in 0..100 {
+            e.choose();
+            assert_eq!(e.variant().name, "B");
+        }
+    }
+
+    #[rstest]
+    #[case::forced_wins(Some("C"), Some("B"), "C", true)]
+    #[case::stored_is_kept(None, Some("B"), "B", false)]
+    #[case::unknown_forced_is_ignored(Some("Z"), Some("B"), "B", false)]
+    fn assign(
+        #[case] forced: Option<&str>,
+        #[case] stored: Option<&str>,
+        #[case] expected: &str,
+        #[case] is_forced: bool,
+    ) {
+        let mut e = Experiment::new(
+            "Experiment",
+            [
+                Variant::new("A", 1),
+                Variant::new("B", 1),
+                Variant::new("C", 1),
+            ],
+        );
+
+        e.assign(forced, stored);
+
+        assert_eq!(e.variant().name, expected);
+        assert_eq!(e.forced, is_forced);
+    }
+
+    #[test]
+    fn unknown_stored_variant_is_chosen_again() {
+        let mut e = Experiment::new("Experiment", [Variant::new("A", 0), Variant::new("B", 1)]);
+        e.selected = 0;
+
+        e.assign(None, Some("Removed"));
+
+        assert_eq!(e.variant().name, "B");
+        assert!(!e.forced);
+    }
+
+    #[rstest]
+    #[case::query(
+        "exp_Other=A&exp_HeroText=ChatGpt",
+        '&',
+        "exp_HeroText",
+        Some("ChatGpt")
+    )]
+    #[case::cookie(
+        "theme=dark; plai_exp_HeroText=Human",
+        ';',
+        "plai_exp_HeroText",
+        Some("Human")
+    )]
+    #[case::prefix_is_not_a_match("plai_exp_HeroTextOld=Human", ';', "plai_exp_HeroText", None)]
+    #[case::empty("", '&', "exp_HeroText", None)]
+    fn find_values(
+        #[case] pairs: &str,
+        #[case] separator: char,
+        #[case] name: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(find_value(pairs, separator, name), expected);
+    }
+
+    #[test]
+    fn props_of_several_experiments() {
+        let mut hero = Experiment::new(
+            "HeroText",
+            [Variant::new("Human", 1), Variant::new("ChatGpt", 1)],
+        );
+        hero.assign(Some("ChatGpt"), None);
+        let mut cta = Experiment::new("Cta", [Variant::new("Join", 1), Variant::new("Play", 1)]);
+        cta.assign(None, Some("Play"));
+
+        let props: HashMap<String, PropValue> = [hero, cta]
+            .iter()
+            .flat_map(Experiment::to_plausible)
+            .collect();
+
+        assert_eq!(props.len(), 3);
+        assert!(matches!(&props["exp_HeroText"], PropValue::String(v) if v == "ChatGpt"));
+        assert!(matches!(
+            props["exp_HeroText_forced"],
+            PropValue::Bool(true)
+        ));
+        assert!(matches!(&props["exp_Cta"], PropValue::String(v) if v == "Play"));
+    }
+
+    #[test]
+    fn cookie_keeps_the_variant() {
+        let mut e = Experiment::new(
+            "HeroText",
+            [Variant::new("Human", 1), Variant::new("ChatGpt", 1)],
+        );
+        assert!(e.select("ChatGpt"));
+
+        assert_eq!(
+            e.cookie(),
+            "plai_exp_HeroText=ChatGpt; Path=/; Max-Age=31536000; SameSite=Lax"
+        );
+    }
+}
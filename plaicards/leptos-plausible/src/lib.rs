@@ -0,0 +1,30 @@
+//! [Plausible](https://plausible.io) analytics for leptos without its script:
+//! pageviews, custom events, tracked links and A/B/n experiments.
+//!
+//! Enable the feature of your rendering mode: `csr`, `hydrate` or `ssr`.
+//! Server rendered apps call [`components::provide_plausible_context`] with
+//! [`PlausibleSettings`] in the context and forward the events with
+//! `ssr::handler` at [`EVENTS_PATH`]. Apps without a server provide where
+//! the events go themselves:
+//!
+//! ```
+//! # use leptos_plausible::components::provide_plausible;
+//! # use leptos_plausible::Plausible;
+//! # let runtime = leptos::create_runtime();
+//! provide_plausible(Plausible::new("your_domain"));
+//! # runtime.dispose();
+//! ```
+#![allow(clippy::wildcard_imports)]
+
+pub mod components;
+pub mod delivery;
+pub mod engagement;
+mod event;
+pub mod experiments;
+pub mod results;
+#[cfg(feature = "ssr")]
+pub mod ssr;
+pub mod tracker;
+
+pub use components::PlausibleSettings;
+pub use event::{Plausible, PlausiblePayload, PropValue, EVENTS_PATH};
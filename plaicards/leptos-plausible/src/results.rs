@@ -0,0 +1,660 @@
+//! Results of the experiments from the stats of plausible.
+//!
+//! Plausible links the props of an event to its goal in the
+//! [Stats API](https://plausible.io/docs/stats-api) breakdowns, so query the
+//! visitors of every goal by variant of the experiment and by the experiment
+//! on screen, and leave out the forced variants:
+//!
+//! ```json
+//! {
+//!   "site_id": "your_domain",
+//!   "metrics": ["visitors"],
+//!   "date_range": "all",
+//!   "dimensions": ["event:goal", "event:props:exp_HeroText", "event:props:experiment"],
+//!   "filters": [["is_not", "event:props:exp_HeroText_forced", ["true"]]]
+//! }
+//! ```
+//!
+//! Parse the response with [`Breakdown::from_json`], or the same table as CSV
+//! with [`Breakdown::from_csv`], and compare every variant with the control in
+//! a [`Report`]. The visitors of a variant are the ones that saw it, the ones
+//! with the `ExperimentView` goal of [`ExperimentView`](crate::experiments::ExperimentView)
+//! for that experiment. Every event carries the variants of all the
+//! experiments, so without the `experiment` prop the visitors of any other
+//! experiment count too.
+use serde::Deserialize;
+use std::fmt;
+use thiserror::Error;
+
+use crate::experiments::{EXPERIMENT_PROP, EXPERIMENT_VIEW};
+
+/// Goal of the visitors that saw an experiment
+pub const EXPOSURE_GOAL: &str = EXPERIMENT_VIEW;
+
+/// `z` of a 95% confidence interval
+const Z_95: f64 = 1.96;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ResultsError {
+    #[error("Invalid JSON export: {0}")]
+    Json(String),
+    #[error("The export has no {0} column")]
+    MissingColumn(String),
+    #[error("Invalid row {line}: {reason}")]
+    InvalidRow { line: usize, reason: String },
+    #[error("No visitor saw the variant {0}")]
+    UnknownControl(String),
+}
+
+type Result<T> = core::result::Result<T, ResultsError>;
+
+/// Visitors of a goal that got a variant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub goal: String,
+    pub variant: String,
+    /// Experiment on screen of the exposures, `None` if the export lacks it
+    pub experiment: Option<String>,
+    pub visitors: u64,
+}
+
+impl Row {
+    /// Whether the visitors saw `experiment` with the `exposure` goal
+    fn saw(&self, exposure: &str, experiment: &str) -> bool {
+        self.goal == exposure && self.experiment.as_deref().is_none_or(|e| e == experiment)
+    }
+}
+
+/// Dimension, or column, with the variant of `experiment`
+fn variant_dimension(experiment: &str) -> String {
+    format!("event:props:exp_{experiment}")
+}
+
+/// Dimension, or column, with the experiment on screen
+fn experiment_dimension() -> String {
+    format!("event:props:{EXPERIMENT_PROP}")
+}
+
+/// Visitors by goal and variant of one experiment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Breakdown(pub Vec<Row>);
+
+#[derive(Deserialize)]
+struct QueryResponse {
+    results: Vec<QueryResult>,
+    query: Option<Query>,
+}
+
+#[derive(Deserialize)]
+struct QueryResult {
+    dimensions: Vec<String>,
+    metrics: Vec<u64>,
+}
+
+#[derive(Deserialize)]
+struct Query {
+    dimensions: Vec<String>,
+}
+
+impl Breakdown {
+    /// Reads a Stats API response with the `event:goal` and
+    /// `event:props:exp_<experiment>` dimensions and the `visitors` metric,
+    /// and the `event:props:experiment` dimension if it has it. Responses
+    /// without their query have the goal and the variant first.
+    ///
+    /// # Errors
+    ///
+    /// * `Json` if it is not a response of the Stats API
+    /// * `MissingColumn` if the query lacks the variant of `experiment`
+    /// * `InvalidRow` if a result lacks a dimension or the metric
+    pub fn from_json(json: &str, experiment: &str) -> Result<Self> {
+        let response: QueryResponse =
+            serde_json::from_str(json).map_err(|e| ResultsError::Json(e.to_string()))?;
+        let (goal, variant, shown) = match response.query {
+            Some(q) => {
+                let position = |name: &str| q.dimensions.iter().position(|d| d == name);
+                let dimension = variant_dimension(experiment);
+                (
+                    position("event:goal").unwrap_or(0),
+                    position(&dimension).ok_or(ResultsError::MissingColumn(dimension))?,
+                    position(&experiment_dimension()),
+                )
+            }
+            None => (0, 1, None),
+        };
+        response
+            .results
+            .into_iter()
+            .enumerate()
+            .map(|(i, r)| {
+                let invalid = |reason: &str| ResultsError::InvalidRow {
+                    line: i + 1,
+                    reason: reason.into(),
+                };
+                Ok(Row {
+                    goal: r
+                        .dimensions
+                        .get(goal)
+                        .ok_or_else(|| invalid("no goal"))?
+                        .clone(),
+                    variant: r
+                        .dimensions
+                        .get(variant)
+                        .ok_or_else(|| invalid("no variant"))?
+                        .clone(),
+                    experiment: shown
+                        .map(|i| r.dimensions.get(i).ok_or_else(|| invalid("no experiment")))
+                        .transpose()?
+                        .cloned(),
+                    visitors: *r.metrics.first().ok_or_else(|| invalid("no visitors"))?,
+                })
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+
+    /// Reads a CSV with a header and the `goal`, `variant` and `visitors`
+    /// columns, and the `experiment` one if it has it. The columns of
+    /// plausible exports work too: `name` or `event:goal` for the goal,
+    /// `event:props:exp_<experiment>` or `value` for the variant and
+    /// `event:props:experiment` for the experiment.
+    ///
+    /// # Errors
+    ///
+    /// * `MissingColumn` if the header lacks one of them, or the variant of
+    ///   `experiment` when it has the ones of other experiments
+    /// * `InvalidRow` if a row lacks a field or the visitors are not a number
+    pub fn from_csv(csv: &str, experiment: &str) -> Result<Self> {
+        let mut lines = csv.lines().filter(|l| !l.trim().is_empty());
+        let header = fields(lines.next().unwrap_or_default());
+        let position = |is: &dyn Fn(&str) -> bool| header.iter().position(|h| is(h));
+        let column = |name: &str, is: &dyn Fn(&str) -> bool| {
+            position(is).ok_or_else(|| ResultsError::MissingColumn(name.into()))
+        };
+        let goal = column("goal", &|h| matches!(h, "goal" | "name" | "event:goal"))?;
+        let dimension = variant_dimension(experiment);
+        let variant = match position(&|h| h == dimension) {
+            Some(variant) => variant,
+            // A single experiment, unless there are the variants of others
+            None if !header.iter().any(|h| h.starts_with("event:props:exp_")) => {
+                column("variant", &|h| matches!(h, "variant" | "value"))?
+            }
+            None => return Err(ResultsError::MissingColumn(dimension)),
+        };
+        let shown = position(&|h| h == EXPERIMENT_PROP || h == experiment_dimension());
+        let visitors = column("visitors", &|h| h == "visitors")?;
+
+        lines
+            .enumerate()
+            .map(|(i, line)| {
+                let line_number = i + 2;
+                let invalid = |reason: String| ResultsError::InvalidRow {
+                    line: line_number,
+                    reason,
+                };
+                let row = fields(line);
+                let field = |i: usize| {
+                    row.get(i)
+                        .cloned()
+                        .ok_or_else(|| invalid(format!("no column {}", i + 1)))
+                };
+                Ok(Row {
+                    goal: field(goal)?,
+                    variant: field(variant)?,
+                    experiment: shown.map(&field).transpose()?,
+                    visitors: field(visitors)?
+                        .parse()
+                        .map_err(|_| invalid("visitors is not a number".into()))?,
+                })
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+
+    /// Visitors of the rows of `variant` that `count`
+    fn visitors(&self, count: impl Fn(&Row) -> bool, variant: &str) -> u64 {
+        self.0
+            .iter()
+            .filter(|r| r.variant == variant && count(r))
+            .map(|r| r.visitors)
+            .sum()
+    }
+}
+
+/// Fields of a CSV line, unquoted
+fn fields(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.trim_end_matches('\r').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+/// How a variant converted, compared with the control
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantResult {
+    pub name: String,
+    /// Visitors that saw the variant
+    pub visitors: u64,
+    /// Visitors that saw the variant and reached the goal
+    pub conversions: u64,
+    /// 95% Wilson interval of the conversion rate
+    pub interval: (f64, f64),
+    /// Relative change of the conversion rate. `None` for the control
+    pub lift: Option<f64>,
+    /// Probability of converting more than the control. `None` for the control
+    pub beat_control: Option<f64>,
+}
+
+impl VariantResult {
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn rate(&self) -> f64 {
+        if self.visitors == 0 {
+            0.0
+        } else {
+            self.conversions as f64 / self.visitors as f64
+        }
+    }
+}
+
+/// Conversion to `goal` of every variant of an experiment
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub goal: String,
+    pub control: String,
+    /// The control goes first
+    pub variants: Vec<VariantResult>,
+}
+
+impl Report {
+    /// Compares the conversion to `goal` of the visitors that saw each
+    /// variant of `experiment`, the ones with the `exposure` goal for it,
+    /// with the ones that saw `control`.
+    ///
+    /// # Errors
+    ///
+    /// * `UnknownControl` if no visitor saw `control`
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(
+        breakdown: &Breakdown,
+        experiment: &str,
+        exposure: &str,
+        goal: &str,
+        control: &str,
+    ) -> Result<Self> {
+        let saw = |r: &Row| r.saw(exposure, experiment);
+        let mut names: Vec<&str> = vec![control];
+        for row in breakdown.0.iter().filter(|r| saw(r)) {
+            if !names.contains(&row.variant.as_str()) {
+                names.push(&row.variant);
+            }
+        }
+        let counts = |name: &str| {
+            let visitors = breakdown.visitors(saw, name);
+            // A visitor can reach the goal without seeing the experiment
+            let conversions = breakdown.visitors(|r| r.goal == goal, name);
+            (visitors, conversions.min(visitors))
+        };
+        let (control_visitors, control_conversions) = counts(control);
+        if control_visitors == 0 {
+            return Err(ResultsError::UnknownControl(control.into()));
+        }
+        let control_rate = control_conversions as f64 / control_visitors as f64;
+
+        let variants = names
+            .into_iter()
+            .map(|name| {
+                let (visitors, conversions) = counts(name);
+                let is_control = name == control;
+                let mut result = VariantResult {
+                    name: name.into(),
+                    visitors,
+                    conversions,
+                    interval: wilson(conversions, visitors),
+                    lift: None,
+                    beat_control: None,
+                };
+                if !is_control {
+                    result.lift = (control_rate > 0.0).then(|| result.rate() / control_rate - 1.0);
+                    result.beat_control = Some(beat(
+                        (conversions, visitors),
+                        (control_conversions, control_visitors),
+                    ));
+                }
+                result
+            })
+            .collect();
+
+        Ok(Self {
+            goal: goal.into(),
+            control: control.into(),
+            variants,
+        })
+    }
+
+    /// The variant most likely to beat the control, if it does with
+    /// `probability` or more
+    #[must_use]
+    pub fn winner(&self, probability: f64) -> Option<&VariantResult> {
+        self.variants
+            .iter()
+            .filter_map(|v| v.beat_control.map(|p| (v, p)))
+            .filter(|(_, p)| *p >= probability)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(v, _)| v)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percent = |p: f64| format!("{:.2}%", p * 100.0);
+        writeln!(f, "Goal: {}", self.goal)?;
+        writeln!(
+            f,
+            "{:<20} {:>9} {:>11} {:>8} {:>20} {:>8} {:>16}",
+            "variant", "visitors", "conversions", "rate", "95% CI", "lift", "P(beat control)"
+        )?;
+        for v in &self.variants {
+            let name = if v.name == self.control {
+                format!("{} (control)", v.name)
+            } else {
+                v.name.clone()
+            };
+            let interval = format!("[{}, {}]", percent(v.interval.0), percent(v.interval.1));
+            let lift = v
+                .lift
+                .map(|l| format!("{:+.1}%", l * 100.0))
+                .unwrap_or_default();
+            let beat = v
+                .beat_control
+                .map(|p| format!("{:.1}%", p * 100.0))
+                .unwrap_or_default();
+            writeln!(
+                f,
+                "{name:<20} {:>9} {:>11} {:>8} {interval:>20} {lift:>8} {beat:>16}",
+                v.visitors,
+                v.conversions,
+                percent(v.rate()),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// 95% Wilson score interval of `conversions` out of `visitors`
+#[allow(clippy::cast_precision_loss)]
+fn wilson(conversions: u64, visitors: u64) -> (f64, f64) {
+    if visitors == 0 {
+        return (0.0, 0.0);
+    }
+    let n = visitors as f64;
+    let p = conversions as f64 / n;
+    let z2 = Z_95 * Z_95;
+    let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let margin = Z_95 / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+/// Probability that the rate of `variant` is higher than the one of
+/// `control`, both `(conversions, visitors)`, with uniform priors. The Beta
+/// posteriors are approximated by normal distributions.
+#[allow(clippy::cast_precision_loss)]
+fn beat(variant: (u64, u64), control: (u64, u64)) -> f64 {
+    let posterior = |(conversions, visitors): (u64, u64)| {
+        let a = conversions as f64 + 1.0;
+        let b = visitors.saturating_sub(conversions) as f64 + 1.0;
+        let mean = a / (a + b);
+        let variance = a * b / ((a + b).powi(2) * (a + b + 1.0));
+        (mean, variance)
+    };
+    let (mean_v, var_v) = posterior(variant);
+    let (mean_c, var_c) = posterior(control);
+    normal_cdf((mean_v - mean_c) / (var_v + var_c).sqrt())
+}
+
+fn normal_cdf(x: f64) -> f64 {
+    0.5 * (1.0 + erf(x / std::f64::consts::SQRT_2))
+}
+
+/// Abramowitz and Stegun 7.1.26, error below 1.5e-7
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / 0.327_591_1_f64.mul_add(x.abs(), 1.0);
+    let poly = t * 1.061_405_429_f64
+        .mul_add(t, -1.453_152_027)
+        .mul_add(t, 1.421_413_741)
+        .mul_add(t, -0.284_496_736)
+        .mul_add(t, 0.254_829_592);
+    let y = poly.mul_add(-(-x * x).exp(), 1.0);
+    y.copysign(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{beat, fields, wilson, Breakdown, Report, ResultsError, Row, EXPOSURE_GOAL};
+
+    fn row(goal: &str, variant: &str, visitors: u64) -> Row {
+        Row {
+            goal: goal.into(),
+            variant: variant.into(),
+            experiment: None,
+            visitors,
+        }
+    }
+
+    fn exposure(experiment: &str, variant: &str, visitors: u64) -> Row {
+        Row {
+            experiment: Some(experiment.into()),
+            ..row(EXPOSURE_GOAL, variant, visitors)
+        }
+    }
+
+    fn hero_text() -> Breakdown {
+        Breakdown(vec![
+            row(EXPOSURE_GOAL, "Human", 1000),
+            row(EXPOSURE_GOAL, "ChatGpt", 1000),
+            row("Newsletter Signup", "Human", 50),
+            row("Newsletter Signup", "ChatGpt", 80),
+        ])
+    }
+
+    #[rstest]
+    fn reads_the_stats_api() {
+        let json = r#"{
+            "results": [
+                {"dimensions": ["ChatGpt", "ExperimentView"], "metrics": [1000]},
+                {"dimensions": ["ChatGpt", "Newsletter Signup"], "metrics": [80]}
+            ],
+            "meta": {},
+            "query": {"dimensions": ["event:props:exp_HeroText", "event:goal"]}
+        }"#;
+
+        let breakdown = Breakdown::from_json(json, "HeroText").expect("Valid export");
+
+        assert_eq!(
+            breakdown.0,
+            [
+                row(EXPOSURE_GOAL, "ChatGpt", 1000),
+                row("Newsletter Signup", "ChatGpt", 80)
+            ]
+        );
+    }
+
+    #[rstest]
+    fn reads_the_experiment_asked_from_the_stats_api() {
+        let json = r#"{
+            "results": [
+                {"dimensions": ["ExperimentView", "ChatGpt", "Join", "Cta"], "metrics": [700]},
+                {"dimensions": ["Newsletter Signup", "ChatGpt", "Join", "(none)"], "metrics": [40]}
+            ],
+            "query": {"dimensions": [
+                "event:goal", "event:props:exp_HeroText", "event:props:exp_Cta", "event:props:experiment"
+            ]}
+        }"#;
+
+        let breakdown = Breakdown::from_json(json, "Cta").expect("Valid export");
+
+        assert_eq!(
+            breakdown.0,
+            [
+                exposure("Cta", "Join", 700),
+                Row {
+                    experiment: Some("(none)".into()),
+                    ..row("Newsletter Signup", "Join", 40)
+                }
+            ]
+        );
+        assert_eq!(
+            Breakdown::from_json(json, "Robot"),
+            Err(ResultsError::MissingColumn("event:props:exp_Robot".into()))
+        );
+    }
+
+    #[rstest]
+    #[case::own_columns("goal,variant,visitors\nExperimentView,Human,1000\n")]
+    #[case::plausible_columns(
+        "name,value,visitors,events\r\n\"ExperimentView\",Human,1000,1200\r\n"
+    )]
+    #[case::stats_api_columns(
+        "event:goal,event:props:exp_HeroText,visitors\nExperimentView,Human,1000"
+    )]
+    fn reads_csv(#[case] csv: &str) {
+        let breakdown = Breakdown::from_csv(csv, "HeroText").expect("Valid export");
+
+        assert_eq!(breakdown.0, [row(EXPOSURE_GOAL, "Human", 1000)]);
+    }
+
+    #[rstest]
+    #[case::hero_text("HeroText", "ChatGpt")]
+    #[case::cta("Cta", "Join")]
+    fn reads_the_experiment_asked_from_csv(#[case] experiment: &str, #[case] variant: &str) {
+        let csv = "event:goal,event:props:exp_HeroText,event:props:exp_Cta,event:props:experiment,visitors\n\
+                   ExperimentView,ChatGpt,Join,HeroText,1000\n";
+
+        let breakdown = Breakdown::from_csv(csv, experiment).expect("Valid export");
+
+        assert_eq!(breakdown.0, [exposure("HeroText", variant, 1000)]);
+    }
+
+    #[rstest]
+    #[case::no_visitors(
+        "goal,variant\nExperimentView,Human",
+        ResultsError::MissingColumn("visitors".into())
+    )]
+    #[case::other_experiment(
+        "event:goal,event:props:exp_Cta,visitors\nExperimentView,Join,1000",
+        ResultsError::MissingColumn("event:props:exp_HeroText".into())
+    )]
+    #[case::not_a_number(
+        "goal,variant,visitors\nExperimentView,Human,many",
+        ResultsError::InvalidRow { line: 2, reason: "visitors is not a number".into() }
+    )]
+    fn rejects_invalid_csv(#[case] csv: &str, #[case] expected: ResultsError) {
+        assert_eq!(Breakdown::from_csv(csv, "HeroText"), Err(expected));
+    }
+
+    #[rstest]
+    fn quoted_fields_keep_their_commas() {
+        assert_eq!(fields(r#"a,"b, ""c""",d"#), ["a", r#"b, "c""#, "d"]);
+    }
+
+    #[rstest]
+    fn compares_variants_with_the_control() {
+        let report = Report::new(
+            &hero_text(),
+            "HeroText",
+            EXPOSURE_GOAL,
+            "Newsletter Signup",
+            "Human",
+        )
+        .expect("Control");
+
+        let [control, chat_gpt] = &report.variants[..] else {
+            panic!("Two variants expected: {report:?}");
+        };
+        assert_eq!((control.name.as_str(), control.conversions), ("Human", 50));
+        assert_eq!(control.beat_control, None);
+        assert!((chat_gpt.rate() - 0.08).abs() < f64::EPSILON);
+        assert!((chat_gpt.lift.expect("Not the control") - 0.6).abs() < 1e-9);
+        assert!(chat_gpt.beat_control.expect("Not the control") > 0.99);
+        assert_eq!(
+            report.winner(0.95).map(|v| v.name.as_str()),
+            Some("ChatGpt")
+        );
+    }
+
+    #[rstest]
+    fn only_visitors_of_the_experiment_are_exposed() {
+        let mut breakdown = hero_text();
+        breakdown.0[0].experiment = Some("HeroText".into());
+        breakdown.0[1].experiment = Some("HeroText".into());
+        breakdown
+            .0
+            .extend([exposure("Cta", "Human", 4000), exposure("Cta", "Robot", 10)]);
+
+        let report = Report::new(
+            &breakdown,
+            "HeroText",
+            EXPOSURE_GOAL,
+            "Newsletter Signup",
+            "Human",
+        )
+        .expect("Control");
+
+        let seen: Vec<_> = report
+            .variants
+            .iter()
+            .map(|v| (v.name.as_str(), v.visitors))
+            .collect();
+        assert_eq!(seen, [("Human", 1000), ("ChatGpt", 1000)]);
+    }
+
+    #[rstest]
+    fn control_must_be_seen() {
+        assert_eq!(
+            Report::new(
+                &hero_text(),
+                "HeroText",
+                EXPOSURE_GOAL,
+                "Newsletter Signup",
+                "Robot"
+            ),
+            Err(ResultsError::UnknownControl("Robot".into()))
+        );
+    }
+
+    #[rstest]
+    #[case(50, 1000, (0.0381, 0.0653))]
+    #[case(0, 10, (0.0, 0.2775))]
+    #[case(0, 0, (0.0, 0.0))]
+    fn wilson_intervals(
+        #[case] conversions: u64,
+        #[case] visitors: u64,
+        #[case] expected: (f64, f64),
+    ) {
+        let (low, high) = wilson(conversions, visitors);
+
+        assert!((low - expected.0).abs() < 1e-4, "{low}");
+        assert!((high - expected.1).abs() < 1e-4, "{high}");
+    }
+
+    #[rstest]
+    fn equal_variants_beat_each_other_half_of_the_time() {
+        assert!((beat((50, 1000), (50, 1000)) - 0.5).abs() < 1e-6);
+        assert!(beat((40, 1000), (50, 1000)) < 0.5);
+    }
+}
@@ -0,0 +1,300 @@
+//! Sends events to plausible from the server.
+//!
+//! Browsers post their events to [`EVENTS_PATH`](super::EVENTS_PATH) and
+//! [`handler`] forwards them with the IP and user agent of the visitor, which
+//! plausible needs to count unique visitors. The server also sends its own
+//! events, like games starting and finishing, with [`Analytics::event`].
+
+use axum::body::Bytes;
+use axum::extract::{ConnectInfo, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use super::event::{PlausiblePayload, PropValue};
+use super::tracker::TrackerKind;
+use super::PlausibleSettings;
+
+/// Events waiting to be sent. More are dropped while plausible is slow.
+const OUTBOX_CAPACITY: usize = 1024;
+const FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// Event on its way to plausible
+#[derive(Debug, Clone)]
+pub struct Outgoing {
+    pub payload: PlausiblePayload,
+    pub user_agent: String,
+    /// `X-Forwarded-For` chain ending with the visitor, if it comes from one
+    pub forwarded_for: Option<String>,
+}
+
+/// Queue of events for plausible. Clones share it.
+#[derive(Clone, Debug)]
+pub struct Analytics {
+    domain: String,
+    public_url: String,
+    /// Peers whose `X-Forwarded-For` is kept
+    trusted_proxies: Arc<[IpAddr]>,
+    /// User agent of the events of the server
+    server_agent: String,
+    outbox: mpsc::Sender<Outgoing>,
+}
+
+impl Analytics {
+    /// Sends the events to the plausible instance of `settings` in the
+    /// background. Pages of the events of the server are under `public_url`.
+    ///
+    /// With another [`TrackerKind`] they are only logged.
+    #[must_use]
+    pub fn new(settings: &PlausibleSettings, public_url: &str) -> Self {
+        let (analytics, mut pending) = Self::channel(settings, public_url);
+        let endpoint = format!("{}/api/event", settings.instance_url);
+        let send = settings.tracker == TrackerKind::Plausible;
+        let http = reqwest::Client::new();
+        tokio::spawn(async move {
+            while let Some(event) = pending.recv().await {
+                if !send {
+                    info!("Plausible event: {:?}", event.payload);
+                } else if let Err(e) = deliver(&http, &endpoint, event).await {
+                    warn!("Plausible event lost: {e}");
+                }
+            }
+        });
+        analytics
+    }
+
+    /// Keeps the events in the returned receiver instead of sending them.
+    /// For tests.
+    #[must_use]
+    pub fn channel(
+        settings: &PlausibleSettings,
+        public_url: &str,
+    ) -> (Self, mpsc::Receiver<Outgoing>) {
+        let (outbox, pending) = mpsc::channel(OUTBOX_CAPACITY);
+        let analytics = Self {
+            domain: settings.domain.clone(),
+            public_url: public_url.into(),
+            trusted_proxies: settings.trusted_proxies.clone().into(),
+            server_agent: settings.server_agent.clone(),
+            outbox,
+        };
+        (analytics, pending)
+    }
+
+    /// Forwards an event of a browser. It is always counted for the domain
+    /// of the configuration.
+    pub fn forward(
+        &self,
+        mut payload: PlausiblePayload,
+        user_agent: String,
+        forwarded_for: String,
+    ) {
+        payload.domain.clone_from(&self.domain);
+        self.enqueue(Outgoing {
+            payload,
+            user_agent,
+            forwarded_for: Some(forwarded_for),
+        });
+    }
+
+    /// Sends an event that happened in the server, like a game starting, as
+    /// seen in `path`
+    pub fn event(&self, name: &str, path: &str, props: HashMap<String, PropValue>) {
+        self.enqueue(Outgoing {
+            payload: PlausiblePayload {
+                name: name.into(),
+                domain: self.domain.clone(),
+                url: format!("{}{path}", self.public_url),
+                referrer: None,
+                screen_width: None,
+                props: Some(props).filter(|p| !p.is_empty()),
+                revenue: None,
+            },
+            user_agent: self.server_agent.clone(),
+            forwarded_for: None,
+        });
+    }
+
+    fn enqueue(&self, event: Outgoing) {
+        if let Err(e) = self.outbox.try_send(event) {
+            debug!("Plausible event dropped: {e}");
+        }
+    }
+}
+
+async fn deliver(http: &reqwest::Client, endpoint: &str, event: Outgoing) -> reqwest::Result<()> {
+    let mut request = http
+        .post(endpoint)
+        .header(header::USER_AGENT, event.user_agent)
+        .json(&event.payload);
+    if let Some(chain) = event.forwarded_for {
+        request = request.header(FORWARDED_FOR, chain);
+    }
+    request.send().await?.error_for_status()?;
+    Ok(())
+}
+
+/// Address of the visitor behind a request from `peer`.
+///
+/// The `X-Forwarded-For` chain is only read if `peer` is one of the
+/// `trusted` proxies, and then from the right: the first address that is not
+/// a trusted proxy is the visitor. Anything to its left could be forged.
+#[must_use]
+pub fn client_ip(headers: &HeaderMap, peer: IpAddr, trusted: &[IpAddr]) -> IpAddr {
+    if !trusted.contains(&peer) {
+        return peer;
+    }
+    let Some(chain) = headers.get(FORWARDED_FOR).and_then(|v| v.to_str().ok()) else {
+        return peer;
+    };
+    let mut client = peer;
+    for hop in chain.rsplit(',') {
+        let Ok(ip) = hop.trim().parse::<IpAddr>() else {
+            break;
+        };
+        client = ip;
+        if !trusted.contains(&ip) {
+            break;
+        }
+    }
+    client
+}
+
+/// `X-Forwarded-For` sent to plausible, which takes its first address as the
+/// visitor. Only the [`client_ip`] is sent so a forged chain is not counted.
+fn forwarded_for(headers: &HeaderMap, peer: SocketAddr, trusted: &[IpAddr]) -> String {
+    client_ip(headers, peer.ip(), trusted).to_string()
+}
+
+/// Receives the events of the browsers. The body is JSON whatever its
+/// content type, `no-cors` requests cannot set it.
+pub async fn handler(
+    State(analytics): State<Analytics>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    let Ok(payload) = serde_json::from_slice::<PlausiblePayload>(&body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+    let user_agent = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let forwarded_for = forwarded_for(&headers, peer, &analytics.trusted_proxies);
+    analytics.forward(payload, user_agent.into(), forwarded_for);
+    StatusCode::ACCEPTED
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Bytes;
+    use axum::extract::{ConnectInfo, State};
+    use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+    use rstest::rstest;
+    use std::collections::HashMap;
+    use std::net::{IpAddr, SocketAddr};
+
+    use super::{client_ip, forwarded_for, handler, Analytics, Outgoing};
+    use crate::{PlausibleSettings, PropValue};
+
+    fn analytics() -> (Analytics, tokio::sync::mpsc::Receiver<Outgoing>) {
+        Analytics::channel(&PlausibleSettings::default(), "https://get.plai.cards")
+    }
+
+    fn peer() -> ConnectInfo<SocketAddr> {
+        ConnectInfo(SocketAddr::from(([203, 0, 113, 7], 4000)))
+    }
+
+    #[rstest]
+    #[case::direct(None, &[], "203.0.113.7")]
+    #[case::behind_a_proxy(Some("198.51.100.1"), &[[203, 0, 113, 7]], "198.51.100.1")]
+    #[case::forged(Some("198.51.100.1"), &[], "203.0.113.7")]
+    #[case::other_proxy(Some("198.51.100.1"), &[[192, 0, 2, 1]], "203.0.113.7")]
+    #[case::forged_behind_a_proxy(
+        Some("192.0.2.99, 198.51.100.1"),
+        &[[203, 0, 113, 7]],
+        "198.51.100.1"
+    )]
+    #[case::two_proxies(
+        Some("198.51.100.1, 192.0.2.1"),
+        &[[203, 0, 113, 7], [192, 0, 2, 1]],
+        "198.51.100.1"
+    )]
+    #[case::garbage(Some("unknown"), &[[203, 0, 113, 7]], "203.0.113.7")]
+    fn client_is_the_last_untrusted_hop(
+        #[case] header: Option<&str>,
+        #[case] trusted: &[[u8; 4]],
+        #[case] expected: &str,
+    ) {
+        let mut headers = HeaderMap::new();
+        if let Some(chain) = header {
+            headers.insert("x-forwarded-for", chain.parse().expect("Valid header"));
+        }
+        let trusted: Vec<IpAddr> = trusted.iter().map(|ip| IpAddr::from(*ip)).collect();
+
+        let ip = client_ip(&headers, peer().0.ip(), &trusted);
+        assert_eq!(ip.to_string(), expected);
+        assert_eq!(forwarded_for(&headers, peer().0, &trusted), expected);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn browser_events_are_forwarded_for_our_domain() {
+        let (analytics, mut pending) = analytics();
+        let mut headers = HeaderMap::new();
+        headers.insert(header::USER_AGENT, HeaderValue::from_static("Firefox"));
+        let body =
+            r#"{"name":"pageview","domain":"someone.else","url":"https://get.plai.cards/cards"}"#;
+
+        let status = handler(State(analytics), peer(), headers, Bytes::from(body)).await;
+
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let event = pending.try_recv().expect("Event is queued");
+        assert_eq!(event.payload.domain, PlausibleSettings::default().domain);
+        assert_eq!(event.payload.url, "https://get.plai.cards/cards");
+        assert_eq!(event.user_agent, "Firefox");
+        assert_eq!(event.forwarded_for.as_deref(), Some("203.0.113.7"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn invalid_events_are_rejected() {
+        let (analytics, mut pending) = analytics();
+
+        let status = handler(
+            State(analytics),
+            peer(),
+            HeaderMap::new(),
+            Bytes::from("{}"),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(pending.try_recv().is_err());
+    }
+
+    #[rstest]
+    fn server_events_are_seen_in_the_public_url() {
+        let settings = PlausibleSettings {
+            server_agent: "plaicards/1.0".into(),
+            ..PlausibleSettings::default()
+        };
+        let (analytics, mut pending) = Analytics::channel(&settings, "https://get.plai.cards");
+
+        analytics.event(
+            "Game Started",
+            "/plai",
+            HashMap::from([("players".into(), PropValue::from(3_usize))]),
+        );
+
+        let event = pending.try_recv().expect("Event is queued");
+        assert_eq!(event.payload.name, "Game Started");
+        assert_eq!(event.payload.url, "https://get.plai.cards/plai");
+        assert_eq!(event.user_agent, "plaicards/1.0");
+        assert!(event.forwarded_for.is_none());
+    }
+}
@@ -0,0 +1,121 @@
+//! Where the events built with [`Plausible`](super::Plausible) end up.
+//!
+//! [`Endpoint`] posts them to plausible. [`ConsoleTracker`] only logs them and
+//! [`MemoryTracker`] keeps them, for development and tests. The server picks
+//! one with [`PlausibleSettings::tracker`](super::PlausibleSettings::tracker).
+
+use leptos::logging::log;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use super::event::PlausiblePayload;
+
+/// Receives every event sent
+pub trait Tracker: Debug {
+    fn track(&self, event: PlausiblePayload);
+
+    /// Receives an event right before the page may be left, like on a link
+    /// click. It must be handed over at once, without waiting for the page.
+    fn beacon(&self, event: PlausiblePayload) {
+        self.track(event);
+    }
+}
+
+/// Which [`Tracker`] the app uses
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackerKind {
+    /// Send the events to plausible, through the server
+    #[default]
+    Plausible,
+    /// Log the events in the console of the browser
+    Console,
+    /// Keep the events in a [`MemoryTracker`] provided as context
+    Memory,
+}
+
+impl TrackerKind {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Plausible => "plausible",
+            Self::Console => "console",
+            Self::Memory => "memory",
+        }
+    }
+}
+
+impl FromStr for TrackerKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        match s {
+            "plausible" => Ok(Self::Plausible),
+            "console" => Ok(Self::Console),
+            "memory" => Ok(Self::Memory),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Posts the events to a plausible events API, see
+/// [`delivery`](super::delivery). Nothing is posted from the server.
+#[derive(Clone, Debug)]
+pub struct Endpoint(String);
+
+impl Endpoint {
+    #[must_use]
+    pub fn new(url: &str) -> Self {
+        Self(url.into())
+    }
+}
+
+impl Tracker for Endpoint {
+    fn track(&self, event: PlausiblePayload) {
+        #[cfg(not(feature = "ssr"))]
+        super::delivery::enqueue(self.0.clone(), event);
+    }
+
+    fn beacon(&self, event: PlausiblePayload) {
+        #[cfg(not(feature = "ssr"))]
+        super::delivery::beacon(self.0.clone(), event);
+    }
+}
+
+/// Logs the events instead of sending them
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ConsoleTracker;
+
+impl Tracker for ConsoleTracker {
+    fn track(&self, event: PlausiblePayload) {
+        log!("Plausible event: {event:?}");
+    }
+}
+
+/// Keeps the events instead of sending them. Clones share them.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryTracker {
+    events: Arc<Mutex<Vec<PlausiblePayload>>>,
+}
+
+impl MemoryTracker {
+    /// Every event tracked so far, oldest first
+    #[must_use]
+    pub fn events(&self) -> Vec<PlausiblePayload> {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl Tracker for MemoryTracker {
+    fn track(&self, event: PlausiblePayload) {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(event);
+    }
+}
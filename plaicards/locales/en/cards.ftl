@@ -0,0 +1,243 @@
+
+#########
+# Cards #
+#########
+
+# Keyed by the id of the card, its English title as a slug
+
+card-nyob-ngo = nyob (NGO)
+    .description = Fighting against giants with GDPR.
+card-100x-roi = 100x ROI
+    .description = You put 10€ and get 1000€ back. You always contribute to society speculating, right? Right?
+card-consultancy-company = Consultancy company
+    .description = In a single project, one consultancy it's OK, two it's too much and three it's just chaos.
+card-middle-manager = Middle manager
+    .description = Trying to scoop water out of the pool with a teaspoon.
+card-twitter-mob = Twitter Mob
+    .description = With a new name, now without controversies, hate, sexism, racism or insults. GG WP EZ. JK.
+card-new-sheriff = New Sheriff
+    .description = Ready to save the town with zero trust, bullish estimations, sign offs, weekly deadlines, CoC and nanomanagement.
+card-xnet-ngo = xNet (NGO)
+    .description = Technopolitics for the new era. Fighting for schools and students with open tech.
+card-regulatory-bodies = Regulatory bodies
+    .description = They are taking their time, but they are doing more and more to mitigate harm.
+card-200-poorly-paid-outsiders = 200 poorly paid outsiders
+    .description = Intelligence yes, artificial no. Cost: probably less than developing your AI but you can suffer a leak if you don't pay them well enough.
+card-ai-regulation = AI regulation
+    .description = It can go anywhere... And it will please no-one.
+card-double-ceos = Double CEOs
+    .description = Double trouble.
+card-human-rights = Human Rights
+    .description = What was this again? Ah, that thing we agreed on that we forget exists if there is no oil to be seized.
+card-pendrive-found = Pendrive Found
+    .description = What will it contain? Cat pictures? For sure there is nothing phishy in it...
+card-tech-debt = Tech Debt
+    .description = It's that critical code written 2 years ago, no one understands the dark magic behind it.
+card-tech-international = Tech International
+    .description = Workers united against the monopolies of knowledge and decisions. They protest against your killer drone program.
+card-expert-ceo = 'Expert' CEO
+    .description = They want all buzzwords in: AI, BigData, NFT, LLM, Quantum ML...
+card-daily-standups = Daily Standups
+    .description = They should be 15 min, have 30 min scheduled and last 2h
+card-deepfake = DeepFake
+    .description = You got played! You thought that call from a politician was real despite being generated by an external actor.
+card-eff-ngo = EFF (NGO)
+    .description = One of many that without its efforts would leave us in a darker web.
+card-rich-white-straight-senoro = Rich white straight señoro
+    .description = He has enough money to buy judges, media and your startup! Maybe racist, misogynist and wants you to work 20h/day.
+card-wikileaks = Wikileaks
+    .description = You know those files that show illegal practices? Yep, they are no longer secret.
+card-cloud-is-down = Cloud is down
+    .description = That happens. Half of the internet does not work today and you are affected.
+card-global-scale = Global Scale
+    .description = You are two technical people but your model has to support half the planet for 50 €/month.
+card-tech-giant = Tech Giant
+    .description = Their motto was don't be evil. They removed it. Now why would that be?
+card-toxic-manager = Toxic Manager
+    .description = He overpromised and now you have all the pressure to deliver 50% of it on time and there is a resignation party.
+card-soshana-zuboff = Soshana Zuboff
+    .description = Author of The Era of Surveillance Capitalism: The Fight for a Human Future at the New Frontier of Power.
+card-accountability = Accountability
+    .description = Show me that the tech is going to be fair and legal before you put it out.
+card-gdpr-fine = GDPR fine
+    .description = Let's be honest, it's low and a small percentage of the damage done.
+card-user-centric = User Centric
+    .description = Focused on selling all user data.
+card-data-outliers = Data Outliers
+    .description = If someone has 10 phones and you have 0, then the average is 5 phones each.
+card-investor-protection = Investor protection
+    .description = You have business angels protecting you.
+card-trolley-dilemma = Trolley dilemma
+    .description = Your AI solves it. Every human dies.
+card-voice-recognition = Voice Recognition
+    .description = With your voice you can order in my shop. You don't even need to use your computer at all!
+card-more-data = More Data
+    .description = You just need more data to improve you AI's poor results. Or you can accept it does not work.
+card-artificial-intelligence = Artificial Intelligence
+    .description = Despite widespread understanding, AI does not resemble our human way of thinking.
+card-machine-learning = Machine Learning
+    .description = Statistics on algorithms on steroids learning from patterns.
+card-nlp = NLP
+    .description = I can process language, write like Shakespeare or Cervantes, and even advance in quantum electrodynamics.
+card-ethical-oversight-board = Ethical Oversight board
+    .description = Internal review organ with zero power, in Facebook's case.
+card-unicorn = Unicorn
+    .description = Your new hire is a data scientist, data engineer, a tech influencer and now an analytics engineer too.
+card-much-more-data = Much More Data
+    .description = When more data is not enough for your hungry training.
+card-neural-network = Neural Network
+    .description = Your brain in my chip since 1943. Now with more computing power.
+card-deep-learning = Deep Learning
+    .description = Neural networks but with fancier names like keras, tensorflow, pytorch...
+card-hugginface = hugginface
+    .description = Every AI practitioner wants to hug them for the great job they are doing.
+card-coded-bias = Coded Bias
+    .description = When your phone cannot detect you face it's because more crimes are predicted in your neighbourhood.
+card-even-more-data = Even More Data
+    .description = You are an even bigger data hoarder than the tech monopolies.
+card-pandas-and-scikit-learn = pandas and scikit Learn
+    .description = Old, trusty and maybe a bit too much backwards compatible. Your data scientist's best friends.
+card-python = Python
+    .description = The snake introducing you to this world while playing snake jazz.
+card-statistics = Statistics
+    .description = Old, but still rocks. It can put your Startup to sleep if you ignore it in favor of the fancy algorithms.
+card-mass-layoffs = Mass Layoffs
+    .description = Ignite the hearts of venture capitalists by constantly firing your employees.
+card-cloud-invoice = Cloud invoice
+    .description = When clouds don't bring rain it means you have to pay.
+card-workers-union = Workers Union
+    .description = You want to bust'em with all the illegal ways you can think of.
+card-hype = Hype
+    .description = Your marketing efforts paid off and now you are near the peak of inflated expectations.
+card-dot-com-crash = Dot-com crash
+    .description = It's the 2000 crisis again. Brace your phone and laptop
+card-machine-learning-operations = Machine Learning Operations
+    .description = They think it's easy to deploy a model to production. Reality says it costs everyone... a card!
+card-international-data-transfer = International data transfer
+    .description = All data passes through Fort Meade.
+card-antitrust = Antitrust
+    .description =
+        You are not too big to fail.*
+
+        {"*"} Your lobbyist might change this sentence.
+card-talent-exchange = Talent exchange
+    .description = That's how juniors become seniors.
+card-press-coverage = Press coverage
+    .description = You pray to the 'gods' of the longtermism cult to make it rain.
+card-killer-drones = Killer drones
+    .description = Congrats, you have a great new contract!
+card-vc-funding = VC funding
+    .description = You have impressed the AI ML GI BD VC AGI DOOM community.
+card-10x-data-scientist = 10x data scientist
+    .description = Do those exist? (Spoiler: you are one of them!)
+card-10x-engineer = 10x engineer
+    .description = Do those exist? (Spoiler: you are one of them!)
+card-financial-data = Financial Data
+    .description = What you bought will tell us if you are pregnant before even you know it.
+card-global-espionage = Global espionage
+    .description = Sponsored by your friendly Human Rights government.
+card-surveillance = Surveillance
+    .description = Walking the narrow line of spying on people and keeping everything secret makes for a distinct power imbalance. But you already knew this, didn't you?
+card-ceo-s-friend = CEO's friend
+    .description = Last minute tasks have no effect on you when you are the CEO's bestie.
+card-ai-bot-army = AI bot army
+    .description = Publicize a distraction with AI-written messages on social media.
+card-board-chaos = Board Chaos
+    .description = While their executives fired each other it's now your opportunity to hire.
+card-great-password = Great Password
+    .description = Your rivals were pwned due to their admin password being 'password'.
+card-ransomware = Ransomware
+    .description = Your rival opens an email and... SURPRISE! Their assets are now yours.
+card-the-ceo-has-requested-this = The CEO has requested this
+    .description = All requests come at 6 PM and are for later today.
+card-arxiv-org = arXiv.org
+    .description = Where great and bad articles reside. But... can you distinguish them?
+card-privacy = Privacy
+    .description = Ah! That which may no longer exist but is essential to our personal growth as individuals.
+card-terms-and-conditions = Terms and Conditions
+    .description = Accept selling your soul to the devil without reading a single word
+card-bias = Bias
+    .description = Big tech tells us their machines are perfect. We need to stop this bullshit.
+card-ethics = Ethics
+    .description = We need time to stop and think. To answer how we can do good, minimize harm, respect human autonomy and be fair and just.
+card-security = Security
+    .description = Cyber security, military security, private security, cartel security, job security. You choose the field!
+card-chatbot = Chatbot
+    .description = You trained it with internet forums and now it's racist and far-right.
+card-impersonate-deceased = Impersonate deceased
+    .description = Your grandma now recommends products from Amazonia.
+card-selling-smoke = Selling smoke
+    .description = Your purpose is to automate snake oil selling with AI generated products, descriptions, images, marketing strategies, everything.
+card-crowdfunding = Crowdfunding
+    .description = Don't depend on Big Capital. Your sole purpose is the same as this game. Steal Branderson's record.
+card-discrimination = Discrimination
+    .description = We may or may not intentionally train our AI to discriminate based on historical data. But we'll hide it regardless.
+card-predict-crimes = Predict crimes
+    .description = None detected in rich neighbourhoods and you don't see what's wrong with that.
+card-select-employees = Select employees
+    .description = Since it's based on historical data, only white men will be accepted for high paying jobs.
+card-stock-trading = Stock trading
+    .description = Get rich making people poor. Now with AI avoiding the Black-Scholes catastrophe.
+card-win-art-competitions = Win art competitions
+    .description = Generate images and videos from random text for internet amazement. Like black cats plaiing this game.
+card-generate-deepfakes = Generate DeepFakes
+    .description = Don't you want to start WWIII?
+card-fix-climate = Fix climate
+    .description = The planet? Greenwashing? I just see the green of money.
+card-facial-recognition = Facial Recognition
+    .description = Your repressive government's favorite tool. Ah, it can also be used to unlock phones too.
+card-internet-of-things = Internet Of Things
+    .description = The promised future since 1982. The smart thing (and you) will learn all about the owners.
+card-manipulate-elections = Manipulate elections
+    .description = Who? We? What proof do you have? - Cambridge Analytica
+card-predict-criminals-using-faces = Predict criminals using faces
+    .description = Pseudo-science from the 30s. Why it's still used nowadays astonishes us.
+card-self-driving-car = Self-driving car
+    .description = Who does it have to put in danger?
+card-sentient-ai = Sentient AI
+    .description = It's for sure a marketing strategy. All media is onboard, but what does it mean to be sentient?
+card-write-black-mirror-episodes = Write black mirror episodes
+    .description = A favorite of the content-creation industry. Will it be like Westworld?
+card-auto-moderation = Auto Moderation
+    .description = You say content moderation is easy and your AI will solve it. In reality, it's highly complex even for humans.
+card-osint-monitoring = OSINT monitoring
+    .description = Monitor Corporations and Governments to reverse the power imbalance.
+
+###########
+# Effects #
+###########
+
+effect-no-effect = {""}
+effect-discard-buzzwords = Discard all buzzword cards plaied
+effect-plus-two-vs-data = +2 against data cards
+effect-plus-two-vs-deceptive = +2 against a deceptive Use-case
+effect-plus-four-vs-data = +4 against data cards
+effect-plus-one-data = +1 with other data cards
+effect-plus-one-python = +1 with python
+effect-plus-two-vs-managers = +2 against CEOs and Managers
+effect-plus-two-buzzwords = +2 to all buzzwords
+effect-plus-three-ceos = +3 against CEOs and Companies
+effect-removes-effect = Removes all card effects
+effect-discard-one = Discard 1 of your rival's plaied cards
+effect-discard-buzzwords-rival = Your rival discards all buzzword cards they plaied
+effect-discard-two = Discard 2 of your rival's plaied cards
+effect-discard-one-each = Randomly discard 1 plaied card each
+effect-cannot-discard = Your plaied cards cannot be discarded
+effect-discard-three-draw-two = Discard 3 cards, draw 2
+effect-discard-three = Discard 3 cards
+effect-discard-attack = Discard all your attack cards
+effect-all-draw-four = Everybody draws 4 cards
+effect-all-discard-four = Everybody discards 4 cards
+effect-antitrust = All plaiers with more than 9 cards discard 10 cards
+effect-cards-to-next-player = All plaiers give their cards to the next plaier. Cannot be blocked.
+effect-change-hands = Change hands with another plaier
+effect-all-discard-one = Everybody discards 1 card
+effect-four-card-vc = Do a 4 card VC funding
+effect-draw-two = Draw 2 cards from the deck
+effect-draw-three = Draw 3 cards from the deck
+effect-revive-card = Revive any card from the discard pile
+effect-spy-player = Spy any plAIer's hand and steal one card
+effect-steal-cat = Steal 1 card from a plAIer. 4 if used as counter for the 'CEO has requested this'. Use it any time.
+effect-steal-2-cards = Steal 2 cards from a plAIer
+effect-stop-effect = plAI anytime to stop any effect
+effect-stop-attack = plAI anytime to stop an attack
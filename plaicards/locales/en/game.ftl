@@ -0,0 +1,29 @@
+
+#########
+# Lobby #
+#########
+
+lobby-startup-name = Your Startup Name
+    .placeholder = Try something edgy like MetaTrust
+lobby-create-startup = Create my startup
+lobby-invite = Invite your friends with this link
+lobby-watch = Streaming the game? Share the read-only link
+lobby-players = Current players
+
+#########
+# Board #
+#########
+
+board-rounds = Rounds: {$rounds}
+board-restarting = Server restarting, your game is saved. Reconnecting...
+board-reconnecting = Reconnecting...
+board-spectators = Spectators
+board-deck = Deck
+board-slow-down = Easy there! Wait a few seconds
+board-chat-everyone = Everyone
+board-chat-send = Send
+board-your-cards = Your Cards {$name}
+board-your-turn = Your turn
+board-family-funding = Family Funding
+board-regional-funding = Regional Funding
+board-vc-funding = VC Funding
@@ -0,0 +1,125 @@
+
+###############
+# How to PLAI #
+###############
+
+rules-title = How to PLAI
+rules-subtitle = THE TECH "WORKER'S" CARD GAME
+rules-players = 2 to 5 players
+rules-ages = Ages: 19+
+rules-playtime = Playtime: 7-59 min
+
+rules-objective = GAME'S OBJECTIVE
+    .summary =
+        Be the only startup alive and leave the others out of
+        business, or destroy all startups with your cooperative
+        Open Source Project.
+    .lead =
+        Lead your AI startup to dominate the competition, aiming to
+        become the ultimate greenwashing monopoly in this ever-changing landscape.
+    .assets = The number of cards in your hand are your company’s assets
+    .assets-use =
+        they will allow you to mess with the other startups. Do not let
+        the number reach zero, but beware if you have too many.
+
+rules-start = Starting the game
+    .numerals = The bottom left of some cards have roman numerals, depending on the number of players pick the following:
+    .two-players = 2 plaiers
+    .two-players-cards = Numeral I
+    .four-players = 3-4 plaiers
+    .four-players-cards = Numerals I & II
+    .five-players = 5 plaiers
+    .five-players-cards = All cards
+    .deal =
+        Remove Market Event cards from the deck. Shuffle, cut and
+        deal 6 cards to each player. Add the Market Event cards back
+        into the deck, shuffle and put the deck face down on the table.
+
+rules-turn = Your turn
+    .actions = During your turn you can do one of the following actions:
+    .funding = Do a funding
+    .special = PlAI a special card
+    .fight = Fight
+    .bankrupt =
+        By the end of turn, if any startup has run out of assets their
+        company is declared bankrupt and can't continue on the market.
+        Any eliminated player becomes an Open Source Maintainer.
+
+rules-funding = Funding
+    .family = Family Funding
+    .family-draw = Draw one card from the deck.
+    .family-safe = Nobody can attack you
+    .regional = Regional pitch
+    .regional-draw = Draw two cards from the deck.
+    .regional-attack = The next player can attack you
+    .vc = Venture capital
+    .vc-draw = Draw three cards.
+    .vc-attack = Anyone can attack you
+    .face-down =
+        During a Regional pitch or a Venture capital, do not look at
+        the drawn cards right away, leave them face down in case
+        another startup wants to attack you.
+    .keep = If no one attacks, keep the assets raised during the funding.
+
+rules-fight = Fight
+    .steal = Steal assets from other startups.
+    .takeover = Hostile Takeover
+    .takeover-when = if you have 9 or more cards, you can challenge any player at your turn.
+    .during-funding = During a Funding
+    .during-funding-when = you or another startup does a Regional pitch or a Venture capital
+    .four-cards = You can play up to four battle cards face down.
+    .cards =
+        Use Adversaries if you are attacking and Use Cases if you
+        are on the defensive. In both cases you can add Buzzwords to
+        increase the number of your attack or defense.
+    .specials =
+        Specific special cards can be played in the middle of battle,
+        such as cards that negate other card's effects. These Special
+        cards do not need to be in the four picked cards at the
+        beginning of the battle.
+    .bluff =
+        You can bluff and only attack or defend yourself with
+        Buzzword cards. However, if the other startup has Use Cases
+        or Adversary cards you will have lost the battle.
+    .bribe = You can always bribe other players for help.
+    .order = Order of events during a fight
+    .order-cards =
+        The attacker(s) chooses their cards to play and places
+        them face down. The defender decides if they want to
+        defend, and if so, put their cards face down.
+    .order-reveal = Defense cards are revealed and their effects are applied. Then, do the same for the attacker's cards.
+    .order-strength =
+        Add up the strength of each played card. Whoever
+        has more points wins, or in the event of a draw, the
+        defense is the winner.
+    .order-winner =
+        The winner takes the drawn cards if it is a Funding, or
+        the entire losing hand if it is a Hostile Takeover.
+    .order-discard = Discard all the played cards.
+
+rules-open-source = Open Source Project
+    .bankrupt =
+        Your startup is bankrupt, but its not your end. You morph
+        into an Open Source Maintainer and can do one of the
+        following actions during your next turns:
+    .family = Family funding
+    .special = PlAI a special card
+    .help = Help fight other startups
+    .together =
+        Open Source Maintainers must act in collaboration and
+        talk in public. They can only have a maximum of 6 assets
+        between them and any additional ones must be discarded.
+
+rules-end = End of game
+    .trough = Trough of Disillusionment
+    .trough-when = There are no more cards available in the draw pile. No one will remember you.
+    .monopoly = Monopoly
+    .monopoly-when =
+        When one startup is left, it must resist
+        a coordinated attack from the Open
+        Source maintainers. If the company wins,
+        congrats, you are now a corporation
+    .teamwork = Teamwork Makes the Dreamwork
+    .teamwork-when =
+        The Open Source Project won the final battle against the only startup left.
+        It's a collective win for all (except for the losing player).
@@ -1,4 +1,58 @@
 
+########
+# Hero #
+########
+
+hero-explore-cards = Explore the cards
+hero-how-to-plai = How to Plai
+hero-create-game = Create a game
+
+hero-career = Seize control of { $mark }
+    .mark = your career
+hero-path = PAVE YOUR OWN PATH*
+hero-startup = Guide your { $mark } to
+    .mark = tech startup
+hero-dominance = MARKET DOMINANCE
+hero-board-game = PLAI, the board game for tech practitioners
+hero-sprint = { $mark } on Kickstarter
+    .mark = Next sprint
+
+hero-b-title = PLAI: The Ultimate Board Game for Tech Enthusiasts
+hero-b-subtitle = Navigate the Tech Startup World and Dominate the Market
+
+language = Language
+
+##############
+# Newsletter #
+##############
+
+newsletter-title = Want the game?
+newsletter-pitch = We want to "release🚀" PLAI to production, but we need a big tech fam to print them.
+newsletter-email-label = Email address
+newsletter-email = Enter your { $kind } email
+    .gdpr-friendly = gdpr-friendly
+    .best = best
+    .msn = msn
+    .cool = cool
+    .yahoo = yahoo
+    .work = work
+    .tinet = tinet
+    .active = active
+    .friends = friends'
+    .spam = spam
+    .aol = AOL
+    .test = test
+    .professional = professional
+    .apple = apple
+    .hotmail = hotmail
+newsletter-submit = I want them!
+newsletter-confirm = Check your inbox to confirm your email
+newsletter-error = Something went wrong. Try again later
+newsletter-dont-miss = Don't miss it
+    .description = It'll be the breakthrough of the nanosecond. Stay ahead of our moonshot moment.
+newsletter-no-spam = No spam
+    .description = I'll send just 2 personal emails 🧔📧. For spam and to see how cool the game is, follow our instagram at
+
 #################
 # Card showcase #
 #################
@@ -73,3 +127,13 @@ showcase-winart = Stealing your content
         from this type of "inspiration". One of them is Glaze from the University 
         of Chicago, it will cloak your creations to make them invisible to AI while remaining
         as beautiful as expected to human eyes.
+
+showcase-criminals = Predict criminals using faces
+    .description =
+        Pseudo-science from the 30s, back with a neural network.
+        Feed it mugshots and it will learn to find criminals
+        by the shape of their faces.
+    .challenge =
+        Mugshots show who gets arrested, not who commits crimes.
+        So the model learns the biases of the police that took them,
+        and calls them science.
@@ -0,0 +1,243 @@
+
+#########
+# Cards #
+#########
+
+# Keyed by the id of the card, its English title as a slug
+
+card-nyob-ngo = nyob (ONG)
+    .description = Luchando contra gigantes con el RGPD.
+card-100x-roi = ROI de 100x
+    .description = Pones 10€ y recuperas 1000€. Especulando siempre contribuyes a la sociedad, ¿verdad? ¿Verdad?
+card-consultancy-company = Consultora
+    .description = En un proyecto, una consultora está bien, dos son demasiadas y tres son el caos.
+card-middle-manager = Mando intermedio
+    .description = Intentando vaciar la piscina con una cucharilla.
+card-twitter-mob = Turba de Twitter
+    .description = Con un nombre nuevo, ahora sin polémicas, odio, sexismo, racismo ni insultos. GG WP EZ. Es broma.
+card-new-sheriff = Nuevo sheriff
+    .description = Listo para salvar el pueblo con confianza cero, estimaciones optimistas, aprobaciones, plazos semanales, código de conducta y nanomanagement.
+card-xnet-ngo = xNet (ONG)
+    .description = Tecnopolítica para la nueva era. Luchando por escuelas y estudiantes con tecnología abierta.
+card-regulatory-bodies = Organismos reguladores
+    .description = Se lo toman con calma, pero cada vez hacen más para mitigar el daño.
+card-200-poorly-paid-outsiders = 200 subcontratados mal pagados
+    .description = Inteligencia sí, artificial no. Coste: seguramente menos que desarrollar tu IA, pero puedes sufrir una filtración si no les pagas lo suficiente.
+card-ai-regulation = Regulación de la IA
+    .description = Puede acabar en cualquier parte... Y no contentará a nadie.
+card-double-ceos = Doble CEO
+    .description = Doble problema.
+card-human-rights = Derechos Humanos
+    .description = ¿Qué era esto? Ah, eso que acordamos y que olvidamos que existe si no hay petróleo que conquistar.
+card-pendrive-found = Pendrive encontrado
+    .description = ¿Qué contendrá? ¿Fotos de gatos? Seguro que no hay nada raro en él...
+card-tech-debt = Deuda técnica
+    .description = Ese código crítico escrito hace 2 años del que nadie entiende la magia negra.
+card-tech-international = Internacional Tech
+    .description = Trabajadores unidos contra los monopolios del conocimiento y las decisiones. Protestan contra tu programa de drones asesinos.
+card-expert-ceo = CEO 'experto'
+    .description = Quiere todas las palabras de moda: IA, BigData, NFT, LLM, Quantum ML...
+card-daily-standups = Dailies
+    .description = Deberían durar 15 min, tienen 30 min en el calendario y duran 2h
+card-deepfake = DeepFake
+    .description = ¡Te la han colado! Creías que la llamada de aquel político era real, pero la había generado un actor externo.
+card-eff-ngo = EFF (ONG)
+    .description = Una de tantas sin cuyos esfuerzos la red sería mucho más oscura.
+card-rich-white-straight-senoro = Señoro blanco, rico y hetero
+    .description = ¡Tiene dinero suficiente para comprar jueces, medios y tu startup! Quizá racista, misógino y quiere que trabajes 20h al día.
+card-wikileaks = Wikileaks
+    .description = ¿Esos archivos que muestran prácticas ilegales? Sí, ya no son secretos.
+card-cloud-is-down = La nube se ha caído
+    .description = Pasa. Media internet no funciona hoy y te ha tocado.
+card-global-scale = Escala global
+    .description = Sois dos personas técnicas pero vuestro modelo tiene que dar servicio a medio planeta por 50 €/mes.
+card-tech-giant = Gigante tecnológico
+    .description = Su lema era no seas malvado. Lo quitaron. ¿Por qué será?
+card-toxic-manager = Jefe tóxico
+    .description = Prometió demasiado y ahora tienes toda la presión para entregar la mitad a tiempo, y hay fiesta de dimisiones.
+card-soshana-zuboff = Soshana Zuboff
+    .description = Autora de La era del capitalismo de la vigilancia: la lucha por un futuro humano frente a las nuevas fronteras del poder.
+card-accountability = Rendición de cuentas
+    .description = Demuéstrame que la tecnología será justa y legal antes de lanzarla.
+card-gdpr-fine = Multa del RGPD
+    .description = Seamos sinceros, es baja y un pequeño porcentaje del daño causado.
+card-user-centric = Centrado en el usuario
+    .description = Centrado en vender todos los datos de los usuarios.
+card-data-outliers = Datos atípicos
+    .description = Si alguien tiene 10 móviles y tú 0, la media es de 5 móviles cada uno.
+card-investor-protection = Protección de inversores
+    .description = Tienes business angels que te protegen.
+card-trolley-dilemma = Dilema del tranvía
+    .description = Tu IA lo resuelve. Muere toda la humanidad.
+card-voice-recognition = Reconocimiento de voz
+    .description = Con tu voz puedes comprar en mi tienda. ¡Ni siquiera necesitas el ordenador!
+card-more-data = Más datos
+    .description = Solo necesitas más datos para mejorar los pobres resultados de tu IA. O puedes aceptar que no funciona.
+card-artificial-intelligence = Inteligencia Artificial
+    .description = Aunque se crea lo contrario, la IA no se parece a nuestra forma humana de pensar.
+card-machine-learning = Machine Learning
+    .description = Estadística en algoritmos con esteroides que aprenden de patrones.
+card-nlp = PLN
+    .description = Puedo procesar el lenguaje, escribir como Shakespeare o Cervantes e incluso avanzar en electrodinámica cuántica.
+card-ethical-oversight-board = Comité de supervisión ética
+    .description = Órgano de revisión interno con poder cero, en el caso de Facebook.
+card-unicorn = Unicornio
+    .description = Tu nueva incorporación es científica de datos, ingeniera de datos, influencer tech y ahora también ingeniera de analítica.
+card-much-more-data = Muchos más datos
+    .description = Cuando más datos no bastan para tu hambriento entrenamiento.
+card-neural-network = Red neuronal
+    .description = Tu cerebro en mi chip desde 1943. Ahora con más potencia de cálculo.
+card-deep-learning = Deep Learning
+    .description = Redes neuronales con nombres más elegantes como keras, tensorflow, pytorch...
+card-hugginface = hugginface
+    .description = Todo profesional de la IA quiere abrazarlos por el gran trabajo que hacen.
+card-coded-bias = Sesgo codificado
+    .description = Cuando tu móvil no reconoce tu cara es porque se predicen más delitos en tu barrio.
+card-even-more-data = Aún más datos
+    .description = Acumulas aún más datos que los monopolios tecnológicos.
+card-pandas-and-scikit-learn = pandas y scikit Learn
+    .description = Viejos, fiables y quizá demasiado retrocompatibles. Los mejores amigos de tu científica de datos.
+card-python = Python
+    .description = La serpiente que te introduce en este mundo mientras toca snake jazz.
+card-statistics = Estadística
+    .description = Vieja, pero sigue molando. Puede dormir a tu startup si la ignoras por los algoritmos de moda.
+card-mass-layoffs = Despidos masivos
+    .description = Enciende los corazones del capital riesgo despidiendo constantemente a tu plantilla.
+card-cloud-invoice = Factura de la nube
+    .description = Cuando las nubes no traen lluvia es que te toca pagar.
+card-workers-union = Sindicato
+    .description = Quieres reventarlo de todas las formas ilegales que se te ocurran.
+card-hype = Hype
+    .description = Tu marketing ha dado frutos y estás cerca del pico de expectativas infladas.
+card-dot-com-crash = Crisis de las puntocom
+    .description = Vuelve la crisis del 2000. Agarra bien tu móvil y tu portátil
+card-machine-learning-operations = Machine Learning Operations
+    .description = Creen que es fácil poner un modelo en producción. La realidad es que le cuesta a todo el mundo... ¡una carta!
+card-international-data-transfer = Transferencia internacional de datos
+    .description = Todos los datos pasan por Fort Meade.
+card-antitrust = Antimonopolio
+    .description =
+        No eres demasiado grande para caer.*
+
+        {"*"} Tu lobista podría cambiar esta frase.
+card-talent-exchange = Intercambio de talento
+    .description = Así es como los juniors se convierten en seniors.
+card-press-coverage = Cobertura mediática
+    .description = Rezas a los 'dioses' de la secta del largoplacismo para que llueva dinero.
+card-killer-drones = Drones asesinos
+    .description = ¡Enhorabuena, tienes un nuevo gran contrato!
+card-vc-funding = Capital riesgo
+    .description = Has impresionado a la comunidad AI ML GI BD VC AGI DOOM.
+card-10x-data-scientist = Científica de datos 10x
+    .description = ¿Existen? (Spoiler: ¡tú eres una de ellas!)
+card-10x-engineer = Ingeniero 10x
+    .description = ¿Existen? (Spoiler: ¡tú eres uno de ellos!)
+card-financial-data = Datos financieros
+    .description = Lo que compras nos dirá si estás embarazada antes de que lo sepas tú.
+card-global-espionage = Espionaje global
+    .description = Patrocinado por tu amable gobierno defensor de los Derechos Humanos.
+card-surveillance = Vigilancia
+    .description = Caminar por la fina línea entre espiar a la gente y mantenerlo todo en secreto crea un claro desequilibrio de poder. Pero eso ya lo sabías, ¿no?
+card-ceo-s-friend = Amiga del CEO
+    .description = Las tareas de última hora no te afectan cuando eres la mejor amiga del CEO.
+card-ai-bot-army = Ejército de bots de IA
+    .description = Difunde una distracción con mensajes escritos por IA en las redes sociales.
+card-board-chaos = Caos en el consejo
+    .description = Mientras sus directivos se despiden entre ellos, es tu oportunidad para contratar.
+card-great-password = Contraseña brillante
+    .description = Tus rivales fueron hackeados porque su contraseña de administrador era 'password'.
+card-ransomware = Ransomware
+    .description = Tu rival abre un correo y... ¡SORPRESA! Sus activos ahora son tuyos.
+card-the-ceo-has-requested-this = El CEO lo ha pedido
+    .description = Todas las peticiones llegan a las 6 de la tarde y son para hoy.
+card-arxiv-org = arXiv.org
+    .description = Donde viven los artículos buenos y los malos. Pero... ¿sabes distinguirlos?
+card-privacy = Privacidad
+    .description = ¡Ah! Eso que quizá ya no existe pero es esencial para crecer como individuos.
+card-terms-and-conditions = Términos y condiciones
+    .description = Acepta vender tu alma al diablo sin leer una sola palabra
+card-bias = Sesgo
+    .description = Las big tech nos dicen que sus máquinas son perfectas. Tenemos que acabar con esta patraña.
+card-ethics = Ética
+    .description = Necesitamos tiempo para parar y pensar. Para responder cómo hacer el bien, minimizar el daño, respetar la autonomía humana y ser justos.
+card-security = Seguridad
+    .description = Ciberseguridad, seguridad militar, seguridad privada, seguridad del cártel, seguridad laboral. ¡Tú eliges el campo!
+card-chatbot = Chatbot
+    .description = Lo entrenaste con foros de internet y ahora es racista y de extrema derecha.
+card-impersonate-deceased = Suplantar a difuntos
+    .description = Tu abuela ahora recomienda productos de Amazonia.
+card-selling-smoke = Vender humo
+    .description = Tu propósito es automatizar la venta de crecepelo con productos, descripciones, imágenes, estrategias de marketing y todo lo demás generado por IA.
+card-crowdfunding = Crowdfunding
+    .description = No dependas del Gran Capital. Tu único propósito es el mismo que el de este juego. Batir el récord de Branderson.
+card-discrimination = Discriminación
+    .description = Puede que entrenemos a nuestra IA para discriminar según datos históricos, o puede que no. Pero lo esconderemos igualmente.
+card-predict-crimes = Predecir delitos
+    .description = No se detecta ninguno en los barrios ricos y no ves qué tiene eso de malo.
+card-select-employees = Seleccionar empleados
+    .description = Como se basa en datos históricos, solo se aceptará a hombres blancos para los trabajos bien pagados.
+card-stock-trading = Trading en bolsa
+    .description = Hazte rico empobreciendo a la gente. Ahora con IA que evita la catástrofe de Black-Scholes.
+card-win-art-competitions = Ganar concursos de arte
+    .description = Genera imágenes y vídeos a partir de texto aleatorio para asombro de internet. Como gatos negros jugando a este juego.
+card-generate-deepfakes = Generar DeepFakes
+    .description = ¿No quieres empezar la Tercera Guerra Mundial?
+card-fix-climate = Arreglar el clima
+    .description = ¿El planeta? ¿Greenwashing? Yo solo veo el verde del dinero.
+card-facial-recognition = Reconocimiento facial
+    .description = La herramienta favorita de tu gobierno represivo. Ah, también sirve para desbloquear móviles.
+card-internet-of-things = Internet de las cosas
+    .description = El futuro prometido desde 1982. La cosa inteligente (y tú) lo sabrá todo de sus dueños.
+card-manipulate-elections = Manipular elecciones
+    .description = ¿Quién? ¿Nosotros? ¿Qué pruebas tienes? - Cambridge Analytica
+card-predict-criminals-using-faces = Predecir criminales por la cara
+    .description = Pseudociencia de los años 30. Nos asombra que aún se use hoy en día.
+card-self-driving-car = Coche autónomo
+    .description = ¿A quién tiene que poner en peligro?
+card-sentient-ai = IA consciente
+    .description = Seguro que es una estrategia de marketing. Todos los medios se han subido al carro, pero ¿qué significa ser consciente?
+card-write-black-mirror-episodes = Escribir episodios de Black Mirror
+    .description = Una de las favoritas de la industria de contenidos. ¿Será como Westworld?
+card-auto-moderation = Moderación automática
+    .description = Dices que moderar contenido es fácil y que tu IA lo resolverá. En realidad, es muy complejo incluso para humanos.
+card-osint-monitoring = Vigilancia OSINT
+    .description = Vigila a corporaciones y gobiernos para invertir el desequilibrio de poder.
+
+###########
+# Effects #
+###########
+
+effect-no-effect = {""}
+effect-discard-buzzwords = Descarta todas las palabras de moda jugadas
+effect-plus-two-vs-data = +2 contra cartas de datos
+effect-plus-two-vs-deceptive = +2 contra un caso de uso engañoso
+effect-plus-four-vs-data = +4 contra cartas de datos
+effect-plus-one-data = +1 con otras cartas de datos
+effect-plus-one-python = +1 con python
+effect-plus-two-vs-managers = +2 contra CEOs y jefes
+effect-plus-two-buzzwords = +2 a todas las palabras de moda
+effect-plus-three-ceos = +3 contra CEOs y empresas
+effect-removes-effect = Anula los efectos de todas las cartas
+effect-discard-one = Descarta 1 de las cartas jugadas por tu rival
+effect-discard-buzzwords-rival = Tu rival descarta todas las palabras de moda que ha jugado
+effect-discard-two = Descarta 2 de las cartas jugadas por tu rival
+effect-discard-one-each = Cada uno descarta 1 carta jugada al azar
+effect-cannot-discard = Tus cartas jugadas no se pueden descartar
+effect-discard-three-draw-two = Descarta 3 cartas, roba 2
+effect-discard-three = Descarta 3 cartas
+effect-discard-attack = Descarta todas tus cartas de ataque
+effect-all-draw-four = Todo el mundo roba 4 cartas
+effect-all-discard-four = Todo el mundo descarta 4 cartas
+effect-antitrust = Todos los jugadores con más de 9 cartas descartan 10 cartas
+effect-cards-to-next-player = Todos los jugadores dan sus cartas al siguiente jugador. No se puede bloquear.
+effect-change-hands = Intercambia la mano con otro jugador
+effect-all-discard-one = Todo el mundo descarta 1 carta
+effect-four-card-vc = Haz una ronda de capital riesgo de 4 cartas
+effect-draw-two = Roba 2 cartas del mazo
+effect-draw-three = Roba 3 cartas del mazo
+effect-revive-card = Recupera cualquier carta de la pila de descartes
+effect-spy-player = Espía la mano de cualquier jugador y róbale una carta
+effect-steal-cat = Roba 1 carta a un jugador. 4 si la usas para contrarrestar 'El CEO lo ha pedido'. Úsala en cualquier momento.
+effect-steal-2-cards = Roba 2 cartas a un jugador
+effect-stop-effect = Juégala en cualquier momento para parar cualquier efecto
+effect-stop-attack = Juégala en cualquier momento para parar un ataque
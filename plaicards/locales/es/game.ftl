@@ -0,0 +1,29 @@
+
+#########
+# Lobby #
+#########
+
+lobby-startup-name = El nombre de tu startup
+    .placeholder = Prueba algo atrevido como MetaTrust
+lobby-create-startup = Crear mi startup
+lobby-invite = Invita a tus amigos con este enlace
+lobby-watch = ¿Retransmites la partida? Comparte el enlace de solo lectura
+lobby-players = Jugadores
+
+#########
+# Board #
+#########
+
+board-rounds = Rondas: {$rounds}
+board-restarting = El servidor se está reiniciando, tu partida está guardada. Reconectando...
+board-reconnecting = Reconectando...
+board-spectators = Espectadores
+board-deck = Mazo
+board-slow-down = ¡Tranquilidad! Espera unos segundos
+board-chat-everyone = Todos
+board-chat-send = Enviar
+board-your-cards = Tus cartas {$name}
+board-your-turn = Tu turno
+board-family-funding = Ronda familiar
+board-regional-funding = Ronda regional
+board-vc-funding = Ronda de capital riesgo
@@ -0,0 +1,126 @@
+
+###############
+# How to PLAI #
+###############
+
+rules-title = Cómo jugar a PLAI
+rules-subtitle = EL JUEGO DE CARTAS DE LOS "TRABAJADORES" TECH
+rules-players = De 2 a 5 jugadores
+rules-ages = Edad: 19+
+rules-playtime = Duración: 7-59 min
+
+rules-objective = OBJETIVO DEL JUEGO
+    .summary =
+        Sé la única startup que sobrevive y saca a las demás del
+        mercado, o destruye todas las startups con tu proyecto
+        cooperativo de Código Abierto.
+    .lead =
+        Lleva tu startup de IA a dominar a la competencia, con el objetivo
+        de convertirte en el monopolio de greenwashing definitivo en este
+        panorama siempre cambiante.
+    .assets = El número de cartas de tu mano son los activos de tu empresa
+    .assets-use =
+        te permitirán fastidiar a las otras startups. No dejes que el
+        número llegue a cero, pero cuidado si tienes demasiadas.
+
+rules-start = Empezar la partida
+    .numerals = Algunas cartas tienen números romanos abajo a la izquierda, según el número de jugadores escoge las siguientes:
+    .two-players = 2 jugadores
+    .two-players-cards = Número I
+    .four-players = 3-4 jugadores
+    .four-players-cards = Números I y II
+    .five-players = 5 jugadores
+    .five-players-cards = Todas las cartas
+    .deal =
+        Saca las cartas de Evento de Mercado del mazo. Baraja, corta y
+        reparte 6 cartas a cada jugador. Vuelve a poner las cartas de Evento
+        de Mercado en el mazo, baraja y déjalo boca abajo en la mesa.
+
+rules-turn = Tu turno
+    .actions = Durante tu turno puedes hacer una de las siguientes acciones:
+    .funding = Hacer una ronda de financiación
+    .special = Jugar una carta especial
+    .fight = Luchar
+    .bankrupt =
+        Al final del turno, si alguna startup se ha quedado sin activos
+        su empresa entra en bancarrota y no puede seguir en el mercado.
+        Los jugadores eliminados pasan a ser Mantenedores de Código Abierto.
+
+rules-funding = Financiación
+    .family = Ronda familiar
+    .family-draw = Roba una carta del mazo.
+    .family-safe = Nadie te puede atacar
+    .regional = Ronda regional
+    .regional-draw = Roba dos cartas del mazo.
+    .regional-attack = El siguiente jugador te puede atacar
+    .vc = Capital riesgo
+    .vc-draw = Roba tres cartas.
+    .vc-attack = Cualquiera te puede atacar
+    .face-down =
+        Durante una ronda regional o de capital riesgo, no mires
+        las cartas robadas enseguida, déjalas boca abajo por si
+        otra startup te quiere atacar.
+    .keep = Si nadie ataca, quédate los activos conseguidos en la ronda.
+
+rules-fight = Luchar
+    .steal = Roba activos de otras startups.
+    .takeover = Adquisición hostil
+    .takeover-when = si tienes 9 cartas o más, puedes desafiar a cualquier jugador en tu turno.
+    .during-funding = Durante una ronda
+    .during-funding-when = tú u otra startup hace una ronda regional o de capital riesgo
+    .four-cards = Puedes jugar hasta cuatro cartas de batalla boca abajo.
+    .cards =
+        Usa Adversarios si atacas y Casos de Uso si te defiendes.
+        En ambos casos puedes añadir Palabras de Moda para
+        aumentar tu ataque o tu defensa.
+    .specials =
+        Algunas cartas especiales se pueden jugar en mitad de la batalla,
+        como las que anulan los efectos de otras cartas. Estas cartas
+        especiales no tienen que estar entre las cuatro escogidas al
+        principio de la batalla.
+    .bluff =
+        Puedes ir de farol y atacar o defenderte solo con Palabras
+        de Moda. Pero si la otra startup tiene Casos de Uso o
+        Adversarios habrás perdido la batalla.
+    .bribe = Siempre puedes sobornar a otros jugadores para que te ayuden.
+    .order = Orden de una batalla
+    .order-cards =
+        Quien ataca escoge las cartas que juega y las pone
+        boca abajo. Quien defiende decide si se defiende y,
+        si es así, pone sus cartas boca abajo.
+    .order-reveal = Se revelan las cartas de la defensa y se aplican sus efectos. Después, lo mismo con las del ataque.
+    .order-strength =
+        Suma la fuerza de cada carta jugada. Quien tenga
+        más puntos gana y, en caso de empate, gana la
+        defensa.
+    .order-winner =
+        Quien gana se lleva las cartas robadas si es una ronda
+        de financiación, o toda la mano perdedora si es una adquisición hostil.
+    .order-discard = Descarta todas las cartas jugadas.
+
+rules-open-source = Proyecto de Código Abierto
+    .bankrupt =
+        Tu startup está en bancarrota, pero no es tu final. Te conviertes
+        en Mantenedor de Código Abierto y puedes hacer una de las
+        siguientes acciones en tus próximos turnos:
+    .family = Ronda familiar
+    .special = Jugar una carta especial
+    .help = Ayudar a luchar contra otras startups
+    .together =
+        Los Mantenedores de Código Abierto deben colaborar y
+        hablar en público. Solo pueden tener 6 activos como máximo
+        entre todos y deben descartar los que sobren.
+
+rules-end = Fin de la partida
+    .trough = Valle de la desilusión
+    .trough-when = No quedan cartas en el mazo. Nadie se acordará de ti.
+    .monopoly = Monopolio
+    .monopoly-when =
+        Cuando solo queda una startup, debe resistir
+        un ataque coordinado de los Mantenedores de
+        Código Abierto. Si la empresa gana,
+        enhorabuena, ahora eres una corporación
+    .teamwork = La unión hace la fuerza
+    .teamwork-when =
+        El Proyecto de Código Abierto ganó la batalla final contra la única startup que quedaba.
+        Es una victoria colectiva (menos para el jugador que pierde).
@@ -0,0 +1,139 @@
+
+########
+# Hero #
+########
+
+hero-explore-cards = Descubre las cartas
+hero-how-to-plai = Cómo jugar
+hero-create-game = Crear una partida
+
+hero-career = Toma el control de { $mark }
+    .mark = tu carrera
+hero-path = TRAZA TU PROPIO CAMINO*
+hero-startup = Lleva tu { $mark } a
+    .mark = startup tecnológica
+hero-dominance = DOMINAR EL MERCADO
+hero-board-game = PLAI, el juego de mesa para profesionales tech
+hero-sprint = { $mark } en Kickstarter
+    .mark = Próximo sprint
+
+hero-b-title = PLAI: el juego de mesa definitivo para entusiastas de la tecnología
+hero-b-subtitle = Navega el mundo de las startups tecnológicas y domina el mercado
+
+language = Idioma
+
+##############
+# Newsletter #
+##############
+
+newsletter-title = ¿Quieres el juego?
+newsletter-pitch = Queremos hacer "release🚀" de PLAI a producción, pero necesitamos una gran familia tech para imprimirlo.
+newsletter-email-label = Correo electrónico
+newsletter-email = Escribe tu email { $kind }
+    .gdpr-friendly = respetuoso con el RGPD
+    .best = favorito
+    .msn = del msn
+    .cool = molón
+    .yahoo = de yahoo
+    .work = del trabajo
+    .tinet = de tinet
+    .active = activo
+    .friends = para los amigos
+    .spam = para spam
+    .aol = de AOL
+    .test = de pruebas
+    .professional = profesional
+    .apple = de apple
+    .hotmail = de hotmail
+newsletter-submit = ¡Los quiero!
+newsletter-confirm = Revisa tu bandeja de entrada para confirmar tu email
+newsletter-error = Algo ha ido mal. Vuelve a intentarlo más tarde
+newsletter-dont-miss = No te lo pierdas
+    .description = Será el avance del nanosegundo. Adelántate a nuestro momento moonshot.
+newsletter-no-spam = Nada de spam
+    .description = Solo te enviaré 2 emails personales 🧔📧. Para spam y para ver lo guapo que es el juego, sigue nuestro instagram en
+
+#################
+# Card showcase #
+#################
+
+showcase-intro = Roba las cartas para descubrir en qué se inspiran.
+showcase-seen =
+    {$cardsCount ->
+      [zero] Aún no has visto ninguna carta
+      [one] ¡Enhorabuena! Has visto tu primera carta
+      *[other] Has visto {$cardsCount} cartas de {$total}
+    }.
+
+showcase-dotcom = La crisis de las puntocom
+    .description =
+        Esta carta se inspira en la burbuja de las puntocom,
+        un ciclo de hiper-hype del mercado en el que era
+        casi imposible no subirse al carro.
+
+showcase-select-employees = Seleccionar empleados
+    .description =
+        Varias startups y corporaciones anunciaron IA para ayudar a las empresas a contratar.
+        Encontrarán a los mejores candidatos entre miles de currículums.
+        Así eliminas el sesgo humano de tu proceso.
+    .challenge =
+        Suena genial, ¿verdad? El reto, sin embargo, es cómo aprenden
+        esos algoritmos.
+        Usan datos históricos, en los que los hombres blancos ocupaban los puestos de poder.
+        Así que la IA hizo lo que aprendió: descartar a mujeres, pieles más oscuras y
+        personas de universidades públicas.
+
+showcase-daily = Daily standup
+    .description =
+        Ah, el paladín del poderoso enemigo del desarrollador.
+
+        Legendarios son su duración y su poder de micromanagement.
+
+        El programador solitario intenta lidiar con bloqueos, bugs, tickets
+        y falta de memoria.
+    .punchline =
+        Y no olvides a sus secuaces, que lo acompañan en su paseo matutino:
+        jira, kanban y scrum a gran escala.
+
+showcase-more-data = Hambre de tus datos
+    .description = Imagina un hambre que nunca acaba.
+        No eres el único que la sufre, pero tienes una reserva secreta.
+    .challenge =
+        Se suele decir que cuando no pagas por el producto,
+        el producto eres tú. Hoy en día, sin embargo, eres el producto
+        pagues o no. Todas las empresas neeeeecesitan el combustible que les das.
+        ¡Es el nuevo petróleo!
+
+showcase-antitrust = Eres demasiado grande, y cambiarás esta frase
+    .description =
+        El libre mercado es el rey teórico de nuestro sistema económico actual.
+        Y se ha demostrado que la concentración de empresas no es buena ni para
+        la economía en general ni para los consumidores.
+        ¡Si solo hay un gran actor, sube los precios para todos!
+    .challenge = Por desgracia, esta carta es más un deseo que una realidad.
+        Las empresas siguen creciendo más y más.
+        Y cuando un regulador actúa, usan su poder para
+        dar dinero a antiguos miembros de esas instituciones.
+        Solo para hacer... cosas normales, ¿sabes?
+showcase-winart = Robando tu contenido
+    .description =
+        La IA generativa es la palabra de moda.
+        Todo CEO la necesita como la ducha diaria.
+        Así que cuidado, artistas, porque vienen a por vosotros.
+        Vuestro talento, innovación, experiencia e ideas quedarán codificados en la máquina,
+        y cualquiera que sepa escribir dos frases podrá copiar vuestro estilo único.
+    .challenge =
+        Para evitarlo, han aparecido varios proyectos que protegen las creaciones artísticas
+        de este tipo de "inspiración". Uno de ellos es Glaze, de la Universidad
+        de Chicago, que camufla tus creaciones para hacerlas invisibles a la IA
+        y tan bonitas como siempre para los ojos humanos.
+
+showcase-criminals = Predecir criminales por la cara
+    .description =
+        Pseudociencia de los años 30, de vuelta con una red neuronal.
+        Dale fotos de fichas policiales y aprenderá a encontrar criminales
+        por la forma de su cara.
+    .challenge =
+        Las fichas policiales muestran a quién detienen, no quién comete delitos.
+        Así que el modelo aprende los sesgos de la policía que las hizo,
+        y los llama ciencia.
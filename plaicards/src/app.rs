@@ -1,13 +1,15 @@
-use crate::error_template::{AppError, ErrorTemplate};
-use crate::web::board::Board;
+use crate::error_template::{error_page, AppError, ErrorTemplate};
+use crate::web::admin::AdminPage;
+use crate::web::board::{Board, Spectate};
 use crate::web::common::BuiltWith;
+use crate::web::i18n::LanguageSwitcher;
 use crate::web::landing::{HomePage, RulePage, Showcase};
-use crate::web::lobby::Lobby;
-use crate::web::plausible::components::{provide_plausible_context, EndPage, PageView};
-use crate::web::plausible::experiments::{Experiment, ExperimentCtx, Variant};
+use crate::web::lobby::{JoinLobby, Lobby};
+use crate::web::subscriptions::{ConfirmPage, UnsubscribePage};
+use leptos_plausible::components::{provide_plausible_context, EndPage, PageView, PlausibleMeta};
+use leptos_plausible::experiments::{provide_experiments, Experiment, Variant};
 
 use fluent_templates::static_loader;
-use leptos::Provider;
 use leptos::*;
 use leptos_fluent::{expect_i18n, leptos_fluent, move_tr, tr};
 use leptos_meta::*;
@@ -17,6 +19,8 @@ static_loader! {
     static TRANSLATIONS = {
         locales: "./locales",
         fallback_language: "en",
+        // Variables are part of the sentence, without bidi marks around them
+        customise: |bundle| bundle.set_use_isolating(false),
     };
 }
 
@@ -41,52 +45,42 @@ pub fn App() -> impl IntoView {
 
     }};
 
-    // Setup experiment
-    let experiment = Experiment::new(
+    // Setup experiments
+    provide_experiments([Experiment::new(
         "HeroText",
-        Variant::new("Human", 1),
-        Variant::new("ChatGpt", 1),
-    );
-    let exp = store_value(experiment);
-    let variant = create_resource(
-        || (),
-        move |()| async move {
-            let mut e = exp();
-            e.choose();
-            e
-        },
-    );
+        [Variant::new("Human", 1), Variant::new("ChatGpt", 1)],
+    )]);
 
     view! {
         <Stylesheet id="leptos" href="/pkg/plaicards.css"/>
         <MetaInfo/>
 
         <Router
-            fallback=|| {
-                let mut outside_errors = Errors::default();
-                outside_errors.insert_with_default_key(AppError::NotFound);
-                view! { <ErrorTemplate outside_errors/> }.into_view()
-            }
+            fallback=|| error_page(AppError::NotFound)
 
             trailing_slash=TrailingSlash::Redirect
         >
-            <Provider value=ExperimentCtx(variant)>
-                <Suspense fallback=|| ()>
-                    <main>
+            <Suspense fallback=|| ()>
+                <main>
 
-                        <PageView/>
-                        <Routes>
-                            <Route path="/" view=HomePage/>
-                            <Route path="/cards" view=Showcase/>
-                            <Route path="/how-to-plai" view=RulePage/>
-                        //<Route path="/lobby/:id/:player_id" view=move || view!{ <Lobby/>} />
-                        //<Route path="/plai/:id/:player_id" view=move || view!{ <Board/>} />
-                        </Routes>
-                    </main>
-                    <BuiltWith/>
-                    <EndPage/>
-                </Suspense>
-            </Provider>
+                    <PageView/>
+                    <Routes>
+                        <Route path="/" view=HomePage/>
+                        <Route path="/cards" view=Showcase/>
+                        <Route path="/how-to-plai" view=RulePage/>
+                        <Route path="/watch/:id" view=Spectate/>
+                        <Route path="/lobby/:id" view=JoinLobby/>
+                        <Route path="/lobby/:id/:player_id" view=Lobby/>
+                        <Route path="/plai/:id/:player_id" view=Board/>
+                        <Route path="/newsletter/confirm/:token" view=ConfirmPage/>
+                        <Route path="/newsletter/unsubscribe/:token" view=UnsubscribePage/>
+                        <Route path="/admin" view=AdminPage/>
+                    </Routes>
+                </main>
+                <LanguageSwitcher/>
+                <BuiltWith/>
+                <EndPage/>
+            </Suspense>
         </Router>
     }
 }
@@ -100,6 +94,7 @@ fn MetaInfo() -> impl IntoView {
     let title = "PLAI - the Board game for tech workers";
     view! {
         <Title text=title/>
+        <PlausibleMeta/>
 
         <Meta charset="utf-8"/>
         <Meta name="description" content=description/>
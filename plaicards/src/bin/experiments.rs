@@ -0,0 +1,128 @@
+//! Results of an A/B/n experiment from plausible stats.
+//!
+//! Reads the visitors by goal and variant of `--experiment`, a Stats API
+//! response or a CSV export (see `leptos_plausible::results`), and prints for every goal the
+//! conversion rate of each variant with its 95% confidence interval, the lift
+//! and the probability of beating the control.
+//!
+//! ```sh
+//! cargo run --features ssr --bin experiments -- --export hero-text.json --experiment HeroText --control Human
+//! ```
+
+use leptos_plausible::results::{Breakdown, Report, EXPOSURE_GOAL};
+use plaicards::web::landing::SIGNUP_EVENT;
+
+/// Variants beating the control with this probability are winners
+const WINNER_PROBABILITY: f64 = 0.95;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Clone, Debug)]
+struct Args {
+    export: String,
+    experiment: String,
+    control: String,
+    exposure: String,
+    goals: Vec<String>,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            export: String::new(),
+            experiment: "HeroText".into(),
+            control: "Human".into(),
+            exposure: EXPOSURE_GOAL.into(),
+            goals: vec![],
+        }
+    }
+}
+
+impl Args {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+        while let Some(flag) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("Missing value for {flag}"))?;
+            match flag.as_str() {
+                "--export" => parsed.export = value,
+                "--experiment" => parsed.experiment = value,
+                "--control" => parsed.control = value,
+                "--exposure" => parsed.exposure = value,
+                "--goal" => parsed.goals.push(value),
+                _ => return Err(format!("Unknown argument {flag}")),
+            }
+        }
+        if parsed.export.is_empty() {
+            return Err("Pass the stats with --export <file.json|file.csv>".into());
+        }
+        if parsed.goals.is_empty() {
+            parsed.goals = vec![SIGNUP_EVENT.into(), "Outbound Link: Click".into()];
+        }
+        Ok(parsed)
+    }
+}
+
+fn read(path: &str, experiment: &str) -> Result<Breakdown, BoxError> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    let breakdown = if path.ends_with(".json") {
+        Breakdown::from_json(&content, experiment)?
+    } else {
+        Breakdown::from_csv(&content, experiment)?
+    };
+    Ok(breakdown)
+}
+
+fn main() -> Result<(), BoxError> {
+    let args = Args::parse(std::env::args().skip(1))?;
+    let breakdown = read(&args.export, &args.experiment)?;
+
+    println!(
+        "Experiment {}, exposed with {}",
+        args.experiment, args.exposure
+    );
+    for goal in &args.goals {
+        let report = Report::new(
+            &breakdown,
+            &args.experiment,
+            &args.exposure,
+            goal,
+            &args.control,
+        )?;
+        println!("\n{report}");
+        match report.winner(WINNER_PROBABILITY) {
+            Some(winner) => println!("{} wins", winner.name),
+            None => println!("No winner yet"),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{Args, SIGNUP_EVENT};
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(ToString::to_string))
+    }
+
+    #[rstest]
+    fn goals_default_to_signups_and_outbound_links() {
+        let parsed = args(&["--export", "stats.csv"]).expect("Valid arguments");
+
+        assert_eq!(parsed.experiment, "HeroText");
+        assert_eq!(parsed.goals, [SIGNUP_EVENT, "Outbound Link: Click"]);
+    }
+
+    #[rstest]
+    #[case::no_export(&["--goal", "Signup"])]
+    #[case::missing_value(&["--export"])]
+    #[case::unknown(&["--export", "stats.csv", "--rooms", "2"])]
+    fn rejects_invalid_arguments(#[case] invalid: &[&str]) {
+        assert!(args(invalid).is_err());
+    }
+}
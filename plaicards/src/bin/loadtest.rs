@@ -0,0 +1,275 @@
+//! Load test of the game websocket.
+//!
+//! Starts the board websocket in-process, opens `--rooms` games with
+//! `--players` bots each, plus `--spectators` watchers, and lets the bots play
+//! family fundings as fast as the server answers until `--secs` pass or every
+//! game ends.
+//!
+//! ```sh
+//! cargo run --release --features loadtest --bin loadtest -- --rooms 200 --players 4
+//! ```
+
+use axum::{routing::get, Router};
+use futures::{SinkExt, StreamExt};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::time::timeout_at;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use uuid::Uuid;
+
+use plaicards::web::board::msg::{ClientMsg, Funding, ServerMsg, WsSerDe};
+use plaicards::web::board::{board_handler, GameController};
+use plaicards::web::metrics::METRICS;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Clone, Copy, Debug)]
+struct Args {
+    rooms: usize,
+    players: usize,
+    spectators: usize,
+    secs: u64,
+    /// Capacity of the broadcast channel of each room
+    capacity: usize,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            rooms: 50,
+            players: 4,
+            spectators: 2,
+            secs: 30,
+            capacity: plaicards::web::board::DEFAULT_CAPACITY,
+        }
+    }
+}
+
+impl Args {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+        while let Some(flag) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("Missing value for {flag}"))?;
+            let number = || {
+                value
+                    .parse()
+                    .map_err(|_| format!("Invalid {flag}: {value}"))
+            };
+            match flag.as_str() {
+                "--rooms" => parsed.rooms = number()?,
+                "--players" => parsed.players = number()?,
+                "--spectators" => parsed.spectators = number()?,
+                "--secs" => parsed.secs = number()?.try_into().map_err(|_| "Invalid --secs")?,
+                "--capacity" => parsed.capacity = number()?,
+                _ => return Err(format!("Unknown argument {flag}")),
+            }
+        }
+        if parsed.players < 2 || parsed.capacity == 0 {
+            return Err("A game needs 2 players and a channel capacity of 1".into());
+        }
+        Ok(parsed)
+    }
+}
+
+/// Counters shared by every bot
+#[derive(Debug, Default)]
+struct Stats {
+    received: AtomicU64,
+    actions: AtomicU64,
+    /// Bots whose connection ended before the test did
+    dropped: AtomicU64,
+    ended_games: AtomicU64,
+    latencies: Mutex<Vec<Duration>>,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), BoxError> {
+    let args = Args::parse(std::env::args().skip(1))?;
+
+    let gc = GameController::with_capacity(args.capacity);
+    let app = Router::new()
+        .route("/game/ws", get(board_handler))
+        .with_state(gc.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let stats = Arc::new(Stats::default());
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(args.secs);
+    let lagged_before = METRICS.lagged_total();
+    let start = Instant::now();
+
+    let mut bots = vec![];
+    for _ in 0..args.rooms {
+        let game_id = Uuid::new_v4();
+        let players: Vec<(Uuid, String)> = (0..args.players)
+            .map(|i| (Uuid::new_v4(), format!("bot{i}")))
+            .collect();
+        gc.new_game(game_id, &players)
+            .await
+            .map_err(|e| format!("Cannot create room: {e:?}"))?;
+
+        let seats = players.iter().map(|(id, _)| Some(*id));
+        for me in seats.chain(std::iter::repeat(None).take(args.spectators)) {
+            let stats = stats.clone();
+            bots.push(tokio::spawn(async move {
+                if bot(addr, game_id, me, &stats, deadline).await.is_err() {
+                    stats.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }));
+        }
+    }
+    for bot in bots {
+        let _ = bot.await;
+    }
+
+    report(
+        &args,
+        &stats,
+        start.elapsed(),
+        METRICS.lagged_total() - lagged_before,
+    );
+    Ok(())
+}
+
+/// Plays a seat, or watches the game when `me` is `None`, until `deadline`.
+///
+/// Returns an error if the server closed the connection before.
+async fn bot(
+    addr: SocketAddr,
+    game_id: Uuid,
+    me: Option<Uuid>,
+    stats: &Stats,
+    deadline: tokio::time::Instant,
+) -> Result<(), BoxError> {
+    let (ws, _) = connect_async(format!("ws://{addr}/game/ws")).await?;
+    let (mut sink, mut stream) = ws.split();
+    let hello = match me {
+        Some(player_id) => ClientMsg::Connect { game_id, player_id },
+        None => ClientMsg::Spectate { game_id },
+    };
+    sink.send(Message::Text(hello.to_str())).await?;
+
+    let mut active = None;
+    let mut pending: Option<Instant> = None;
+    let mut latencies = vec![];
+    let res = loop {
+        let frame = match timeout_at(deadline, stream.next()).await {
+            // Time is up
+            Err(_) => break Ok(()),
+            Ok(None) => break Err("Connection closed".into()),
+            Ok(Some(frame)) => frame?,
+        };
+        let Message::Text(text) = frame else { continue };
+        stats.received.fetch_add(1, Ordering::Relaxed);
+
+        match ServerMsg::from_str(&text)? {
+            ServerMsg::StateSnapshot(snapshot) => active = Some(snapshot.active_player),
+            ServerMsg::StateDiff(diff) => {
+                if let Some(player) = diff.active_player {
+                    active = Some(player);
+                }
+                if let Some(sent) = pending.take() {
+                    latencies.push(sent.elapsed());
+                }
+            }
+            ServerMsg::GameEnded => {
+                stats.ended_games.fetch_add(1, Ordering::Relaxed);
+                break Ok(());
+            }
+            ServerMsg::NotYourTurn | ServerMsg::BadMove => pending = None,
+            _ => {}
+        }
+
+        if me.is_some() && active == me && pending.is_none() {
+            // The only action that is always allowed
+            let action = ClientMsg::DoFunding(Funding::Family);
+            sink.send(Message::Text(action.to_str())).await?;
+            stats.actions.fetch_add(1, Ordering::Relaxed);
+            pending = Some(Instant::now());
+        }
+    };
+
+    if let Ok(mut all) = stats.latencies.lock() {
+        all.extend(latencies);
+    }
+    res
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn report(args: &Args, stats: &Stats, elapsed: Duration, lagged: u64) {
+    let secs = elapsed.as_secs_f64();
+    let per_sec = |n: u64| n as f64 / secs;
+    let received = stats.received.load(Ordering::Relaxed);
+    let actions = stats.actions.load(Ordering::Relaxed);
+    let mut latencies = stats
+        .latencies
+        .lock()
+        .map(|l| l.clone())
+        .unwrap_or_default();
+    latencies.sort_unstable();
+
+    println!(
+        "{} rooms x ({} players + {} spectators), channel capacity {}, {secs:.1}s",
+        args.rooms, args.players, args.spectators, args.capacity
+    );
+    println!("actions:           {actions} ({:.0}/s)", per_sec(actions));
+    println!("messages received: {received} ({:.0}/s)", per_sec(received));
+    println!(
+        "action latency:    p50 {:?}, p99 {:?}, max {:?}",
+        percentile(&latencies, 50),
+        percentile(&latencies, 99),
+        latencies.last().copied().unwrap_or_default()
+    );
+    println!("lagged messages:   {lagged}");
+    println!(
+        "dropped clients:   {}",
+        stats.dropped.load(Ordering::Relaxed)
+    );
+    println!(
+        "ended games:       {}",
+        stats.ended_games.load(Ordering::Relaxed)
+    );
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use std::time::Duration;
+
+    use super::{percentile, Args};
+
+    #[rstest]
+    #[case(50, 50)]
+    #[case(99, 99)]
+    #[case(100, 100)]
+    #[case(1, 1)]
+    fn nearest_rank_percentile(#[case] p: usize, #[case] expected: u64) {
+        let values: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&values, p), Duration::from_millis(expected));
+    }
+
+    #[rstest]
+    fn args_are_parsed() {
+        let args = Args::parse(["--rooms", "7", "--secs", "3"].map(String::from))
+            .expect("Valid arguments");
+        assert_eq!(args.rooms, 7);
+        assert_eq!(args.secs, 3);
+        assert!(Args::parse(["--players", "1"].map(String::from)).is_err());
+        assert!(Args::parse(["--rooms"].map(String::from)).is_err());
+    }
+}
@@ -0,0 +1,649 @@
+//! Configuration of the server.
+//!
+//! Values come, from lowest to highest priority, from the defaults, a TOML
+//! file and the `PLAICARDS_*` environment variables:
+//!
+//! ```toml
+//! log_level = "info"
+//! public_url = "https://get.plai.cards"
+//! dev_seed = true
+//! seat_grace_secs = 60
+//! admin_token = "a long random secret"
+//!
+//! [subscriptions]
+//! store = "file"
+//! path = "data/crowdfunding-emails.txt"
+//! max_per_ip = 3
+//! window_secs = 600
+//! mailer = "sendmail"
+//! sendmail = "/usr/sbin/sendmail"
+//! from = "PLAI <newsletter@get.plai.cards>"
+//!
+//! [plausible]
+//! domain = "get.plai.cards"
+//! instance_url = "https://frumentarii.8vi.cat"
+//! tracker = "plausible"
+//! trusted_proxies = ["127.0.0.1"]
+//!
+//! [channels]
+//! game_room = 5
+//! lobby = 10
+//!
+//! [shutdown]
+//! drain_secs = 5
+//! grace_secs = 10
+//! rooms_path = "data/rooms.json"
+//!
+//! [telemetry]
+//! otlp_endpoint = "http://localhost:4317"
+//! service_name = "plaicards"
+//!
+//! [bus]
+//! redis_url = "redis://127.0.0.1:6379"
+//! node = "plaicards-0"
+//! lease_secs = 30
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+use tracing::Level;
+
+use leptos_plausible::PlausibleSettings;
+
+/// File read when no other is given and it exists
+pub const DEFAULT_FILE: &str = "plaicards.toml";
+/// Environment variable with the path of the configuration file
+pub const FILE_ENV: &str = "PLAICARDS_CONFIG";
+const ENV_PREFIX: &str = "PLAICARDS_";
+const MIN_ADMIN_TOKEN_LEN: usize = 16;
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("cannot read {path:?}: {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("cannot parse {path:?}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("invalid value for {key}: {value:?}")]
+    Env { key: String, value: String },
+    #[error("invalid {field}: {reason}")]
+    Invalid { field: &'static str, reason: String },
+}
+
+pub type Result<T> = core::result::Result<T, ConfigError>;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// One of `trace`, `debug`, `info`, `warn` or `error`
+    pub log_level: String,
+    /// Where the site is reachable, for links in emails. Without trailing slash.
+    pub public_url: String,
+    /// Seeds the development game rooms. Only with the `dev-seed` feature.
+    pub dev_seed: bool,
+    /// Time a disconnected player keeps the seat before the game moves on
+    /// without them
+    pub seat_grace_secs: u64,
+    /// Secret to access `/admin`. The admin area is disabled without it.
+    /// Never printed with `--print-config`.
+    #[serde(skip_serializing)]
+    pub admin_token: Option<String>,
+    pub plausible: PlausibleSettings,
+    pub channels: Channels,
+    pub subscriptions: SubscriptionsConfig,
+    pub telemetry: Telemetry,
+    pub shutdown: Shutdown,
+    pub bus: Bus,
+}
+
+/// Room messages between instances. See `web::bus`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Bus {
+    /// Redis shared by every instance. Rooms stay in the process without it.
+    /// Only with the `redis` feature.
+    pub redis_url: Option<String>,
+    /// Name of this instance, which owns the rooms it creates. Keep it
+    /// stable across restarts so saved rooms are claimed again. Random if
+    /// not set.
+    pub node: Option<String>,
+    /// Time another instance waits to claim the rooms of an instance that
+    /// died without releasing them. Owners extend it while they run.
+    pub lease_secs: u64,
+}
+
+impl Default for Bus {
+    fn default() -> Self {
+        Self {
+            redis_url: None,
+            node: None,
+            lease_secs: 30,
+        }
+    }
+}
+
+/// What happens on SIGTERM
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Shutdown {
+    /// Time `/readyz` fails before the server stops accepting connections,
+    /// so load balancers stop sending traffic first
+    pub drain_secs: u64,
+    /// Time given to clients to leave before closing their connections
+    pub grace_secs: u64,
+    /// Where game rooms are saved on shutdown and restored on start
+    pub rooms_path: PathBuf,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self {
+            drain_secs: 5,
+            grace_secs: 10,
+            rooms_path: "data/rooms.json".into(),
+        }
+    }
+}
+
+/// Export of traces. See [`crate::telemetry`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Telemetry {
+    /// OTLP gRPC collector. Traces are only exported when set.
+    pub otlp_endpoint: Option<String>,
+    pub service_name: String,
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        Self {
+            otlp_endpoint: None,
+            service_name: "plaicards".into(),
+        }
+    }
+}
+
+/// Capacity of the broadcast channels
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Channels {
+    /// Clients that fall further behind than this get a fresh snapshot
+    pub game_room: usize,
+    pub lobby: usize,
+}
+
+/// Newsletter subscriptions. See `web::subscriptions`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubscriptionsConfig {
+    pub store: StoreKind,
+    /// CSV file or SQLite database, depending on `store`
+    pub path: PathBuf,
+    /// Subscriptions allowed from the same IP every `window_secs`
+    pub max_per_ip: usize,
+    pub window_secs: u64,
+    /// How the confirmation emails are sent
+    pub mailer: MailerKind,
+    /// Program used by the `sendmail` mailer
+    pub sendmail: PathBuf,
+    /// Sender of the emails
+    pub from: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MailerKind {
+    /// Only writes the emails to the log. Nobody can confirm a subscription.
+    Log,
+    Sendmail,
+}
+
+impl FromStr for MailerKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        match s {
+            "log" => Ok(Self::Log),
+            "sendmail" => Ok(Self::Sendmail),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StoreKind {
+    File,
+    /// Only with the `sqlite` feature
+    Sqlite,
+}
+
+impl FromStr for StoreKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        match s {
+            "file" => Ok(Self::File),
+            "sqlite" => Ok(Self::Sqlite),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let log_level = if cfg!(debug_assertions) {
+            "trace"
+        } else {
+            "info"
+        };
+        Self {
+            log_level: log_level.into(),
+            public_url: "http://127.0.0.1:3000".into(),
+            dev_seed: true,
+            seat_grace_secs: 60,
+            admin_token: None,
+            plausible: PlausibleSettings {
+                domain: "get.plai.cards".into(),
+                instance_url: "https://frumentarii.8vi.cat".into(),
+                server_agent: concat!("plaicards/", env!("CARGO_PKG_VERSION")).into(),
+                ..PlausibleSettings::default()
+            },
+            channels: Channels::default(),
+            subscriptions: SubscriptionsConfig::default(),
+            telemetry: Telemetry::default(),
+            shutdown: Shutdown::default(),
+            bus: Bus::default(),
+        }
+    }
+}
+
+impl Default for SubscriptionsConfig {
+    fn default() -> Self {
+        Self {
+            store: StoreKind::File,
+            path: "data/crowdfunding-emails.txt".into(),
+            max_per_ip: 3,
+            window_secs: 600,
+            mailer: MailerKind::Log,
+            sendmail: "/usr/sbin/sendmail".into(),
+            from: "PLAI <newsletter@get.plai.cards>".into(),
+        }
+    }
+}
+
+impl Default for Channels {
+    fn default() -> Self {
+        Self {
+            game_room: 5,
+            lobby: 10,
+        }
+    }
+}
+
+impl Config {
+    /// Loads and validates the configuration of the server.
+    ///
+    /// The file is `path` if given, else the one in [`FILE_ENV`] or
+    /// [`DEFAULT_FILE`] if it exists.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read or any value is invalid
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = path
+            .map(Path::to_path_buf)
+            .or_else(|| std::env::var_os(FILE_ENV).map(PathBuf::from))
+            .or_else(|| Some(PathBuf::from(DEFAULT_FILE)).filter(|p| p.exists()));
+
+        let mut config = match path {
+            Some(path) => Self::from_file(&path)?,
+            None => Self::default(),
+        };
+        config.apply_env(std::env::vars())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// # Errors
+    ///
+    /// If the file cannot be read or is not valid TOML for a [`Config`]
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        toml::from_str(&content).map_err(|source| ConfigError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Overrides values with the `PLAICARDS_*` variables in `vars`
+    ///
+    /// # Errors
+    ///
+    /// If a value cannot be parsed
+    pub fn apply_env(&mut self, vars: impl IntoIterator<Item = (String, String)>) -> Result<()> {
+        for (key, value) in vars {
+            let Some(name) = key.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            match name {
+                "LOG_LEVEL" => self.log_level = value,
+                "PUBLIC_URL" => self.public_url = value,
+                "DEV_SEED" => self.dev_seed = parse(&key, &value)?,
+                "SEAT_GRACE_SECS" => self.seat_grace_secs = parse(&key, &value)?,
+                "ADMIN_TOKEN" => self.admin_token = Some(value),
+                "PLAUSIBLE_DOMAIN" => self.plausible.domain = value,
+                "PLAUSIBLE_URL" => self.plausible.instance_url = value,
+                "PLAUSIBLE_TRACKER" => self.plausible.tracker = parse(&key, &value)?,
+                "TRUSTED_PROXIES" => {
+                    self.plausible.trusted_proxies = value
+                        .split(',')
+                        .map(|ip| parse(&key, ip.trim()))
+                        .collect::<Result<_>>()?;
+                }
+                "GAME_ROOM_CHANNEL" => self.channels.game_room = parse(&key, &value)?,
+                "LOBBY_CHANNEL" => self.channels.lobby = parse(&key, &value)?,
+                "SUBSCRIPTIONS_STORE" => self.subscriptions.store = parse(&key, &value)?,
+                "SUBSCRIPTIONS_PATH" => self.subscriptions.path = value.into(),
+                "MAILER" => self.subscriptions.mailer = parse(&key, &value)?,
+                "SENDMAIL" => self.subscriptions.sendmail = value.into(),
+                "MAIL_FROM" => self.subscriptions.from = value,
+                "OTLP_ENDPOINT" => self.telemetry.otlp_endpoint = Some(value),
+                "SERVICE_NAME" => self.telemetry.service_name = value,
+                "SHUTDOWN_DRAIN_SECS" => self.shutdown.drain_secs = parse(&key, &value)?,
+                "SHUTDOWN_GRACE_SECS" => self.shutdown.grace_secs = parse(&key, &value)?,
+                "ROOMS_PATH" => self.shutdown.rooms_path = value.into(),
+                "REDIS_URL" => self.bus.redis_url = Some(value),
+                "NODE" => self.bus.node = Some(value),
+                "ROOM_LEASE_SECS" => self.bus.lease_secs = parse(&key, &value)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// The first invalid value found
+    pub fn validate(&self) -> Result<()> {
+        if Level::from_str(&self.log_level).is_err() {
+            return Err(invalid(
+                "log_level",
+                "use trace, debug, info, warn or error",
+            ));
+        }
+        if !is_base_url(&self.public_url) {
+            return Err(invalid(
+                "public_url",
+                "must be an http(s) URL without trailing slash",
+            ));
+        }
+        if self
+            .admin_token
+            .as_ref()
+            .is_some_and(|t| t.len() < MIN_ADMIN_TOKEN_LEN)
+        {
+            return Err(invalid(
+                "admin_token",
+                "must be at least 16 characters long",
+            ));
+        }
+        if self.plausible.domain.is_empty() {
+            return Err(invalid("plausible.domain", "cannot be empty"));
+        }
+        if !is_base_url(&self.plausible.instance_url) {
+            return Err(invalid(
+                "plausible.instance_url",
+                "must be an http(s) URL without trailing slash",
+            ));
+        }
+        // Tokio panics with empty broadcast channels
+        if self.channels.game_room == 0 {
+            return Err(invalid("channels.game_room", "must be at least 1"));
+        }
+        if self.channels.lobby == 0 {
+            return Err(invalid("channels.lobby", "must be at least 1"));
+        }
+        if self
+            .telemetry
+            .otlp_endpoint
+            .as_ref()
+            .is_some_and(|e| !is_base_url(e))
+        {
+            return Err(invalid(
+                "telemetry.otlp_endpoint",
+                "must be an http(s) URL without trailing slash",
+            ));
+        }
+        if self.telemetry.service_name.is_empty() {
+            return Err(invalid("telemetry.service_name", "cannot be empty"));
+        }
+        if self.shutdown.rooms_path.as_os_str().is_empty() {
+            return Err(invalid("shutdown.rooms_path", "cannot be empty"));
+        }
+        if self.subscriptions.path.as_os_str().is_empty() {
+            return Err(invalid("subscriptions.path", "cannot be empty"));
+        }
+        if self.subscriptions.max_per_ip == 0 {
+            return Err(invalid("subscriptions.max_per_ip", "must be at least 1"));
+        }
+        if self.subscriptions.mailer == MailerKind::Sendmail {
+            if self.subscriptions.sendmail.as_os_str().is_empty() {
+                return Err(invalid("subscriptions.sendmail", "cannot be empty"));
+            }
+            if !self.subscriptions.from.contains('@') || self.subscriptions.from.contains('\n') {
+                return Err(invalid("subscriptions.from", "must be an email address"));
+            }
+        }
+        if self.subscriptions.store == StoreKind::Sqlite && !cfg!(feature = "sqlite") {
+            return Err(invalid(
+                "subscriptions.store",
+                "sqlite needs the sqlite feature",
+            ));
+        }
+        if let Some(url) = &self.bus.redis_url {
+            if !(url.starts_with("redis://") || url.starts_with("rediss://")) {
+                return Err(invalid("bus.redis_url", "must be a redis:// URL"));
+            }
+            if !cfg!(feature = "redis") {
+                return Err(invalid("bus.redis_url", "redis needs the redis feature"));
+            }
+        }
+        if self.bus.node.as_ref().is_some_and(String::is_empty) {
+            return Err(invalid("bus.node", "cannot be empty"));
+        }
+        if self.bus.lease_secs == 0 {
+            return Err(invalid("bus.lease_secs", "must be at least 1"));
+        }
+        Ok(())
+    }
+
+    /// Log level. Only valid after [`Self::validate`]
+    #[must_use]
+    pub fn level(&self) -> Level {
+        Level::from_str(&self.log_level).unwrap_or(Level::INFO)
+    }
+
+    /// The configuration as it would be written in the file
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        toml::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+fn parse<T: FromStr>(key: &str, value: &str) -> Result<T> {
+    value.parse().map_err(|_| ConfigError::Env {
+        key: key.into(),
+        value: value.into(),
+    })
+}
+
+fn is_base_url(url: &str) -> bool {
+    (url.starts_with("https://") || url.starts_with("http://")) && !url.ends_with('/')
+}
+
+fn invalid(field: &'static str, reason: &str) -> ConfigError {
+    ConfigError::Invalid {
+        field,
+        reason: reason.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    use super::{Config, ConfigError, MailerKind, StoreKind};
+    use leptos_plausible::tracker::TrackerKind;
+
+    fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[rstest]
+    fn default_is_valid() {
+        assert!(Config::default().validate().is_ok());
+    }
+
+    #[rstest]
+    fn file_can_be_partial() {
+        let config: Config = toml::from_str(
+            r#"
+            log_level = "warn"
+
+            [channels]
+            lobby = 32
+            "#,
+        )
+        .expect("Partial file should parse");
+
+        assert_eq!(config.log_level, "warn");
+        assert_eq!(config.channels.lobby, 32);
+        assert_eq!(config.channels.game_room, 5);
+    }
+
+    #[rstest]
+    fn unknown_keys_are_rejected() {
+        assert!(toml::from_str::<Config>("log_levle = \"warn\"").is_err());
+    }
+
+    #[rstest]
+    fn env_overrides_file() {
+        let mut config = Config::default();
+        config
+            .apply_env(env(&[
+                ("PLAICARDS_LOG_LEVEL", "debug"),
+                ("PLAICARDS_GAME_ROOM_CHANNEL", "64"),
+                ("PLAICARDS_PLAUSIBLE_DOMAIN", "localhost"),
+                ("PLAICARDS_PLAUSIBLE_TRACKER", "console"),
+                ("PLAICARDS_TRUSTED_PROXIES", "127.0.0.1, ::1"),
+                ("PLAICARDS_SEAT_GRACE_SECS", "5"),
+                ("PLAICARDS_SUBSCRIPTIONS_STORE", "sqlite"),
+                ("PLAICARDS_MAILER", "sendmail"),
+                ("PLAICARDS_OTLP_ENDPOINT", "http://localhost:4317"),
+                ("PLAICARDS_NODE", "plaicards-1"),
+                ("OTHER_LOG_LEVEL", "error"),
+            ]))
+            .expect("Valid variables");
+
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.channels.game_room, 64);
+        assert_eq!(config.plausible.domain, "localhost");
+        assert_eq!(config.plausible.tracker, TrackerKind::Console);
+        assert_eq!(config.seat_grace_secs, 5);
+        assert_eq!(
+            config.plausible.trusted_proxies,
+            [
+                IpAddr::from(Ipv4Addr::LOCALHOST),
+                Ipv6Addr::LOCALHOST.into()
+            ]
+        );
+        assert_eq!(config.subscriptions.store, StoreKind::Sqlite);
+        assert_eq!(config.subscriptions.mailer, MailerKind::Sendmail);
+        assert_eq!(
+            config.telemetry.otlp_endpoint.as_deref(),
+            Some("http://localhost:4317")
+        );
+        assert_eq!(config.bus.node.as_deref(), Some("plaicards-1"));
+    }
+
+    #[rstest]
+    fn unparsable_env_is_an_error() {
+        let mut config = Config::default();
+        let res = config.apply_env(env(&[("PLAICARDS_LOBBY_CHANNEL", "many")]));
+        assert!(matches!(res, Err(ConfigError::Env { .. })));
+        let res = config.apply_env(env(&[("PLAICARDS_SUBSCRIPTIONS_STORE", "postgres")]));
+        assert!(matches!(res, Err(ConfigError::Env { .. })));
+        let res = config.apply_env(env(&[("PLAICARDS_TRUSTED_PROXIES", "127.0.0.1,proxy")]));
+        assert!(matches!(res, Err(ConfigError::Env { .. })));
+    }
+
+    #[rstest]
+    #[case::log_level("PLAICARDS_LOG_LEVEL", "loud")]
+    #[case::empty_channel("PLAICARDS_GAME_ROOM_CHANNEL", "0")]
+    #[case::url_without_scheme("PLAICARDS_PLAUSIBLE_URL", "plausible.io")]
+    #[case::url_with_slash("PLAICARDS_PLAUSIBLE_URL", "https://plausible.io/")]
+    #[case::empty_domain("PLAICARDS_PLAUSIBLE_DOMAIN", "")]
+    #[case::public_url_with_slash("PLAICARDS_PUBLIC_URL", "https://get.plai.cards/")]
+    #[case::empty_subscriptions("PLAICARDS_SUBSCRIPTIONS_PATH", "")]
+    #[case::short_admin_token("PLAICARDS_ADMIN_TOKEN", "1234")]
+    #[case::empty_rooms_path("PLAICARDS_ROOMS_PATH", "")]
+    #[case::otlp_without_scheme("PLAICARDS_OTLP_ENDPOINT", "localhost:4317")]
+    #[case::redis_without_scheme("PLAICARDS_REDIS_URL", "127.0.0.1:6379")]
+    #[case::empty_node("PLAICARDS_NODE", "")]
+    #[case::no_lease("PLAICARDS_ROOM_LEASE_SECS", "0")]
+    fn invalid_values_are_rejected(#[case] key: &str, #[case] value: &str) {
+        let mut config = Config::default();
+        config
+            .apply_env(env(&[(key, value)]))
+            .expect("Value should parse");
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid { .. })
+        ));
+    }
+
+    #[rstest]
+    fn sendmail_needs_a_sender() {
+        let mut config = Config::default();
+        config
+            .apply_env(env(&[
+                ("PLAICARDS_MAILER", "sendmail"),
+                ("PLAICARDS_MAIL_FROM", "PLAI"),
+            ]))
+            .expect("Value should parse");
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid { .. })
+        ));
+    }
+
+    #[rstest]
+    fn printed_config_can_be_loaded() {
+        let config = Config::default();
+        let reloaded: Config = toml::from_str(&config.to_toml()).expect("Printed config parses");
+        assert_eq!(config, reloaded);
+    }
+
+    #[rstest]
+    fn admin_token_is_not_printed() {
+        let config = Config {
+            admin_token: Some("correct horse battery staple".into()),
+            ..Config::default()
+        };
+        assert!(!config.to_toml().contains("staple"));
+    }
+}
@@ -6,16 +6,27 @@ use thiserror::Error;
 pub enum AppError {
     #[error("Not Found")]
     NotFound,
+    #[error("Invalid link")]
+    InvalidLink,
 }
 
 impl AppError {
     #[must_use] pub fn status_code(&self) -> StatusCode {
         match self {
             Self::NotFound => StatusCode::NOT_FOUND,
+            Self::InvalidLink => StatusCode::BAD_REQUEST,
         }
     }
 }
 
+/// Error page for a single error found outside of an error boundary
+#[must_use]
+pub fn error_page(error: AppError) -> View {
+    let mut outside_errors = Errors::default();
+    outside_errors.insert_with_default_key(error);
+    view! { <ErrorTemplate outside_errors/> }.into_view()
+}
+
 // A basic function to display errors served by the error boundaries.
 // Feel free to do more complicated things here than just displaying the error.
 #[component]
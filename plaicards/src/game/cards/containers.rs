@@ -1,6 +1,7 @@
 use std::fmt;
 
 use rand::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::game::{Error, Result};
 
@@ -21,7 +22,7 @@ impl fmt::Debug for DeckEmptyError {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Hand {
     cards: Vec<Card>,
 }
@@ -98,7 +99,7 @@ impl Hand {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Deck {
     cards: Vec<Card>,
 }
@@ -148,6 +149,7 @@ mod tests {
 
         for i in 1..=5 {
             cards.push(Card::Adversary {
+                id: format!("card-{i}"),
                 title: format!("Card_{i}"),
                 effect: crate::game::cards::CardEffect::NoEffect,
                 description: String::new(),
@@ -210,6 +212,7 @@ mod testhand {
 
         for i in 1..=60 {
             cards.push(Card::Adversary {
+                id: format!("card-{i}"),
                 title: format!("Card_{i}"),
                 effect: crate::game::cards::CardEffect::NoEffect,
                 description: String::new(),
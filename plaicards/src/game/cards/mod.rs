@@ -1,4 +1,4 @@
-use std::{error::Error, str::FromStr};
+use std::error::Error;
 
 use serde::{Deserialize, Serialize};
 
@@ -7,32 +7,44 @@ mod containers;
 pub use containers::{Deck, DeckEmptyError, Hand};
 use uuid::Uuid;
 
+/// Card of the game. Cards saved before they had an `id` load with an empty
+/// one.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Card {
     Adversary {
+        #[serde(default)]
+        id: String,
         title: String,
         description: String,
         strength: i8,
         effect: CardEffect,
     },
     Buzzword {
+        #[serde(default)]
+        id: String,
         title: String,
         description: String,
         strength: i8,
         effect: CardEffect,
     },
     UseCase {
+        #[serde(default)]
+        id: String,
         title: String,
         description: String,
         strength: i8,
         effect: CardEffect,
     },
     Special {
+        #[serde(default)]
+        id: String,
         title: String,
         description: String,
         effect: CardEffect,
     },
     MarketEvent {
+        #[serde(default)]
+        id: String,
         title: String,
         description: String,
         effect: CardEffect,
@@ -40,6 +52,19 @@ pub enum Card {
 }
 
 impl Card {
+    /// Stable id of the card, the same for every copy of it. It keys its
+    /// texts in the `cards.ftl` locales as `card-<id>`.
+    pub fn id(&self) -> String {
+        match self {
+            Self::Adversary { id, .. }
+            | Self::Buzzword { id, .. }
+            | Self::UseCase { id, .. }
+            | Self::Special { id, .. }
+            | Self::MarketEvent { id, .. } => id,
+        }
+        .into()
+    }
+
     pub fn title(&self) -> String {
         match self {
             Self::Adversary { title, .. }
@@ -61,15 +86,14 @@ impl Card {
         }
         .into()
     }
-    pub fn effect(&self) -> String {
+    pub const fn effect(&self) -> CardEffect {
         match self {
             Self::Adversary { effect, .. }
             | Self::Buzzword { effect, .. }
             | Self::UseCase { effect, .. }
             | Self::Special { effect, .. }
-            | Self::MarketEvent { effect, .. } => effect,
+            | Self::MarketEvent { effect, .. } => *effect,
         }
-        .to_string()
     }
 
     pub fn description(&self) -> String {
@@ -126,90 +150,47 @@ pub enum CardEffect {
     StopAttack,
 }
 
-impl ToString for CardEffect {
-    fn to_string(&self) -> String {
+impl CardEffect {
+    /// Stable id of the effect, that keys its text in the `cards.ftl` locales
+    /// as `effect-<id>`
+    #[must_use]
+    pub const fn id(self) -> &'static str {
         match self {
-            Self::NoEffect => "",
-            Self::PlusTwoVsData => "+2 against data cards",
-            Self::DiscardBuzzwords => "Discard all buzzword cards plaied",
-            Self::PlusTwoVsDeceptive => "+2 against a deceptive Use-case",
-            Self::PlusFourVsData => "+4 against data cards",
-            Self::RemovesEffect => "Removes all card effects ",
-            Self::DiscardOne => "Discard 1 of your rival's plaied cards",
-            Self::DiscardBuzzwordsRival => "Your rival discards all buzzword cards they plaied",
-            Self::DiscardTwo => "Discard 2 of your rival's plaied cards",
-            Self::PlusOneData => "+1 with other data cards",
-            Self::PlusOnePython => "+1 with python",
-            Self::DiscardOneEach => "Randomly discard 1 plaied card each",
-            Self::CannotDiscard => "Your plaied cards cannot be discarded",
-            Self::DiscardThreeDrawTwo => "Discard 3 cards, draw 2",
-            Self::DiscardThree => "Discard 3 cards",
-            Self::DiscardAttack => "Discard all your attack cards",
-            Self::AllDrawFour => "Everybody draws 4 cards",
-            Self::AllDiscardFour => "Everybody discards 4 cards",
-            Self::AllDiscardOne => "Everybody discards 1 card",
-            Self::CardsToNextPlayer => "All plaiers give their cards to the next plaier. Cannot be blocked.",
-            Self::Antitrust => "All plaiers with more than 9 cards discard 10 cards ",
-            Self::ChangeHands => "Change hands with another plaier",
-            Self::FourCardVc => "Do a 4 card VC funding",
-            Self::DrawTwo => "Draw 2 cards from the deck",
-            Self::DrawThree => "Draw 3 cards from the deck",
-            Self::ReviveCard => "Revive any card from the discard pile",
-            Self::SpyPlayer => "Spy any plAIer's hand and steal one card",
-            Self::StealCat => "Steal 1 card from a plAIer. 4 if used as counter for the 'CEO has requested this'. Use it any time.",
-            Self::Steal2Cards => "Steal 2 cards from a plAIer",
-            Self::StopEffect => "plAI anytime to stop any effect",
-            Self::StopAttack => "plAI anytime to stop an attack",
-            Self::PlusTwoVsManagers => "+2 against  CEOs and Managers",
-            Self::PlusTwoBuzzwords => "+2 to all buzzwords",
-            Self::PlusThreeCEOs => "+3 against CEOs and Companies",
-        }.to_string()
-    }
-}
-
-#[derive(Debug, PartialEq, Eq)]
-pub struct ParseCardEffectError;
-
-impl FromStr for CardEffect {
-    type Err = ParseCardEffectError;
-    fn from_str(item: &str) -> Result<Self, Self::Err> {
-        Ok(match item {
-            "" => Self::NoEffect,
-              "+2 against data cards"=>Self::PlusTwoVsData,
-              "Discard all buzzword cards plaied"=>Self::DiscardBuzzwords,
-              "+2 against a deceptive Use-case"=>Self::PlusTwoVsDeceptive,
-              "+4 against data cards"=>Self::PlusFourVsData,
-              "Removes all card effects "=>Self::RemovesEffect,
-              "Discard 1 of your rival's plaied cards"=>Self::DiscardOne,
-              "Your rival discards all buzzword cards they plaied"=>Self::DiscardBuzzwordsRival,
-              "Discard 2 of your rival's plaied cards"=>Self::DiscardTwo,
-              "+1 with other data cards"=>Self::PlusOneData,
-              "+1 with python"=>Self::PlusOnePython,
-              "Randomly discard 1 plaied card each"=>Self::DiscardOneEach,
-              "Your plaied cards cannot be discarded"=>Self::CannotDiscard,
-              "Discard 3 cards, draw 2"=>Self::DiscardThreeDrawTwo,
-              "Discard 3 cards"=>Self::DiscardThree,
-              "Discard all your attack cards"=>Self::DiscardAttack,
-              "Everybody draws 4 cards"=>Self::AllDrawFour,
-              "Everybody discards 4 cards"=>Self::AllDiscardFour,
-              "Everybody discards 1 card"=>Self::AllDiscardOne,
-              "All plaiers give their cards to the next plaier. Cannot be blocked."=>Self::CardsToNextPlayer,
-              "All plaiers with more than 9 cards discard 10 cards "=>Self::Antitrust,
-              "Change hands with another plaier"=>Self::ChangeHands,
-              "Do a 4 card VC funding"=>Self::FourCardVc,
-              "Draw 2 cards from the deck"=>Self::DrawTwo,
-              "Draw 3 cards from the deck"=>Self::DrawThree,
-              "Revive any card from the discard pile"=>Self::ReviveCard,
-              "Spy any plAIer's hand and steal one card"=>Self::SpyPlayer,
-              "Steal 1 card from a plAIer. 4 if used as counter for the 'CEO has requested this'. Use it any time."=>Self::StealCat,
-              "Steal 2 cards from a plAIer"=>Self::Steal2Cards,
-              "plAI anytime to stop any effect"=>Self::StopEffect,
-              "plAI anytime to stop an attack"=>Self::StopAttack,
-              "+2 against  CEOs and Managers"=>Self::PlusTwoVsManagers,
-              "+2 to all buzzwords"=>Self::PlusTwoBuzzwords,
-              "+3 against CEOs and Companies"=>Self::PlusThreeCEOs,
-              &_ => todo!(),
-        })
+            Self::NoEffect => "no-effect",
+            Self::DiscardBuzzwords => "discard-buzzwords",
+            Self::PlusTwoVsData => "plus-two-vs-data",
+            Self::PlusTwoVsDeceptive => "plus-two-vs-deceptive",
+            Self::PlusFourVsData => "plus-four-vs-data",
+            Self::PlusOneData => "plus-one-data",
+            Self::PlusOnePython => "plus-one-python",
+            Self::PlusTwoVsManagers => "plus-two-vs-managers",
+            Self::PlusTwoBuzzwords => "plus-two-buzzwords",
+            Self::PlusThreeCEOs => "plus-three-ceos",
+            Self::RemovesEffect => "removes-effect",
+            Self::DiscardOne => "discard-one",
+            Self::DiscardBuzzwordsRival => "discard-buzzwords-rival",
+            Self::DiscardTwo => "discard-two",
+            Self::DiscardOneEach => "discard-one-each",
+            Self::CannotDiscard => "cannot-discard",
+            Self::DiscardThreeDrawTwo => "discard-three-draw-two",
+            Self::DiscardThree => "discard-three",
+            Self::DiscardAttack => "discard-attack",
+            Self::AllDrawFour => "all-draw-four",
+            Self::AllDiscardFour => "all-discard-four",
+            Self::Antitrust => "antitrust",
+            Self::CardsToNextPlayer => "cards-to-next-player",
+            Self::ChangeHands => "change-hands",
+            Self::AllDiscardOne => "all-discard-one",
+            Self::FourCardVc => "four-card-vc",
+            Self::DrawTwo => "draw-two",
+            Self::DrawThree => "draw-three",
+            Self::ReviveCard => "revive-card",
+            Self::SpyPlayer => "spy-player",
+            Self::StealCat => "steal-cat",
+            Self::Steal2Cards => "steal-2-cards",
+            Self::StopEffect => "stop-effect",
+            Self::StopAttack => "stop-attack",
+        }
     }
 }
 
@@ -224,599 +205,705 @@ pub fn get_cards_available() -> Vec<Card> {
     use Card::{Adversary, Buzzword, MarketEvent, Special, UseCase};
     vec![
         Adversary {
+            id: "nyob-ngo".to_string(),
             title: "nyob (NGO)".to_string(),
             description: "Fighting against giants with GDPR.".to_string(),
             strength: 1,
             effect: CardEffect::PlusTwoVsData,
         },
         Adversary {
+            id: "100x-roi".to_string(),
             title: "100x ROI".to_string(),
             description: "You put 10€ and get 1000€ back. You always contribute to society speculating, right? Right? ".to_string(),
             strength: 1,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "consultancy-company".to_string(),
             title: "Consultancy company".to_string(),
             description: "In a single project, one consultancy it's OK, two it's too much and three it's just chaos.".to_string(),
             strength: 1,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "middle-manager".to_string(),
             title: "Middle manager".to_string(),
             description: "Trying to scoop water out of the pool with a teaspoon.".to_string(),
             strength: 1,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "twitter-mob".to_string(),
             title: "Twitter Mob".to_string(),
             description: "With a new name, now without controversies, hate, sexism, racism or insults. GG WP EZ. JK.".to_string(),
             strength: 1,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "new-sheriff".to_string(),
             title: "New Sheriff".to_string(),
             description: "Ready to save the town with zero trust, bullish estimations, sign offs, weekly deadlines, CoC and nanomanagement.".to_string(),
             strength: 1,
             effect: CardEffect::DiscardBuzzwords,
         },
         Adversary {
+            id: "xnet-ngo".to_string(),
             title: "xNet (NGO)".to_string(),
             description: "Technopolitics for the new era. Fighting for schools and students with open tech.".to_string(),
             strength: 2,
             effect: CardEffect::PlusTwoVsDeceptive,
         },
         Adversary {
+            id: "regulatory-bodies".to_string(),
             title: "Regulatory bodies".to_string(),
             description: "They are taking their time, but they are doing more and more to mitigate harm.".to_string(),
             strength: 2,
             effect: CardEffect::PlusFourVsData,
         },
         Adversary {
+            id: "200-poorly-paid-outsiders".to_string(),
             title: "200 poorly paid outsiders".to_string(),
             description: "Intelligence yes, artificial no. Cost: probably less than developing your AI but you can suffer a leak if you don't pay them well enough.".to_string(),
             strength: 2,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "ai-regulation".to_string(),
             title: "AI regulation".to_string(),
             description: "It can go anywhere... And it will please no-one.".to_string(),
             strength: 2,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "double-ceos".to_string(),
             title: "Double CEOs".to_string(),
             description: "Double trouble. ".to_string(),
             strength: 2,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "human-rights".to_string(),
             title: "Human Rights".to_string(),
             description: "What was this again? Ah, that thing we agreed on that we forget exists if there is no oil to be seized.".to_string(),
             strength: 2,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "pendrive-found".to_string(),
             title: "Pendrive Found".to_string(),
             description: "What will it contain? Cat pictures? For sure there is nothing phishy in it...".to_string(),
             strength: 2,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "tech-debt".to_string(),
             title: "Tech Debt".to_string(),
             description: "It's that critical code written 2 years ago, no one understands the dark magic behind it.".to_string(),
             strength: 2,
             effect: CardEffect::RemovesEffect,
         },
         Adversary {
+            id: "tech-international".to_string(),
             title: "Tech International".to_string(),
             description: "Workers united against the monopolies of knowledge and decisions. They protest against your killer drone program.".to_string(),
             strength: 2,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "expert-ceo".to_string(),
             title: "'Expert' CEO".to_string(),
             description: "They want all buzzwords in: AI, BigData, NFT, LLM, Quantum ML...".to_string(),
             strength: 3,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "daily-standups".to_string(),
             title: "Daily Standups".to_string(),
             description: "They should be 15 min, have 30 min scheduled and last 2h".to_string(),
             strength: 3,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "deepfake".to_string(),
             title: "DeepFake".to_string(),
             description: "You got played! You thought that call from a politician was real despite being generated by an external actor.".to_string(),
             strength: 3,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "eff-ngo".to_string(),
             title: "EFF (NGO)".to_string(),
             description: "One of many that without its efforts would leave us in a darker web.".to_string(),
             strength: 3,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "rich-white-straight-senoro".to_string(),
             title: "Rich white straight señoro".to_string(),
             description: "He has enough money to buy judges, media and your startup! Maybe racist, misogynist and wants you to work 20h/day.".to_string(),
             strength: 3,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "wikileaks".to_string(),
             title: "Wikileaks ".to_string(),
             description: "You know those files that show illegal practices? Yep, they are no longer secret.".to_string(),
             strength: 3,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "cloud-is-down".to_string(),
             title: "Cloud is down".to_string(),
             description: "That happens. Half of the internet does not work today and you are affected.".to_string(),
             strength: 4,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "global-scale".to_string(),
             title: "Global Scale".to_string(),
             description: "You are two technical people but your model has to support half the planet for 50 €/month.".to_string(),
             strength: 4,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "tech-giant".to_string(),
             title: "Tech Giant".to_string(),
             description: "Their motto was don't be evil. They removed it. Now why would that be?".to_string(),
             strength: 5,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "toxic-manager".to_string(),
             title: "Toxic Manager".to_string(),
             description: "He overpromised and now you have all the pressure to deliver 50% of it on time and there is a resignation party.".to_string(),
             strength: 5,
             effect: CardEffect::NoEffect,
         },
         Adversary {
+            id: "soshana-zuboff".to_string(),
             title: "Soshana Zuboff".to_string(),
             description: "Author of The Era of Surveillance Capitalism: The Fight for a Human Future at the New Frontier of Power.".to_string(),
             strength: 6,
             effect: CardEffect::NoEffect,
         },
         Buzzword {
+            id: "accountability".to_string(),
             title: "Accountability".to_string(),
             description: "Show me that the tech is going to be fair and legal before you put it out.".to_string(),
             strength: -1,
             effect: CardEffect::DiscardOne,
         },
         Buzzword {
+            id: "gdpr-fine".to_string(),
             title: "GDPR fine".to_string(),
             description: "Let's be honest, it's low and a small percentage of the damage done.".to_string(),
             strength: -1,
             effect: CardEffect::DiscardOne,
         },
         Buzzword {
+            id: "user-centric".to_string(),
             title: "User Centric".to_string(),
             description: "Focused on selling all user data. ".to_string(),
             strength: -1,
             effect: CardEffect::DiscardOne,
         },
         Buzzword {
+            id: "data-outliers".to_string(),
             title: "Data Outliers".to_string(),
             description: "If someone has 10 phones and you have 0, then the average is 5 phones each.".to_string(),
             strength: -1,
             effect: CardEffect::DiscardBuzzwordsRival,
         },
         Buzzword {
+            id: "investor-protection".to_string(),
             title: "Investor protection".to_string(),
             description: "You have business angels protecting you.".to_string(),
             strength: -2,
             effect: CardEffect::DiscardTwo,
         },
         Buzzword {
+            id: "trolley-dilemma".to_string(),
             title: "Trolley dilemma".to_string(),
             description: "Your AI solves it. Every human dies.".to_string(),
             strength: -2,
             effect: CardEffect::DiscardTwo,
         },
         Buzzword {
+            id: "voice-recognition".to_string(),
             title: "Voice Recognition".to_string(),
             description: "With your voice you can order in my shop. You don't even need to use your computer at all!".to_string(),
             strength: -2,
             effect: CardEffect::DiscardTwo,
         },
         Buzzword {
+            id: "more-data".to_string(),
             title: "More Data".to_string(),
             description: "You just need more data to improve you AI's poor results. Or you can accept it does not work. ".to_string(),
             strength: 1,
             effect: CardEffect::PlusOneData,
         },
         Buzzword {
+            id: "artificial-intelligence".to_string(),
             title: "Artificial Intelligence".to_string(),
             description: "Despite widespread understanding, AI does not resemble our human way of thinking. ".to_string(),
             strength: 1,
             effect: CardEffect::PlusOnePython,
         },
         Buzzword {
+            id: "machine-learning".to_string(),
             title: "Machine Learning".to_string(),
             description: "Statistics on algorithms on steroids learning from patterns.".to_string(),
             strength: 1,
             effect: CardEffect::PlusOnePython,
         },
         Buzzword {
+            id: "nlp".to_string(),
             title: "NLP".to_string(),
             description: "I can process language, write like Shakespeare or Cervantes, and even advance in quantum electrodynamics.".to_string(),
             strength: 1,
             effect: CardEffect::PlusOnePython,
         },
         Buzzword {
+            id: "ethical-oversight-board".to_string(),
             title: "Ethical Oversight board".to_string(),
             description: "Internal review organ with zero power, in Facebook's case.".to_string(),
             strength: 1,
             effect: CardEffect::DiscardOneEach,
         },
         Buzzword {
+            id: "unicorn".to_string(),
             title: "Unicorn".to_string(),
             description: "Your new hire is a data scientist, data engineer, a tech influencer and now an analytics engineer too.".to_string(),
             strength: 1,
             effect: CardEffect::CannotDiscard,
         },
         Buzzword {
+            id: "much-more-data".to_string(),
             title: "Much More Data".to_string(),
             description: "When more data is not enough for your hungry training. ".to_string(),
             strength: 2,
             effect: CardEffect::PlusOneData,
         },
         Buzzword {
+            id: "neural-network".to_string(),
             title: "Neural Network".to_string(),
             description: "Your brain in my chip since 1943. Now with more computing power.".to_string(),
             strength: 2,
             effect: CardEffect::PlusOnePython,
         },
         Buzzword {
+            id: "deep-learning".to_string(),
             title: "Deep Learning".to_string(),
             description: "Neural networks but with fancier names like keras, tensorflow, pytorch...".to_string(),
             strength: 2,
             effect: CardEffect::NoEffect,
         },
         Buzzword {
+            id: "hugginface".to_string(),
             title: "hugginface".to_string(),
             description: "Every AI practitioner wants to hug them for the great job they are doing.".to_string(),
             strength: 2,
             effect: CardEffect::PlusOnePython,
         },
         Buzzword {
+            id: "coded-bias".to_string(),
             title: "Coded Bias".to_string(),
             description: "When your phone cannot detect you face it's because more crimes are predicted in your neighbourhood.".to_string(),
             strength: 2,
             effect: CardEffect::DiscardOneEach,
         },
         Buzzword {
+            id: "even-more-data".to_string(),
             title: "Even More Data".to_string(),
             description: "You are an even bigger data hoarder than the tech monopolies.".to_string(),
             strength: 3,
             effect: CardEffect::PlusOneData,
         },
         Buzzword {
+            id: "pandas-and-scikit-learn".to_string(),
             title: "pandas and scikit Learn".to_string(),
             description: "Old, trusty and maybe a bit too much backwards compatible. Your data scientist's best friends.".to_string(),
             strength: 3,
             effect: CardEffect::NoEffect,
         },
         Buzzword {
+            id: "python".to_string(),
             title: "Python".to_string(),
             description: "The snake introducing you to this world while playing snake jazz.".to_string(),
             strength: 3,
             effect: CardEffect::NoEffect,
         },
         Buzzword {
+            id: "python".to_string(),
             title: "Python".to_string(),
             description: "The snake introducing you to this world while playing snake jazz.".to_string(),
             strength: 3,
             effect: CardEffect::NoEffect,
         },
         Buzzword {
+            id: "statistics".to_string(),
             title: "Statistics".to_string(),
             description: "Old, but still rocks. It can put your Startup to sleep if you ignore it in favor of the fancy algorithms.".to_string(),
             strength: 3,
             effect: CardEffect::NoEffect,
         },
         MarketEvent {
+            id: "mass-layoffs".to_string(),
             title: "Mass Layoffs".to_string(),
             description: "Ignite the hearts of venture capitalists by constantly firing your employees.".to_string(),
             effect: CardEffect::DiscardThreeDrawTwo,
         },
         MarketEvent {
+            id: "cloud-invoice".to_string(),
             title: "Cloud invoice".to_string(),
             description: "When clouds don't bring rain it means you have to pay.".to_string(),
             effect: CardEffect::DiscardThree,
         },
         MarketEvent {
+            id: "workers-union".to_string(),
             title: "Workers Union".to_string(),
             description: "You want to bust'em with all the illegal ways you can think of.".to_string(),
             effect: CardEffect::DiscardAttack,
         },
         MarketEvent {
+            id: "hype".to_string(),
             title: "Hype".to_string(),
             description: "Your marketing efforts paid off and now you are near the peak of inflated expectations.".to_string(),
             effect: CardEffect::AllDrawFour,
         },
         MarketEvent {
+            id: "dot-com-crash".to_string(),
             title: "Dot-com crash".to_string(),
             description: "It's the 2000 crisis again. Brace your phone and laptop".to_string(),
             effect: CardEffect::AllDiscardFour,
         },
         MarketEvent {
+            id: "machine-learning-operations".to_string(),
             title: "Machine Learning Operations".to_string(),
             description: "They think it's easy to deploy a model to production. Reality says it costs everyone... a card!".to_string(),
             effect: CardEffect::AllDiscardOne,
         },
         Special {
+            id: "international-data-transfer".to_string(),
             title: "International data transfer".to_string(),
             description: "All data passes through Fort Meade.".to_string(),
             effect: CardEffect::CardsToNextPlayer,
         },
         Special {
+            id: "antitrust".to_string(),
             title: "Antitrust".to_string(),
             description: "You are not too big to fail.*\n\n* Your lobbyist might change this sentence.".to_string(),
             effect: CardEffect::Antitrust,
         },
         Special {
+            id: "antitrust".to_string(),
             title: "Antitrust".to_string(),
             description: "You are not too big to fail.*\n\n* Your lobbyist might change this sentence.".to_string(),
             effect: CardEffect::Antitrust,
         },
         Special {
+            id: "antitrust".to_string(),
             title: "Antitrust".to_string(),
             description: "You are not too big to fail.*\n\n* Your lobbyist might change this sentence.".to_string(),
             effect: CardEffect::Antitrust,
         },
         Special {
+            id: "antitrust".to_string(),
             title: "Antitrust".to_string(),
             description: "You are not too big to fail.*\n\n* Your lobbyist might change this sentence.".to_string(),
             effect: CardEffect::Antitrust,
         },
         Special {
+            id: "antitrust".to_string(),
             title: "Antitrust".to_string(),
             description: "You are not too big to fail.*\n\n* Your lobbyist might change this sentence.".to_string(),
             effect: CardEffect::Antitrust,
         },
         Special {
+            id: "talent-exchange".to_string(),
             title: "Talent exchange".to_string(),
             description: "That's how juniors become seniors.".to_string(),
             effect: CardEffect::ChangeHands,
         },
         Special {
+            id: "talent-exchange".to_string(),
             title: "Talent exchange".to_string(),
             description: "That's how juniors become seniors.".to_string(),
             effect: CardEffect::ChangeHands,
         },
         Special {
+            id: "press-coverage".to_string(),
             title: "Press coverage".to_string(),
             description: "You pray to the 'gods' of the longtermism cult to make it rain.".to_string(),
             effect: CardEffect::FourCardVc,
         },
         Special {
+            id: "killer-drones".to_string(),
             title: "Killer drones".to_string(),
             description: "Congrats, you have a great new contract!".to_string(),
             effect: CardEffect::DrawTwo,
         },
         Special {
+            id: "vc-funding".to_string(),
             title: "VC funding".to_string(),
             description: "You have impressed the AI ML GI BD VC AGI DOOM community.".to_string(),
             effect: CardEffect::DrawThree,
         },
         Special {
+            id: "10x-data-scientist".to_string(),
             title: "10x data scientist".to_string(),
             description: "Do those exist? (Spoiler: you are one of them!)".to_string(),
             effect: CardEffect::ReviveCard,
         },
         Special {
+            id: "10x-engineer".to_string(),
             title: "10x engineer".to_string(),
             description: "Do those exist? (Spoiler: you are one of them!)".to_string(),
             effect: CardEffect::ReviveCard,
         },
         Special {
+            id: "financial-data".to_string(),
             title: "Financial Data".to_string(),
             description: "What you bought will tell us if you are pregnant before even you know it.".to_string(),
             effect: CardEffect::SpyPlayer,
         },
         Special {
+            id: "global-espionage".to_string(),
             title: "Global espionage".to_string(),
             description: "Sponsored by your friendly Human Rights government.".to_string(),
             effect: CardEffect::SpyPlayer,
         },
         Special {
+            id: "global-espionage".to_string(),
             title: "Global espionage".to_string(),
             description: "Sponsored by your friendly Human Rights government.".to_string(),
             effect: CardEffect::SpyPlayer,
         },
         Special {
+            id: "surveillance".to_string(),
             title: "Surveillance".to_string(),
             description: "Walking the narrow line of spying on people and keeping everything secret makes for a distinct power imbalance. But you already knew this, didn't you?".to_string(),
             effect: CardEffect::SpyPlayer,
         },
         Special {
+            id: "ceo-s-friend".to_string(),
             title: "CEO's friend".to_string(),
             description: "Last minute tasks have no effect on you when you are the CEO's bestie.".to_string(),
             effect: CardEffect::StealCat,
         },
         Special {
+            id: "ai-bot-army".to_string(),
             title: "AI bot army".to_string(),
             description: "Publicize a distraction with AI-written messages on social media.".to_string(),
             effect: CardEffect::Steal2Cards,
         },
         Special {
+            id: "board-chaos".to_string(),
             title: "Board Chaos".to_string(),
             description: "While their executives fired each other it's now your opportunity to hire.".to_string(),
             effect: CardEffect::Steal2Cards,
         },
         Special {
+            id: "great-password".to_string(),
             title: "Great Password".to_string(),
             description: "Your rivals were pwned due to their admin password being 'password'. ".to_string(),
             effect: CardEffect::Steal2Cards,
         },
         Special {
+            id: "ransomware".to_string(),
             title: "Ransomware".to_string(),
             description: "Your rival opens an email and... SURPRISE! Their assets are now yours.".to_string(),
             effect: CardEffect::Steal2Cards,
         },
         Special {
+            id: "the-ceo-has-requested-this".to_string(),
             title: "The CEO has requested this".to_string(),
             description: "All requests come at 6 PM and are for later today.".to_string(),
             effect: CardEffect::Steal2Cards,
         },
         Special {
+            id: "the-ceo-has-requested-this".to_string(),
             title: "The CEO has requested this".to_string(),
             description: "All requests come at 6 PM and are for later today.".to_string(),
             effect: CardEffect::Steal2Cards,
         },
         Special {
+            id: "arxiv-org".to_string(),
             title: "arXiv.org ".to_string(),
             description: "Where great and bad articles reside. But... can you distinguish them? ".to_string(),
             effect: CardEffect::StopEffect,
         },
         Special {
+            id: "privacy".to_string(),
             title: "Privacy".to_string(),
             description: "Ah! That which may no longer exist but is essential to our personal growth as individuals.".to_string(),
             effect: CardEffect::StopEffect,
         },
         Special {
+            id: "privacy".to_string(),
             title: "Privacy".to_string(),
             description: "Ah! That which may no longer exist but is essential to our personal growth as individuals.".to_string(),
             effect: CardEffect::StopEffect,
         },
         Special {
+            id: "terms-and-conditions".to_string(),
             title: "Terms and Conditions".to_string(),
             description: "Accept selling your soul to the devil without reading a single word".to_string(),
             effect: CardEffect::StopEffect,
         },
         Special {
+            id: "bias".to_string(),
             title: "Bias".to_string(),
             description: "Big tech tells us their machines are perfect. We need to stop this bullshit.".to_string(),
             effect: CardEffect::StopAttack,
         },
         Special {
+            id: "ethics".to_string(),
             title: "Ethics".to_string(),
             description: "We need time to stop and think. To answer how we can do good, minimize harm, respect human autonomy and be fair and just.".to_string(),
             effect: CardEffect::StopAttack,
         },
         Special {
+            id: "security".to_string(),
             title: "Security".to_string(),
             description: "Cyber security, military security, private security, cartel security, job security. You choose the field!".to_string(),
             effect: CardEffect::StopAttack,
         },
         UseCase {
+            id: "chatbot".to_string(),
             title: "Chatbot".to_string(),
             description: "You trained it with internet forums and now it's racist and far-right.".to_string(),
             strength: 1,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "impersonate-deceased".to_string(),
             title: "Impersonate deceased".to_string(),
             description: "Your grandma now recommends products from Amazonia.".to_string(),
             strength: 1,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "selling-smoke".to_string(),
             title: "Selling smoke".to_string(),
             description: "Your purpose is to automate snake oil selling with AI generated products, descriptions, images, marketing strategies, everything.".to_string(),
             strength: 1,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "crowdfunding".to_string(),
             title: "Crowdfunding".to_string(),
             description: "Don't depend on Big Capital. Your sole purpose is the same as this game. Steal Branderson's record.".to_string(),
             strength: 2,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "discrimination".to_string(),
             title: "Discrimination".to_string(),
             description: "We may or may not intentionally train our AI to discriminate based on historical data. But we'll hide it regardless.".to_string(),
             strength: 2,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "predict-crimes".to_string(),
             title: "Predict crimes".to_string(),
             description: "None detected in rich neighbourhoods and you don't see what's wrong with that.".to_string(),
             strength: 2,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "select-employees".to_string(),
             title: "Select employees".to_string(),
             description: "Since it's based on historical data, only white men will be accepted for high paying jobs.".to_string(),
             strength: 2,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "stock-trading".to_string(),
             title: "Stock trading".to_string(),
             description: "Get rich making people poor. Now with AI avoiding the Black-Scholes catastrophe.".to_string(),
             strength: 2,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "win-art-competitions".to_string(),
             title: "Win art competitions".to_string(),
             description: "Generate images and videos from random text for internet amazement. Like black cats plaiing this game.".to_string(),
             strength: 2,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "generate-deepfakes".to_string(),
             title: "Generate DeepFakes".to_string(),
             description: "Don't you want to start WWIII?".to_string(),
             strength: 3,
             effect: CardEffect::PlusTwoVsManagers,
         },
         UseCase {
+            id: "fix-climate".to_string(),
             title: "Fix climate".to_string(),
             description: "The planet? Greenwashing? I just see the green of money.".to_string(),
             strength: 3,
             effect: CardEffect::PlusTwoBuzzwords,
         },
         UseCase {
+            id: "facial-recognition".to_string(),
             title: "Facial Recognition".to_string(),
             description: "Your repressive government's favorite tool. Ah, it can also be used to unlock phones too.".to_string(),
             strength: 3,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "internet-of-things".to_string(),
             title: "Internet Of Things".to_string(),
             description: "The promised future since 1982. The smart thing (and you) will learn all about the owners.".to_string(),
             strength: 3,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "manipulate-elections".to_string(),
             title: "Manipulate elections".to_string(),
             description: "Who? We? What proof do you have? - Cambridge Analytica".to_string(),
             strength: 3,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "predict-criminals-using-faces".to_string(),
             title: "Predict criminals using faces".to_string(),
             description: "Pseudo-science from the 30s. Why it's still used nowadays astonishes us.".to_string(),
             strength: 4,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "self-driving-car".to_string(),
             title: "Self-driving car".to_string(),
             description: "Who does it have to put in danger?".to_string(),
             strength: 4,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "sentient-ai".to_string(),
             title: "Sentient AI".to_string(),
             description: "It's for sure a marketing strategy. All media is onboard, but what does it mean to be sentient? ".to_string(),
             strength: 4,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "write-black-mirror-episodes".to_string(),
             title: "Write black mirror episodes".to_string(),
             description: "A favorite of the content-creation industry. Will it be like Westworld? ".to_string(),
             strength: 5,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "auto-moderation".to_string(),
             title: "Auto Moderation".to_string(),
             description: "You say content moderation is  easy and your AI will solve it. In reality, it's highly complex even for humans.".to_string(),
             strength: 6,
             effect: CardEffect::NoEffect,
         },
         UseCase {
+            id: "osint-monitoring".to_string(),
             title: "OSINT monitoring".to_string(),
             description: "Monitor Corporations and Governments to reverse the power imbalance.".to_string(),
             strength: 7,
@@ -6,8 +6,8 @@ mod cards;
 mod errors;
 mod player;
 mod round;
-use crate::game::cards::{get_cards_available, Deck, DeckEmptyError};
-pub use crate::game::cards::{Card, CardEffect, Hand};
+pub use crate::game::cards::{get_cards_available, Card, CardEffect, Hand};
+use crate::game::cards::{Deck, DeckEmptyError};
 pub use crate::game::player::Player;
 use crate::game::player::PlayerState;
 use crate::game::round::Round;
@@ -16,10 +16,14 @@ pub use crate::game::errors::{Error, Result};
 
 ///Represents a game of PLAI, containing all the items and logic
 ///to play the game until the end.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Game {
     pub players: Vec<Player>,
     deck: Box<Deck>,
+    /// Played and discarded cards, the last one on top. Games saved before
+    /// there was a pile load it empty.
+    #[serde(default)]
+    discard: Vec<Card>,
     pub round: Round,
 }
 
@@ -37,6 +41,7 @@ impl Game {
         let mut game = Self {
             players,
             deck: Box::new(deck),
+            discard: vec![],
             round: Round::new(0, players_id),
         };
 
@@ -64,6 +69,28 @@ impl Game {
         self.round.next_player();
     }
 
+    /// Number of cards left in the draw pile
+    #[must_use]
+    pub fn deck_len(&self) -> usize {
+        self.deck.len()
+    }
+
+    /// Face up pile of played and discarded cards, the last one on top
+    #[must_use]
+    pub fn discard_pile(&self) -> &[Card] {
+        &self.discard
+    }
+
+    /// Choices the game is waiting for. None once it has ended.
+    #[must_use]
+    pub fn pending_decisions(&self) -> Vec<Decision> {
+        if self.has_ended() {
+            vec![]
+        } else {
+            vec![Decision::TurnAction(self.active_player().id)]
+        }
+    }
+
     /// # Panics
     /// If user id does not exist
     pub fn get_player(&self, id: Uuid) -> &Player {
@@ -121,12 +148,15 @@ impl Game {
     /// * ``EmptyDeck``
     /// * ``GameEnded``
     /// * ``NotYourTurn``
+    /// * ``RuleBreak`` if the card played is not in the hand
+    /// * ``NotImplemented`` for the actions the game does not have yet
+    #[cfg_attr(feature = "ssr", tracing::instrument(skip(self), err(Debug)))]
     pub fn turn_action(&mut self, player_id: Uuid, action: TurnAction) -> Result<()> {
         self.ensure_player_can_act(player_id)?;
 
         match action {
             TurnAction::Funding(f) => self.do_funding(f),
-            TurnAction::HostileTakeover(target) => todo!(),
+            TurnAction::HostileTakeover(target) => Err(Error::NotImplemented),
             TurnAction::SpecialCard(c) => self.do_special(c),
         }?;
 
@@ -143,13 +173,15 @@ impl Game {
                 self.active_player_mut().hand.add_multiple(cards);
                 Ok(())
             }
-            Funding::Regional => todo!(),
-            Funding::VC => todo!(),
+            Funding::Regional | Funding::VC => Err(Error::NotImplemented),
         }
     }
 
     fn do_special(&mut self, c: &Card) -> Result<()> {
         use crate::game::cards::CardEffect::*;
+        if !self.active_player().hand.contains(c) {
+            return Err(Error::RuleBreak);
+        }
         self.active_player_mut().hand.use_card(c);
         let deck = &mut self.deck;
         if let Card::Special { effect, .. } = c {
@@ -174,13 +206,13 @@ impl Game {
                     // TODO players should be able to choose discarded cards
                     // TODO discarded cards should go to the discard pile
                     self.players.iter_mut().try_for_each(|p| {
-                        p.hand.take(4);
+                        self.discard.extend(p.hand.take(4));
                         Ok(())
                     })?;
                 }
                 AllDiscardOne => {
                     self.players.iter_mut().try_for_each(|p| {
-                        p.hand.take(1);
+                        self.discard.extend(p.hand.take(1));
                         Ok(())
                     })?;
                 }
@@ -191,7 +223,7 @@ impl Game {
                         .iter_mut()
                         .filter(|p| p.hand.len() > 9)
                         .try_for_each(|p| {
-                            p.hand.take(10);
+                            self.discard.extend(p.hand.take(10));
                             Ok(())
                         })?;
                 }
@@ -222,9 +254,11 @@ impl Game {
                 StealCat => return Err(Error::NotImplemented),
                 Steal2Cards => return Err(Error::NotImplemented),
                 DiscardAttack => {
-                    self.active_player_mut()
+                    let attacks = self
+                        .active_player_mut()
                         .hand
                         .remove(|c| matches!(c, Card::Adversary { .. }));
+                    self.discard.extend(attacks);
                 }
 
                 DiscardBuzzwords
@@ -249,6 +283,7 @@ impl Game {
                 StopEffect | StopAttack => return Err(Error::RuleBreak),
             }
         };
+        self.discard.push(c.clone());
 
         Ok(())
     }
@@ -322,6 +357,38 @@ mod test_game_actions {
         assert_eq!(game.deck.len(), original_deck_size - 1);
     }
 
+    #[rstest]
+    #[case::regional(Funding::Regional)]
+    #[case::vc(Funding::VC)]
+    fn pending_fundings_are_not_implemented(mut game: Game, #[case] funding: Funding) {
+        let active_pid = game.active_player().id;
+
+        let res = game.turn_action(active_pid, TurnAction::Funding(funding));
+
+        assert_eq!(res, Err(Error::NotImplemented));
+        assert_eq!(game.active_player().id, active_pid, "The turn goes on");
+    }
+
+    #[rstest]
+    fn hostile_takeover_is_not_implemented(mut game: Game) {
+        let active_pid = game.active_player().id;
+        let target = game.players[1].clone();
+
+        let res = game.turn_action(active_pid, TurnAction::HostileTakeover(&target));
+
+        assert_eq!(res, Err(Error::NotImplemented));
+    }
+
+    #[rstest]
+    fn cards_not_in_hand_cannot_be_played(mut game: Game) {
+        let active_pid = game.active_player().id;
+        let card = special_card(CardEffect::DrawTwo);
+
+        let res = game.turn_action(active_pid, TurnAction::SpecialCard(&card));
+
+        assert_eq!(res, Err(Error::RuleBreak));
+    }
+
     // #[rstest]
     // fn regional_funding(mut game: Game) {
     //     let original_deck_size = game.deck.len();
@@ -366,6 +433,7 @@ mod test_game_actions {
 
     fn special_card(effect: CardEffect) -> Card {
         Special {
+            id: String::new(),
             title: String::new(),
             description: String::new(),
             effect,
@@ -442,6 +510,27 @@ mod test_game_actions {
         assert_eq!(end_cards, should_be_cards);
     }
 
+    #[rstest]
+    fn discarded_and_played_cards_go_to_the_pile(mut game: Game) {
+        let card = special_card(CardEffect::AllDiscardOne);
+        game.active_player_mut().hand.add(card.clone());
+        let p = game.active_player().id;
+
+        game.turn_action(p, TurnAction::SpecialCard(&card))
+            .expect("The card is in the hand");
+
+        assert_eq!(game.discard_pile().len(), game.players.len() + 1);
+        assert_eq!(
+            game.discard_pile().last(),
+            Some(&card),
+            "Played card on top"
+        );
+        assert_eq!(
+            game.pending_decisions(),
+            vec![Decision::TurnAction(game.active_player().id)]
+        );
+    }
+
     #[rstest]
     fn special_all_discard_four(mut game: Game) {
         let should_be_cards: Vec<usize> = game.players.iter().map(|p| p.hand.len() - 4).collect();
@@ -495,6 +584,7 @@ mod test_game_actions {
             let p = game.active_player_mut();
             p.hand.add(card.clone());
             p.hand.add(Card::Adversary {
+                id: "test".to_string(),
                 title: "test".to_string(),
                 description: "test".to_string(),
                 strength: 0,
@@ -610,6 +700,14 @@ pub enum Funding {
     VC,
 }
 
+/// A choice the game waits for before going on
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "t", content = "c")]
+pub enum Decision {
+    /// The player has to fund, play a special card or take over a rival
+    TurnAction(Uuid),
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TurnAction<'a> {
     Funding(Funding),
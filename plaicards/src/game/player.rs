@@ -1,10 +1,11 @@
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::game::cards::{Card, Hand};
 use crate::game::Funding::{Family, Regional, VC};
 use crate::game::TurnAction;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum PlayerState {
     Startup,
     OpenSource,
@@ -32,7 +33,7 @@ impl std::fmt::Display for PlayerState {
 ///
 /// * [`PlayerState`]
 /// * [`Hand`] representing the cards the player has
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Player {
     pub id: Uuid,
     name: String,
@@ -104,6 +105,7 @@ mod test_player {
 
         for i in 1..=60 {
             cards.push(Card::Adversary {
+                id: format!("card-{i}"),
                 title: format!("Card_{i}"),
                 effect: CardEffect::NoEffect,
                 description: String::new(),
@@ -137,6 +139,7 @@ mod test_player {
     #[rstest]
     fn possible_actions_special_card(mut player: Player) {
         let c = Card::Special {
+            id: "c".into(),
             title: "c".into(),
             description: "c".into(),
             effect: CardEffect::DrawTwo,
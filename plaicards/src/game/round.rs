@@ -3,9 +3,10 @@
 //! * Round number
 //! * Player order
 
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Round {
     pub number: u32,
     players: Vec<Uuid>,
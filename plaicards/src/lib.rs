@@ -5,11 +5,15 @@
 mod game;
 
 pub mod app;
+#[cfg(feature = "ssr")]
+pub mod config;
 pub mod web;
 
 pub mod error_template;
 #[cfg(feature = "ssr")]
 pub mod fileserv;
+#[cfg(feature = "ssr")]
+pub mod telemetry;
 
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
@@ -1,79 +1,47 @@
 #![allow(unused_variables)]
 
 use std::collections::HashMap;
+use std::future::IntoFuture;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
 
-use axum::{
-    body::Body as AxumBody,
-    extract::{ws::Message, Path, RawQuery, State},
-    http::{header::HeaderMap, Request},
-    response::{IntoResponse, Response},
-    routing::get,
-    Router,
-};
-use leptos::{get_configuration, provide_context, tracing, Props};
-use leptos_axum::{
-    generate_route_list, handle_server_fns_with_context, render_app_to_stream_with_context,
-    LeptosRoutes,
-};
+use leptos::{get_configuration, tracing};
+use leptos_axum::generate_route_list;
 
 use tower_http::compression::CompressionLayer;
 // Tracing
 use tracing::Instrument;
-use tracing::{event, info, instrument, span, Level};
-use tracing_subscriber::filter::LevelFilter;
-use tracing_subscriber::FmtSubscriber;
-
-use plaicards::web::board::{board_handler, GameController};
-use plaicards::web::lobby::lobby_handler;
-use plaicards::web::{lobby::Player, ssr::AppState, Result as Res};
-use plaicards::{app::App, web::lobby::ssr::LobbyController};
-use plaicards::{fileserv::file_and_error_handler, web::lobby::ssr::Lobby};
-
-async fn server_fn_handler(
-    State(app_state): State<AppState>,
-    path: Path<String>,
-    raw_query: RawQuery,
-    request: Request<AxumBody>,
-) -> impl IntoResponse {
-    handle_server_fns_with_context(
-        move || {
-            provide_context(app_state.lobby.clone());
-        },
-        request,
-    )
-    .await
-}
+use tracing::{error, event, info, instrument, span, warn, Level};
 
-async fn leptos_routes_handler(
-    State(app_state): State<AppState>,
-    req: Request<AxumBody>,
-) -> Response {
-    let handler = render_app_to_stream_with_context(
-        app_state.leptos_options.clone(),
-        move || {
-            provide_context(app_state.lobby.clone());
-        },
-        App,
-    );
-    handler(req).await.into_response()
-}
+use leptos_plausible::ssr as plausible;
+use plaicards::app::App;
+use plaicards::config::Config;
+use plaicards::web::board::GameController;
+use plaicards::web::health::Health;
+use plaicards::web::lobby::ssr::LobbyController;
+use plaicards::web::ssr::{router, AppState};
+use plaicards::web::subscriptions::ssr::{open_mailer, open_store, Subscriptions};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Tracing
-    // See https://github.com/tokio-rs/tracing?tab=readme-ov-file
-    let log_level = if cfg!(debug_assertions) {
-        tracing::Level::TRACE
-    } else {
-        tracing::Level::INFO
-    };
-    let subscriber = tracing_subscriber::fmt()
-        .with_max_level(log_level)
-        .compact()
-        .finish();
-    tracing::subscriber::set_global_default(subscriber)?;
-    // TODO add axum insight
-    // https://crates.io/crates/axum-insights
+    // Configuration. `--config <file>` and `--print-config` are the only arguments
+    let args: Vec<String> = std::env::args().collect();
+    let config_path = args
+        .iter()
+        .position(|a| a == "--config")
+        .and_then(|i| args.get(i + 1))
+        .map(PathBuf::from);
+    let config = Config::load(config_path.as_deref())?;
+    if args.iter().any(|a| a == "--print-config") {
+        print!("{}", config.to_toml());
+        return Ok(());
+    }
+
+    // Logs and traces. Spans are flushed when the guard drops
+    let _telemetry = plaicards::telemetry::init(&config)?;
 
     // Setting get_configuration(None) means we'll be using cargo-leptos's env values
     // For deployment these variables are:
@@ -87,39 +55,119 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let addr = leptos_options.site_addr;
     let routes = generate_route_list(App);
 
-    let lobby_controller = LobbyController::new().await;
-
-    let game_controller = GameController::new().await;
+    // Rooms are shared with other instances when there is a broker
+    let bus = plaicards::web::bus::open(&config.bus).await?;
+    info!("Room messages go through {bus:?}");
+    let lobby_controller = LobbyController::with_bus(bus.clone(), config.channels.lobby);
+
+    let analytics = plausible::Analytics::new(&config.plausible, &config.public_url);
+    let game_controller = GameController::with_bus(bus, config.channels.game_room)
+        .with_analytics(analytics.clone())
+        .with_seat_grace(Duration::from_secs(config.seat_grace_secs));
+    let rooms_path = config.shutdown.rooms_path.clone();
+    let restored = game_controller.restore(&rooms_path).await?;
+    if restored > 0 {
+        info!("Restored {restored} game rooms from {rooms_path:?}");
+    }
+    #[cfg(feature = "dev-seed")]
+    if config.dev_seed {
+        plaicards::web::board::seed::seed(&game_controller).await?;
+    }
+
+    let subscriptions = Subscriptions::new(
+        open_store(&config.subscriptions)?,
+        open_mailer(&config.subscriptions),
+        &config.public_url,
+        &config.subscriptions,
+    );
 
+    let grace = Duration::from_secs(config.shutdown.grace_secs);
+    let drain_delay = Duration::from_secs(config.shutdown.drain_secs);
+    let health = Health::default();
     let app_state = AppState {
         leptos_options,
-        gc: game_controller,
-        lobby: lobby_controller,
-        routes: routes.clone(),
+        config,
+        gc: game_controller.clone(),
+        lobby: lobby_controller.clone(),
+        health: health.clone(),
+        subscriptions,
+        analytics,
+        routes,
     };
 
     let compression_layer = CompressionLayer::new().deflate(true).gzip(true);
 
-    // build our application with a route
-    let app = Router::new()
-        .route(
-            "/api/*fn_name",
-            get(server_fn_handler).post(server_fn_handler),
-        )
-        .route("/lobby/:lobby_id/ws", get(lobby_handler))
-        .route("/game/ws", get(board_handler))
-        .leptos_routes_with_handler(routes, get(leptos_routes_handler))
-        .fallback(file_and_error_handler)
-        .with_state(app_state)
-        .layer(compression_layer);
+    let app = router(app_state).layer(compression_layer);
 
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
     info!("Starting server. Listening on http://{}", &addr);
-    axum::serve(listener, app.into_make_service()).await?;
-
+    // On SIGTERM fail the readiness probe, give the load balancer time to
+    // notice, then stop accepting connections and tell clients to leave
+    let drained = Arc::new(Notify::new());
+    let drain = {
+        let (gc, drained) = (game_controller.clone(), drained.clone());
+        async move {
+            shutdown_signal().await;
+            info!("Shutting down. Not ready anymore, draining in {drain_delay:?}");
+            health.start_draining();
+            lobby_controller.close();
+            tokio::time::sleep(drain_delay).await;
+            info!("Draining connections");
+            gc.notify_shutdown().await;
+            drained.notify_one();
+        }
+    };
+    // The peer address is used to rate limit subscriptions
+    let server = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(drain);
+
+    tokio::select! {
+        res = server.into_future() => res?,
+        () = async {
+            drained.notified().await;
+            tokio::time::sleep(grace).await;
+        } => warn!("Connections still open after {grace:?}. Closing them"),
+    }
+
+    let saved = game_controller.save(&rooms_path).await?;
+    info!("Saved {saved} game rooms to {rooms_path:?}");
+    game_controller.release_all().await;
     Ok(())
 }
 
+/// Completes on Ctrl+C or, on Unix, SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("Cannot listen to Ctrl+C: {e}");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                error!("Cannot listen to SIGTERM: {e}");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {},
+        () = terminate => {},
+    }
+}
+
 #[cfg(not(feature = "ssr"))]
 pub fn main() {
     // no client-side main function
@@ -0,0 +1,62 @@
+//! Logs and, when a collector is configured, OTLP trace export.
+
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{runtime, trace, Resource};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+use crate::config::Config;
+
+/// Flushes the pending spans when dropped. Keep it alive until shutdown.
+#[derive(Debug)]
+pub struct Guard {
+    exporting: bool,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        if self.exporting {
+            opentelemetry::global::shutdown_tracer_provider();
+        }
+    }
+}
+
+/// Installs the global subscriber: compact logs plus the OTLP exporter if
+/// `telemetry.otlp_endpoint` is set. Needs a Tokio runtime.
+///
+/// # Errors
+///
+/// If the exporter cannot be built or a subscriber is already installed
+pub fn init(config: &Config) -> Result<Guard, Box<dyn std::error::Error>> {
+    let otel = match &config.telemetry.otlp_endpoint {
+        Some(endpoint) => {
+            let tracer = opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .tonic()
+                        .with_endpoint(endpoint),
+                )
+                .with_trace_config(trace::config().with_resource(Resource::new(vec![
+                    KeyValue::new("service.name", config.telemetry.service_name.clone()),
+                ])))
+                .install_batch(runtime::Tokio)?;
+            Some(tracing_opentelemetry::layer().with_tracer(tracer))
+        }
+        None => None,
+    };
+    let exporting = otel.is_some();
+
+    tracing_subscriber::registry()
+        .with(LevelFilter::from_level(config.level()))
+        .with(tracing_subscriber::fmt::layer().compact())
+        .with(otel)
+        .try_init()?;
+
+    if let Some(endpoint) = &config.telemetry.otlp_endpoint {
+        tracing::info!("Exporting traces to {endpoint}");
+    }
+    Ok(Guard { exporting })
+}
@@ -0,0 +1,78 @@
+//! Dashboard of what runs on the server.
+//!
+//! Every server function asks for the `admin_token` of the configuration.
+//! Without one configured the area is closed.
+
+use cfg_if::cfg_if;
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+mod view;
+pub use self::view::AdminPage;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        pub mod ssr;
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Overview {
+    pub lobbies: Vec<LobbySummary>,
+    pub rooms: Vec<RoomSummary>,
+    pub subscribers: SubscriberCounts,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LobbySummary {
+    pub id: Uuid,
+    pub players: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoomSummary {
+    pub id: Uuid,
+    pub players: Vec<PlayerSummary>,
+    pub spectators: usize,
+    pub round: u32,
+    /// Cards left in the draw pile
+    pub deck: usize,
+    /// Updates done to the game
+    pub seq: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerSummary {
+    pub id: Uuid,
+    pub name: String,
+    pub connected: bool,
+    /// It is the turn of this player
+    pub active: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubscriberCounts {
+    /// Waiting for the confirmation
+    pub pending: usize,
+    pub active: usize,
+    pub unsubscribed: usize,
+}
+
+#[server(GetOverview, "/api/admin")]
+pub async fn get_overview(token: String) -> Result<Overview, ServerFnError> {
+    use self::ssr::*;
+    authorize(&token)?;
+    overview().await
+}
+
+#[server(TerminateRoom, "/api/admin")]
+pub async fn terminate_room(token: String, room_id: Uuid) -> Result<(), ServerFnError> {
+    use crate::web::board::GameController;
+    self::ssr::authorize(&token)?;
+    use_context::<GameController>()
+        .ok_or_else(|| ServerFnError::ServerError("Game rooms missing".into()))?
+        .terminate(&room_id)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use leptos::{use_context, ServerFnError};
+use tracing::warn;
+
+use super::{LobbySummary, Overview, PlayerSummary, RoomSummary, SubscriberCounts};
+use crate::config::Config;
+use crate::web::board::GameController;
+use crate::web::lobby::ssr::{lobbys, LobbyController};
+use crate::web::subscriptions::ssr::{subscriptions, Result as SubResult, SubscriptionStore};
+
+/// Checks `token` against the `admin_token` of the configuration
+///
+/// # Errors
+///
+/// If the token is wrong or there is no admin token configured
+pub fn authorize(token: &str) -> Result<(), ServerFnError> {
+    let expected = use_context::<Config>().and_then(|c| c.admin_token);
+    if is_authorized(expected.as_deref(), token) {
+        Ok(())
+    } else {
+        warn!("Rejected admin token");
+        Err(ServerFnError::ServerError("Unauthorized".into()))
+    }
+}
+
+#[must_use]
+pub fn is_authorized(expected: Option<&str>, token: &str) -> bool {
+    expected.is_some_and(|e| constant_time_eq(e.as_bytes(), token.as_bytes()))
+}
+
+/// Compares without stopping at the first difference, so the time taken
+/// does not tell how much of the token was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Overview of everything in the context of the request
+///
+/// # Errors
+///
+/// If some controller is missing from the context or the store fails
+pub async fn overview() -> Result<Overview, ServerFnError> {
+    let gc = use_context::<GameController>()
+        .ok_or_else(|| ServerFnError::ServerError("Game rooms missing".into()))?;
+    let subs = subscriptions()?;
+    Ok(summarize(&lobbys()?, &gc, subs.store()).await?)
+}
+
+/// # Errors
+///
+/// If the subscriptions cannot be read
+pub async fn summarize(
+    lobbies: &LobbyController,
+    gc: &GameController,
+    store: &dyn SubscriptionStore,
+) -> SubResult<Overview> {
+    let mut overview = Overview {
+        subscribers: count_subscribers(store)?,
+        ..Overview::default()
+    };
+
+    for lobby in lobbies.lobbies().await {
+        overview.lobbies.push(LobbySummary {
+            id: lobby.id,
+            players: lobby.players().await.into_iter().map(|p| p.name).collect(),
+        });
+    }
+
+    for room in gc.rooms().await {
+        let snapshot = room.snapshot(None).await;
+        let connected = room.connected().await;
+        overview.rooms.push(RoomSummary {
+            id: room.id,
+            players: snapshot
+                .players
+                .into_iter()
+                .map(|p| PlayerSummary {
+                    connected: connected.contains(&p.id),
+                    active: p.id == snapshot.active_player,
+                    id: p.id,
+                    name: p.name,
+                })
+                .collect(),
+            spectators: room.spectators.load(std::sync::atomic::Ordering::SeqCst),
+            round: snapshot.round,
+            deck: snapshot.deck,
+            seq: snapshot.seq,
+        });
+    }
+    overview.rooms.sort_by_key(|r| r.id);
+
+    Ok(overview)
+}
+
+fn count_subscribers(store: &dyn SubscriptionStore) -> SubResult<SubscriberCounts> {
+    let mut counts = SubscriberCounts::default();
+    for s in store.all()? {
+        if s.is_active() {
+            counts.active += 1;
+        } else if s.is_pending() {
+            counts.pending += 1;
+        } else {
+            counts.unsubscribed += 1;
+        }
+    }
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use rstest::rstest;
+    use uuid::Uuid;
+
+    use super::{is_authorized, summarize};
+    use crate::web::board::GameController;
+    use crate::web::lobby::ssr::LobbyController;
+    use crate::web::lobby::Player;
+    use crate::web::subscriptions::ssr::{MemoryStore, Subscription, SubscriptionStore};
+
+    #[rstest]
+    #[case::right(Some("0123456789abcdef"), "0123456789abcdef", true)]
+    #[case::wrong(Some("0123456789abcdef"), "0123456789abcdeF", false)]
+    #[case::prefix(Some("0123456789abcdef"), "0123", false)]
+    #[case::not_configured(None, "", false)]
+    fn admin_token_is_checked(
+        #[case] expected: Option<&str>,
+        #[case] token: &str,
+        #[case] authorized: bool,
+    ) {
+        assert_eq!(is_authorized(expected, token), authorized);
+    }
+
+    #[rstest]
+    async fn overview_lists_everything() {
+        let lobbies = LobbyController::with_capacity(1);
+        let lobby = lobbies.create().await.expect("New lobby");
+        lobby
+            .update_player(Player {
+                id: Uuid::new_v4(),
+                name: "MetaTrust".into(),
+            })
+            .await
+            .expect("New player");
+
+        let gc = GameController::with_capacity(1);
+        let players = [
+            (Uuid::new_v4(), "MetaTrust".to_string()),
+            (Uuid::new_v4(), "MalaTesta".to_string()),
+        ];
+        let room_id = Uuid::new_v4();
+        gc.new_game(room_id, &players).await.expect("New room");
+        let room = gc.get(&room_id).await.expect("Room exists");
+        room.join(players[1].0)
+            .await
+            .expect("Player is in the game");
+
+        let store = MemoryStore::default();
+        let now = Utc::now();
+        store
+            .save(Subscription::new("pending@metatrust.ai".into(), now))
+            .expect("Store works");
+        let mut active = Subscription::new("active@metatrust.ai".into(), now);
+        active.confirmed_at = Some(now);
+        store.save(active).expect("Store works");
+
+        let overview = summarize(&lobbies, &gc, &store).await.expect("Store works");
+
+        assert_eq!(overview.lobbies.len(), 1);
+        assert_eq!(overview.lobbies[0].players, vec!["MetaTrust".to_string()]);
+        assert_eq!(overview.rooms.len(), 1);
+        let connected: Vec<bool> = overview.rooms[0]
+            .players
+            .iter()
+            .map(|p| p.connected)
+            .collect();
+        assert_eq!(connected, vec![false, true]);
+        assert_eq!(
+            overview.rooms[0]
+                .players
+                .iter()
+                .filter(|p| p.active)
+                .count(),
+            1
+        );
+        assert_eq!(overview.subscribers.pending, 1);
+        assert_eq!(overview.subscribers.active, 1);
+        assert_eq!(overview.subscribers.unsubscribed, 0);
+    }
+}
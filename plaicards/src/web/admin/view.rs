@@ -0,0 +1,165 @@
+use data_encoding::BASE64URL_NOPAD;
+use leptos::html::Input;
+use leptos::*;
+use leptos_meta::Title;
+use uuid::Uuid;
+
+use super::{get_overview, terminate_room, LobbySummary, Overview, RoomSummary};
+
+#[component]
+pub fn AdminPage() -> impl IntoView {
+    let (token, set_token) = create_signal(String::new());
+    let token_input = create_node_ref::<Input>();
+
+    let terminate = create_action(move |room_id: &Uuid| {
+        let (token, room_id) = (token.get_untracked(), *room_id);
+        async move { terminate_room(token, room_id).await }
+    });
+    let (refresh, set_refresh) = create_signal(0_u32);
+
+    let overview = create_resource(
+        move || (token.get(), refresh.get(), terminate.version().get()),
+        |(token, _, _)| async move {
+            if token.is_empty() {
+                None
+            } else {
+                Some(get_overview(token).await)
+            }
+        },
+    );
+
+    let on_login = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        if let Some(input) = token_input.get() {
+            set_token.set(input.value());
+        }
+    };
+
+    view! {
+      <Title text="PLAI admin"/>
+      <div class="p-6 mx-auto max-w-5xl">
+        <h1 class="mb-6 text-3xl font-bold">"Admin"</h1>
+        <form class="flex gap-2 mb-8" on:submit=on_login>
+          <input
+            type="password"
+            placeholder="Admin token"
+            autocomplete="off"
+            class="flex-auto py-2 px-3 rounded-md border"
+            node_ref=token_input
+          />
+          <button type="submit" class="py-2 px-3 text-white bg-gray-700 rounded-md">
+            "Enter"
+          </button>
+          <button
+            type="button"
+            class="py-2 px-3 rounded-md border"
+            on:click=move |_| set_refresh.update(|r| *r += 1)
+          >
+            "Refresh"
+          </button>
+        </form>
+        <Transition fallback=|| view! { <p>"Loading..."</p> }>
+          {move || {
+              overview
+                  .get()
+                  .map(|res| match res {
+                      None => view! { <p>"Enter the admin token"</p> }.into_view(),
+                      Some(Err(e)) => view! { <p class="text-red-700">{e.to_string()}</p> }.into_view(),
+                      Some(Ok(overview)) => view! { <OverviewTables overview terminate/> }.into_view(),
+                  })
+          }}
+        </Transition>
+      </div>
+    }
+}
+
+#[component]
+fn OverviewTables(
+    overview: Overview,
+    terminate: Action<Uuid, Result<(), ServerFnError>>,
+) -> impl IntoView {
+    let subs = overview.subscribers;
+    view! {
+      <section class="mb-8">
+        <h2 class="mb-2 text-xl font-semibold">"Newsletter"</h2>
+        <p>
+          {format!(
+              "{} active, {} pending confirmation, {} unsubscribed",
+              subs.active,
+              subs.pending,
+              subs.unsubscribed,
+          )}
+        </p>
+      </section>
+      <section class="mb-8">
+        <h2 class="mb-2 text-xl font-semibold">
+          {format!("Game rooms ({})", overview.rooms.len())}
+        </h2>
+        {overview.rooms.into_iter().map(|room| view! { <Room room terminate/> }).collect_view()}
+      </section>
+      <section>
+        <h2 class="mb-2 text-xl font-semibold">
+          {format!("Lobbies ({})", overview.lobbies.len())}
+        </h2>
+        <ul>
+          {overview
+              .lobbies
+              .into_iter()
+              .map(|LobbySummary { id, players }| {
+                  view! {
+                    <li>
+                      <code>{id.to_string()}</code>
+                      ": "
+                      {if players.is_empty() { "empty".to_string() } else { players.join(", ") }}
+                    </li>
+                  }
+              })
+              .collect_view()}
+        </ul>
+      </section>
+    }
+}
+
+#[component]
+fn Room(room: RoomSummary, terminate: Action<Uuid, Result<(), ServerFnError>>) -> impl IntoView {
+    let watch = format!("/watch/{}", BASE64URL_NOPAD.encode(room.id.as_bytes()));
+    let id = room.id;
+
+    view! {
+      <div class="p-4 mb-4 rounded-md border">
+        <div class="flex justify-between items-center">
+          <a href=watch class="font-mono underline">{id.to_string()}</a>
+          <button
+            class="py-1 px-2 text-white bg-red-700 rounded-md"
+            on:click=move |_| terminate.dispatch(id)
+          >
+            "Terminate"
+          </button>
+        </div>
+        <p class="text-sm text-gray-600">
+          {format!(
+              "Round {}, {} cards in the deck, {} updates, {} spectators",
+              room.round,
+              room.deck,
+              room.seq,
+              room.spectators,
+          )}
+        </p>
+        <ul class="mt-2">
+          {room
+              .players
+              .into_iter()
+              .map(|p| {
+                  view! {
+                    <li class:font-bold=p.active class:text-gray-400={!p.connected}>
+                      {p.name}
+                      {if p.connected { "" } else { " (offline)" }}
+                      {if p.active { " ← turn" } else { "" }}
+                    </li>
+                  }
+              })
+              .collect_view()}
+        </ul>
+      </div>
+    }
+}
@@ -2,7 +2,7 @@ use cfg_if::cfg_if;
 
 mod view;
 
-pub use self::view::Board;
+pub use self::view::{Board, Spectate};
 
 cfg_if! {
     if #[cfg(feature = "ssr")] {
@@ -11,7 +11,10 @@ cfg_if! {
         mod websocket;
 
         pub use self::websocket::handler as board_handler;
-        pub use self::ssr::GameController;
+        pub use self::ssr::{GameController, DEFAULT_CAPACITY};
+
+        #[cfg(feature = "dev-seed")]
+        pub mod seed;
     }
 }
 
@@ -19,8 +22,9 @@ cfg_if! {
 pub mod msg {
 
     #[cfg(feature = "ssr")]
-    use crate::game::{Card as GCard, CardEffect, Player as GPlayer};
-    use crate::game::{Error as GError, Funding, TurnAction};
+    use crate::game::{Card as GCard, Game, Player as GPlayer};
+    use crate::game::{CardEffect, Error as GError, TurnAction};
+    pub use crate::game::{Decision, Funding};
 
     use serde::{Deserialize, Serialize};
     use serde_json::Result;
@@ -31,9 +35,11 @@ pub mod msg {
             serde_json::to_string(&self).expect("Malformed struct. Cannot serialize to String")
         }
 
-        #[must_use]
-        fn from_str(msg: &'a str) -> Self {
-            serde_json::from_str(msg).expect("Malformed Message")
+        /// # Errors
+        ///
+        /// If `msg` is not a valid message of this type
+        fn from_str(msg: &'a str) -> Result<Self> {
+            serde_json::from_str(msg)
         }
     }
     #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -60,14 +66,46 @@ pub mod msg {
         NotYourTurn,
         BadMove,
         BadRequest,
-        PlayerLeft,
         GameEnded,
+        /// The server is restarting. The game is saved and clients should
+        /// reconnect in a moment.
+        ShuttingDown,
+
+        /// The connection of a player dropped. The seat stays reserved for a while.
+        PlayerLeft {
+            id: Uuid,
+        },
+        /// A player that left came back
+        PlayerRejoined {
+            id: Uuid,
+        },
 
-        NextPlayer(Uuid),
+        /// Number of people watching the game
+        Spectators(usize),
 
-        Players(Vec<Player>),
-        RivalHand { id: Uuid, num_cards: usize },
-        AddCard(Card),
+        /// Chat message of a player. Private ones only reach `from` and `to`.
+        Chat {
+            from: Uuid,
+            to: Option<Uuid>,
+            text: String,
+        },
+        Reaction {
+            from: Uuid,
+            reaction: Reaction,
+        },
+        /// Too many chat messages or reactions in a short time
+        SlowDown,
+
+        /// Complete view of the game. Sent on (re)connect and on resync
+        StateSnapshot(Snapshot),
+        /// Public changes of the game, broadcasted to the whole room
+        StateDiff(StateDiff),
+        /// Changes in the hand of the receiving player for update `seq`
+        HandDiff {
+            seq: u64,
+            added: Vec<Card>,
+            removed: Vec<Card>,
+        },
 
         NotImplemented,
     }
@@ -86,10 +124,13 @@ pub mod msg {
 
     #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
     pub struct Card {
+        /// See [`crate::game::Card::id`], keys the texts shown in the browser
+        #[serde(rename = "i")]
+        pub id: String,
         #[serde(rename = "t")]
         pub title: String,
         #[serde(rename = "e")]
-        pub effect: String,
+        pub effect: CardEffect,
         #[serde(rename = "d")]
         pub description: String,
         #[serde(rename = "ty")]
@@ -108,6 +149,7 @@ pub mod msg {
         fn from(c: &GCard) -> Self {
             Self {
                 ctype: c.ctype(),
+                id: c.id(),
                 title: c.title(),
                 effect: c.effect(),
                 description: c.description(),
@@ -115,31 +157,337 @@ pub mod msg {
         }
     }
 
+    /// Only special cards can be played on their own, any other card is a
+    /// [`ServerMsg::BadRequest`]
     #[cfg(feature = "ssr")]
-    impl Into<GCard> for Card {
-        fn into(self) -> GCard {
-            if self.ctype == "Special" {
-                GCard::Special {
-                    title: self.title,
-                    effect: self.effect.parse::<CardEffect>().unwrap(),
-                    description: self.description,
-                }
+    impl TryFrom<Card> for GCard {
+        type Error = ServerMsg;
+
+        fn try_from(c: Card) -> core::result::Result<Self, ServerMsg> {
+            if c.ctype == "Special" {
+                Ok(Self::Special {
+                    id: c.id,
+                    title: c.title,
+                    effect: c.effect,
+                    description: c.description,
+                })
             } else {
-                todo!()
+                Err(ServerMsg::BadRequest)
+            }
+        }
+    }
+
+    /// Number of cards a player holds. The only public information of a hand.
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct HandSize {
+        pub id: Uuid,
+        pub num_cards: usize,
+    }
+
+    /// View of the game for a given client at update `seq`.
+    ///
+    /// Clients replace their whole state with it. Afterwards they only receive
+    /// [`StateDiff`] and [`ServerMsg::HandDiff`] with consecutive `seq` numbers,
+    /// so a jump in the sequence means something got lost and the client
+    /// should ask for a [`ClientMsg::Resync`].
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+    pub struct Snapshot {
+        pub seq: u64,
+        pub players: Vec<Player>,
+        /// Cards of the receiving player. Always empty for spectators.
+        pub hand: Vec<Card>,
+        pub hands: Vec<HandSize>,
+        pub active_player: Uuid,
+        pub round: u32,
+        /// Cards left in the draw pile
+        pub deck: usize,
+        /// Discard pile, the last card on top
+        pub discard: Vec<Card>,
+        /// Choices the game is waiting for
+        pub pending: Vec<Decision>,
+        /// Players whose connection dropped. Their seat is reserved for a while.
+        pub away: Vec<Uuid>,
+    }
+
+    #[cfg(feature = "ssr")]
+    impl Snapshot {
+        /// Builds the view of `game` for `viewer`. Spectators use `None`.
+        #[must_use]
+        pub fn new(game: &Game, seq: u64, viewer: Option<Uuid>) -> Self {
+            let hand = viewer
+                .map(|id| {
+                    game.get_player(id)
+                        .hand
+                        .card_iter()
+                        .map(Card::from)
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            Self {
+                seq,
+                players: game.players.iter().map(Player::from).collect(),
+                hand,
+                hands: game
+                    .players
+                    .iter()
+                    .map(|p| HandSize {
+                        id: p.id,
+                        num_cards: p.hand.len(),
+                    })
+                    .collect(),
+                active_player: game.active_player().id,
+                round: game.round.number,
+                deck: game.deck_len(),
+                discard: game.discard_pile().iter().map(Card::from).collect(),
+                pending: game.pending_decisions(),
+                away: vec![],
             }
         }
     }
 
+    /// Public changes between two consecutive updates.
+    ///
+    /// Only the values that changed are set.
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+    pub struct StateDiff {
+        pub seq: u64,
+        pub hands: Vec<HandSize>,
+        pub active_player: Option<Uuid>,
+        pub round: Option<u32>,
+        pub deck: Option<usize>,
+        /// The whole discard pile, when it changed
+        pub discard: Option<Vec<Card>>,
+        pub pending: Option<Vec<Decision>>,
+    }
+
+    impl StateDiff {
+        /// Computes the public changes from `before` to `after`, taking the
+        /// sequence number of the latter.
+        #[must_use]
+        pub fn between(before: &Snapshot, after: &Snapshot) -> Self {
+            Self {
+                seq: after.seq,
+                hands: after
+                    .hands
+                    .iter()
+                    .filter(|h| !before.hands.contains(h))
+                    .copied()
+                    .collect(),
+                active_player: changed(&before.active_player, &after.active_player),
+                round: changed(&before.round, &after.round),
+                deck: changed(&before.deck, &after.deck),
+                discard: (before.discard != after.discard).then(|| after.discard.clone()),
+                pending: (before.pending != after.pending).then(|| after.pending.clone()),
+            }
+        }
+    }
+
+    fn changed<T: PartialEq + Copy>(old: &T, new: &T) -> Option<T> {
+        if old == new {
+            None
+        } else {
+            Some(*new)
+        }
+    }
+
+    /// Returns the cards `(added, removed)` to go from hand `before` to `after`.
+    ///
+    /// Hands can have repeated cards, so each copy is accounted for.
+    #[must_use]
+    pub fn diff_cards(before: &[Card], after: &[Card]) -> (Vec<Card>, Vec<Card>) {
+        let mut removed = before.to_vec();
+        let mut added = vec![];
+        for card in after {
+            match removed.iter().position(|c| c == card) {
+                Some(pos) => {
+                    removed.remove(pos);
+                }
+                None => added.push(card.clone()),
+            }
+        }
+        (added, removed)
+    }
+
+    /// Applies a [`ServerMsg::HandDiff`] to a hand
+    pub fn apply_cards_diff(hand: &mut Vec<Card>, added: Vec<Card>, removed: &[Card]) {
+        for card in removed {
+            if let Some(pos) = hand.iter().position(|c| c == card) {
+                hand.remove(pos);
+            }
+        }
+        hand.extend(added);
+    }
+
     /// Encapsulates all messages the client will send
     #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
     #[serde(tag = "t", content = "c")]
     pub enum ClientMsg {
-        Connect { game_id: Uuid, player_id: Uuid },
+        Connect {
+            game_id: Uuid,
+            player_id: Uuid,
+        },
+        /// Join the game as a read-only observer
+        Spectate {
+            game_id: Uuid,
+        },
+        /// Ask for a new [`Snapshot`] after detecting a gap in the updates
+        Resync,
 
         // Actions
         DoFunding(Funding),
         PlayCard(Card),
+
+        // Social. Up to [`MAX_CHAT_LEN`] characters, everyone if `to` is `None`.
+        Chat {
+            to: Option<Uuid>,
+            text: String,
+        },
+        React(Reaction),
+    }
+
+    impl ClientMsg {
+        /// Name of the message, without its content. For logs and traces.
+        #[must_use]
+        pub const fn kind(&self) -> &'static str {
+            match self {
+                Self::Connect { .. } => "Connect",
+                Self::Spectate { .. } => "Spectate",
+                Self::Resync => "Resync",
+                Self::DoFunding(_) => "DoFunding",
+                Self::PlayCard(_) => "PlayCard",
+                Self::Chat { .. } => "Chat",
+                Self::React(_) => "React",
+            }
+        }
+    }
+
+    /// Maximum number of characters of a chat message
+    pub const MAX_CHAT_LEN: usize = 280;
+
+    /// Quick reactions players can throw at the table
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Reaction {
+        ThumbsUp,
+        Laugh,
+        Bribe,
+        Fire,
+        Cry,
+    }
+
+    impl Reaction {
+        pub const ALL: [Self; 5] = [
+            Self::ThumbsUp,
+            Self::Laugh,
+            Self::Bribe,
+            Self::Fire,
+            Self::Cry,
+        ];
+
+        #[must_use]
+        pub const fn emoji(self) -> &'static str {
+            match self {
+                Self::ThumbsUp => "👍",
+                Self::Laugh => "😂",
+                Self::Bribe => "💰",
+                Self::Fire => "🔥",
+                Self::Cry => "😭",
+            }
+        }
     }
 
     impl<'a> WsSerDe<'a> for ClientMsg {}
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::{fixture, rstest};
+    use uuid::Uuid;
+
+    use crate::game::CardEffect;
+
+    use super::msg::{apply_cards_diff, diff_cards, Card, Decision, HandSize, Snapshot, StateDiff};
+
+    fn card(title: &str) -> Card {
+        Card {
+            id: title.to_lowercase(),
+            title: title.into(),
+            effect: CardEffect::NoEffect,
+            description: String::new(),
+            ctype: "Special".into(),
+        }
+    }
+
+    #[fixture]
+    fn snapshot() -> Snapshot {
+        let (p1, p2) = (Uuid::new_v4(), Uuid::new_v4());
+        Snapshot {
+            seq: 3,
+            players: vec![],
+            hand: vec![],
+            hands: vec![
+                HandSize {
+                    id: p1,
+                    num_cards: 6,
+                },
+                HandSize {
+                    id: p2,
+                    num_cards: 6,
+                },
+            ],
+            active_player: p1,
+            round: 0,
+            deck: 40,
+            discard: vec![card("a")],
+            pending: vec![Decision::TurnAction(p1)],
+            away: vec![],
+        }
+    }
+
+    #[rstest]
+    fn cards_diff_roundtrip() {
+        let before = vec![card("a"), card("b"), card("b"), card("c")];
+        let after = vec![card("b"), card("c"), card("d"), card("d")];
+
+        let (added, removed) = diff_cards(&before, &after);
+        assert_eq!(added, vec![card("d"), card("d")]);
+        assert_eq!(removed, vec![card("a"), card("b")]);
+
+        let mut hand = before;
+        apply_cards_diff(&mut hand, added, &removed);
+        hand.sort_by(|a, b| a.title.cmp(&b.title));
+        assert_eq!(hand, after);
+    }
+
+    #[rstest]
+    fn state_diff_only_contains_changes(snapshot: Snapshot) {
+        let mut after = snapshot.clone();
+        after.seq += 1;
+        after.hands[1].num_cards = 7;
+        after.active_player = after.hands[1].id;
+        after.deck -= 1;
+
+        let diff = StateDiff::between(&snapshot, &after);
+
+        assert_eq!(diff.seq, 4);
+        assert_eq!(diff.hands, vec![after.hands[1]]);
+        assert_eq!(diff.active_player, Some(after.hands[1].id));
+        assert_eq!(diff.round, None);
+        assert_eq!(diff.deck, Some(39));
+        assert_eq!(diff.discard, None);
+        assert_eq!(diff.pending, None);
+    }
+
+    #[rstest]
+    fn state_diff_has_the_new_pile_and_decisions(snapshot: Snapshot) {
+        let mut after = snapshot.clone();
+        after.seq += 1;
+        after.discard.push(card("b"));
+        after.pending = vec![Decision::TurnAction(after.hands[1].id)];
+
+        let diff = StateDiff::between(&snapshot, &after);
+
+        assert_eq!(diff.discard, Some(vec![card("a"), card("b")]));
+        assert_eq!(diff.pending, Some(after.pending));
+    }
+}
@@ -0,0 +1,215 @@
+//! Game rooms for local UI development, enabled with the `dev-seed` feature.
+//!
+//! Each [`Fixture`] is a game with fixed ids played up to a given [`Stage`],
+//! so the board can be opened directly in interesting situations.
+
+use data_encoding::BASE64URL_NOPAD;
+use tracing::info;
+use uuid::Uuid;
+
+use super::ssr::GameController;
+use crate::game::{Funding, Game, TurnAction};
+use crate::web::{Error, Result as Res};
+
+/// How far a seeded game has been played
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    /// Cards just dealt
+    Fresh,
+    /// After this number of turns
+    Turns(usize),
+    /// Played until the draw pile has at most this number of cards
+    DeckLeft(usize),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Fixture {
+    pub name: &'static str,
+    pub id: Uuid,
+    pub players: &'static [(Uuid, &'static str)],
+    pub stage: Stage,
+}
+
+pub const FIXTURES: &[Fixture] = &[
+    Fixture {
+        name: "fresh",
+        id: Uuid::from_u128(0x9cb1_4765_bbfd_447a_b29e_bb20_3801_acb6),
+        players: &[
+            (
+                Uuid::from_u128(0x541f_4eec_07c3_46b3_942c_4ed1_5e07_f8e4),
+                "MetaTrust",
+            ),
+            (
+                Uuid::from_u128(0x59f9_9963_fddf_49b2_9a4c_8381_b766_8ec3),
+                "MalaTesta",
+            ),
+        ],
+        stage: Stage::Fresh,
+    },
+    Fixture {
+        name: "mid-battle",
+        id: Uuid::from_u128(0x0c6f_1d2e_8a41_4b7e_9d53_2f10_6e8a_c001),
+        players: &[
+            (
+                Uuid::from_u128(0x0c6f_1d2e_8a41_4b7e_9d53_2f10_6e8a_c101),
+                "MetaTrust",
+            ),
+            (
+                Uuid::from_u128(0x0c6f_1d2e_8a41_4b7e_9d53_2f10_6e8a_c102),
+                "GigaSpy",
+            ),
+            (
+                Uuid::from_u128(0x0c6f_1d2e_8a41_4b7e_9d53_2f10_6e8a_c103),
+                "Idefix",
+            ),
+            (
+                Uuid::from_u128(0x0c6f_1d2e_8a41_4b7e_9d53_2f10_6e8a_c104),
+                "BadBiker",
+            ),
+        ],
+        stage: Stage::Turns(10),
+    },
+    Fixture {
+        name: "near deck exhaustion",
+        id: Uuid::from_u128(0x0c6f_1d2e_8a41_4b7e_9d53_2f10_6e8a_c002),
+        players: &[
+            (
+                Uuid::from_u128(0x0c6f_1d2e_8a41_4b7e_9d53_2f10_6e8a_c201),
+                "MalaTesta",
+            ),
+            (
+                Uuid::from_u128(0x0c6f_1d2e_8a41_4b7e_9d53_2f10_6e8a_c202),
+                "GigaSpy",
+            ),
+            (
+                Uuid::from_u128(0x0c6f_1d2e_8a41_4b7e_9d53_2f10_6e8a_c203),
+                "Idefix",
+            ),
+        ],
+        stage: Stage::DeckLeft(3),
+    },
+];
+
+impl Fixture {
+    /// Builds the game and plays it until the [`Stage`] is reached.
+    ///
+    /// Every turn is a family funding, so hands grow and the deck shrinks.
+    /// If the game ends before, it stays as it ended.
+    #[must_use]
+    pub fn game(&self) -> Game {
+        let players: Vec<_> = self
+            .players
+            .iter()
+            .map(|(id, name)| (*id, (*name).to_string()))
+            .collect();
+        let mut game = Game::new(&players);
+
+        let mut turns = 0;
+        loop {
+            let done = match self.stage {
+                Stage::Fresh => true,
+                Stage::Turns(n) => turns >= n,
+                Stage::DeckLeft(n) => game.deck_len() <= n,
+            };
+            if done {
+                break;
+            }
+            let active = game.active_player().id;
+            if game
+                .turn_action(active, TurnAction::Funding(Funding::Family))
+                .is_err()
+            {
+                break;
+            }
+            turns += 1;
+        }
+        game
+    }
+}
+
+fn url_id(id: Uuid) -> String {
+    BASE64URL_NOPAD.encode(id.as_bytes())
+}
+
+/// Adds a room for every fixture and logs the links to join them
+///
+/// Rooms restored from the last shutdown are kept as they were.
+///
+/// # Errors
+///
+/// If a room cannot be added
+pub async fn seed(gc: &GameController) -> Res<()> {
+    for fixture in FIXTURES {
+        match gc.put(gc.room(fixture.id, fixture.game())).await {
+            // Restored from the last shutdown, or owned by another instance
+            Err(Error::Duplicated) => {}
+            res => res?,
+        }
+
+        info!(
+            "Seeded {} game room. Watch it at http://127.0.0.1:3000/watch/{}",
+            fixture.name,
+            url_id(fixture.id)
+        );
+        for (id, name) in fixture.players {
+            info!(
+                "\t{name}: http://127.0.0.1:3000/plai/{}/{}",
+                url_id(fixture.id),
+                url_id(*id)
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use std::collections::HashSet;
+
+    use super::{seed, Stage, FIXTURES};
+    use crate::web::board::GameController;
+
+    #[rstest]
+    fn fixtures_reach_their_stage() {
+        for fixture in FIXTURES {
+            let game = fixture.game();
+            match fixture.stage {
+                Stage::Fresh => assert_eq!(game.round.number, 0),
+                Stage::Turns(n) => {
+                    let cards: usize = game.players.iter().map(|p| p.hand.len()).sum();
+                    assert!(
+                        cards > 6 * game.players.len(),
+                        "{} has no turns",
+                        fixture.name
+                    );
+                }
+                Stage::DeckLeft(n) => {
+                    assert!(game.deck_len() <= n, "{} deck too big", fixture.name)
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    fn fixtures_have_unique_ids() {
+        let ids: HashSet<_> = FIXTURES
+            .iter()
+            .flat_map(|f| std::iter::once(f.id).chain(f.players.iter().map(|(id, _)| *id)))
+            .collect();
+        let total: usize = FIXTURES.iter().map(|f| 1 + f.players.len()).sum();
+        assert_eq!(ids.len(), total);
+    }
+
+    #[rstest]
+    async fn seed_adds_every_fixture() {
+        let gc = GameController::new().await;
+        seed(&gc)
+            .await
+            .expect("Empty controller should accept the seeds");
+
+        for fixture in FIXTURES {
+            assert!(gc.get(&fixture.id).await.is_ok());
+        }
+    }
+}
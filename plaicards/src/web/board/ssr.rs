@@ -1,20 +1,26 @@
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
+use std::path::Path;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{debug, event, info, instrument, trace};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, event, info, instrument, trace, warn};
 use uuid::Uuid;
 
 use axum::extract::FromRef;
 use leptos::use_context;
 use leptos::ServerFnError;
-use tokio::sync::broadcast;
 
-use super::msg::ServerMsg;
-use crate::game::Game;
+use super::msg::{self, ServerMsg, Snapshot, StateDiff, WsSerDe};
+use crate::game::{Game, Result as GResult, TurnAction};
+use crate::web::bus::{LocalBus, Payload, RoomBus, Topic};
+use crate::web::metrics::METRICS;
 use crate::web::{Error, Result as Res};
+use leptos_plausible::ssr::Analytics;
+use leptos_plausible::PropValue;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Player {
@@ -22,73 +28,321 @@ pub struct Player {
     pub name: String,
 }
 
+/// Messages a room keeps for clients that are behind, unless configured
+pub const DEFAULT_CAPACITY: usize = 5;
+
+/// Time a disconnected player keeps the seat before the game moves on
+/// without them, unless configured
+pub const DEFAULT_SEAT_GRACE: Duration = Duration::from_secs(60);
+
+/// Connection status of a player in the room
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Seat {
+    /// Open sockets of the player. More than one while a reconnect races
+    /// with the detection of the old socket being dead.
+    connections: usize,
+    /// Number of times the player went away. Identifies each grace period.
+    departures: u64,
+    /// The grace period expired and the player is still away
+    released: bool,
+}
+
+impl Payload for ServerMsg {
+    fn encode(&self) -> String {
+        self.to_str()
+    }
+
+    fn decode(payload: String) -> Option<Self> {
+        <Self as WsSerDe>::from_str(&payload).ok()
+    }
+}
+
 /// Middleware to store the websocket room
 #[derive(Clone, Debug)]
 pub struct GameRoom {
     pub id: Uuid,
     pub game: Arc<RwLock<Game>>,
-    pub tx: broadcast::Sender<ServerMsg>,
+    /// Messages for every client of the room
+    pub tx: Topic<ServerMsg>,
+    /// Number of clients connected as spectators
+    pub spectators: Arc<AtomicUsize>,
+    /// Sequence number of the last update done to the game
+    seq: Arc<AtomicU64>,
+    /// Players that connected at least once
+    seats: Arc<Mutex<HashMap<Uuid, Seat>>>,
 }
 
 impl GameRoom {
     pub fn new(id: Uuid, players: &[(Uuid, String)]) -> Self {
-        let (tx, _rx) = broadcast::channel::<ServerMsg>(5);
+        Self::from_game(id, Game::new(players), DEFAULT_CAPACITY)
+    }
 
-        let game = Game::new(players);
+    /// Room for a game that may be already started, alone in its own bus
+    pub fn from_game(id: Uuid, game: Game, capacity: usize) -> Self {
+        Self::on_bus(id, game, Arc::new(LocalBus::default()), capacity)
+    }
+
+    /// Room whose messages travel through `bus`
+    pub fn on_bus(id: Uuid, game: Game, bus: Arc<dyn RoomBus>, capacity: usize) -> Self {
         Self {
             id,
-            tx,
+            tx: Topic::new(bus, format!("game:{id}"), capacity),
             game: Arc::new(RwLock::new(game)),
+            spectators: Arc::default(),
+            seq: Arc::default(),
+            seats: Arc::default(),
         }
     }
+
+    /// Current view of the game for `viewer`. Spectators use `None`.
+    pub async fn snapshot(&self, viewer: Option<Uuid>) -> Snapshot {
+        let seats = self.seats.lock().await;
+        let game = self.game.read().await;
+        let mut snapshot = Snapshot::new(&game, self.seq.load(Ordering::SeqCst), viewer);
+        snapshot.away = seats
+            .iter()
+            .filter(|(_, s)| s.connections == 0)
+            .map(|(id, _)| *id)
+            .collect();
+        snapshot
+    }
+
+    /// Players with at least one open connection
+    pub async fn connected(&self) -> Vec<Uuid> {
+        self.seats
+            .lock()
+            .await
+            .iter()
+            .filter(|(_, s)| s.connections > 0)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    pub async fn has_player(&self, player_id: Uuid) -> bool {
+        self.game
+            .read()
+            .await
+            .players
+            .iter()
+            .any(|p| p.id == player_id)
+    }
+
+    /// Registers a new connection of a player.
+    ///
+    /// Returns `true` if the player was away and is taking back the seat.
+    ///
+    /// # Errors
+    ///
+    /// If the player is not part of this game
+    pub async fn join(&self, player_id: Uuid) -> Res<bool> {
+        let mut seats = self.seats.lock().await;
+        if !self.has_player(player_id).await {
+            return Err(Error::NotFound);
+        }
+
+        let seat = seats.entry(player_id).or_default();
+        let rejoined = seat.connections == 0 && seat.departures > 0;
+        seat.connections += 1;
+        seat.released = false;
+        Ok(rejoined)
+    }
+
+    /// Removes a connection of a player.
+    ///
+    /// When it was the last one, the seat stays reserved and it returns the
+    /// departure to give to [`Self::release`] once the grace period ends.
+    pub async fn leave(&self, player_id: Uuid) -> Option<u64> {
+        let mut seats = self.seats.lock().await;
+        let seat = seats.get_mut(&player_id)?;
+        seat.connections = seat.connections.saturating_sub(1);
+        if seat.connections > 0 {
+            return None;
+        }
+        seat.departures += 1;
+        Some(seat.departures)
+    }
+
+    /// Ends the grace period of a player that is still away.
+    ///
+    /// From now on, the turns of the player are skipped. Returns the changes
+    /// if the game had to move on.
+    pub async fn release(&self, player_id: Uuid, departure: u64) -> Option<StateDiff> {
+        let mut seats = self.seats.lock().await;
+        let seat = seats.get_mut(&player_id)?;
+        if seat.connections > 0 || seat.departures != departure {
+            return None;
+        }
+        seat.released = true;
+
+        let mut game = self.game.write().await;
+        let before = Snapshot::new(&game, self.seq.load(Ordering::SeqCst), None);
+        if !Self::skip_released(&mut game, &seats) {
+            return None;
+        }
+        let seq = self.seq.fetch_add(1, Ordering::SeqCst) + 1;
+        let after = Snapshot::new(&game, seq, None);
+        Some(StateDiff::between(&before, &after))
+    }
+
+    /// Passes the turn while the active player has lost the seat.
+    ///
+    /// Returns `true` if the active player changed.
+    fn skip_released(game: &mut Game, seats: &HashMap<Uuid, Seat>) -> bool {
+        let is_released = |id: &Uuid| seats.get(id).is_some_and(|s| s.released);
+        let mut skipped = false;
+        // Bounded so a room with everyone away does not spin forever
+        for _ in 0..game.players.len() {
+            if !is_released(&game.active_player().id) {
+                break;
+            }
+            game.end_turn();
+            skipped = true;
+        }
+        skipped
+    }
+
+    /// Cards currently in the hand of a player
+    pub async fn hand(&self, player_id: Uuid) -> Vec<msg::Card> {
+        let game = self.game.read().await;
+        game.get_player(player_id)
+            .hand
+            .card_iter()
+            .map(msg::Card::from)
+            .collect()
+    }
+
+    /// Executes a turn action and returns the public changes it produced.
+    ///
+    /// The sequence number only increases when the action succeeds.
+    #[instrument(skip(self, action), fields(room = %self.id))]
+    pub async fn turn_action(&self, player_id: Uuid, action: TurnAction<'_>) -> GResult<StateDiff> {
+        let start = Instant::now();
+        let seats = self.seats.lock().await;
+        let mut game = self.game.write().await;
+        let before = Snapshot::new(&game, self.seq.load(Ordering::SeqCst), None);
+
+        let res = game.turn_action(player_id, action);
+        METRICS.action(start.elapsed());
+        res?;
+        Self::skip_released(&mut game, &seats);
+
+        let seq = self.seq.fetch_add(1, Ordering::SeqCst) + 1;
+        let after = Snapshot::new(&game, seq, None);
+        Ok(StateDiff::between(&before, &after))
+    }
+}
+
+/// State of a room that survives a restart. Seats are not kept, players
+/// take them back when they reconnect.
+#[derive(Serialize, Deserialize)]
+struct SavedRoom {
+    id: Uuid,
+    seq: u64,
+    game: Game,
 }
 
 /// Database
+///
+/// Only holds the rooms owned by this instance. See [`RoomBus::claim`].
 #[derive(Clone, Debug)]
 pub struct GameController {
     store: Arc<RwLock<HashMap<Uuid, GameRoom>>>,
+    bus: Arc<dyn RoomBus>,
+    /// Capacity of the channel of new rooms
+    capacity: usize,
+    /// Where games starting and finishing are tracked
+    analytics: Option<Analytics>,
+    /// Time the seat of a disconnected player is kept
+    seat_grace: Duration,
 }
 
 impl GameController {
     pub async fn new() -> Self {
-        let gc = Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_bus(Arc::new(LocalBus::default()), capacity)
+    }
+
+    #[must_use]
+    pub fn with_bus(bus: Arc<dyn RoomBus>, capacity: usize) -> Self {
+        Self {
             store: Arc::default(),
-        };
+            bus,
+            capacity,
+            analytics: None,
+            seat_grace: DEFAULT_SEAT_GRACE,
+        }
+    }
 
-        // FIXME just for dev purposes
-        let gr = GameRoom::new(
-            Uuid::from_str("9cb14765-bbfd-447a-b29e-bb203801acb6").unwrap(),
-            &[
-                (
-                    Uuid::from_str("541f4eec-07c3-46b3-942c-4ed15e07f8e4").unwrap(),
-                    // http://127.0.0.1:3000/plai/nLFHZbv9RHqynrsgOAGstg/VB9O7AfDRrOULE7RXgf45A
-                    "MetaTrust".to_string(),
-                ),
-                //(Uuid::new_v4(), "GigaSpy".to_string()),
-                (
-                    // http://127.0.0.1:3000/plai/nLFHZbv9RHqynrsgOAGstg/WfmZY_3fSbKaTIOBt2aOww
-                    Uuid::from_str("59f99963-fddf-49b2-9a4c-8381b7668ec3").unwrap(),
-                    "MalaTesta".to_string(),
-                ),
-                //(Uuid::new_v4(), "Idefix".to_string()),
-                //(Uuid::new_v4(), "BadBiker".to_string()),
-            ],
-        );
+    /// Tracks games starting and finishing in plausible
+    #[must_use]
+    pub fn with_analytics(mut self, analytics: Analytics) -> Self {
+        self.analytics = Some(analytics);
+        self
+    }
 
-        debug!("DUMMY game room with players was created. Can be found at \n\thttp://127.0.0.1:3000/plai/nLFHZbv9RHqynrsgOAGstg/VB9O7AfDRrOULE7RXgf45A \n\thttp://127.0.0.1:3000/plai/nLFHZbv9RHqynrsgOAGstg/WfmZY_3fSbKaTIOBt2aOww");
-        gc.put(gr).await.unwrap();
+    /// Keeps the seat of disconnected players for `grace`
+    #[must_use]
+    pub const fn with_seat_grace(mut self, grace: Duration) -> Self {
+        self.seat_grace = grace;
+        self
+    }
 
-        gc
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[must_use]
+    pub const fn seat_grace(&self) -> Duration {
+        self.seat_grace
     }
 }
 
 impl GameController {
+    /// New room for `game` on the bus of the controller. Not added yet.
+    #[must_use]
+    pub fn room(&self, id: Uuid, game: Game) -> GameRoom {
+        GameRoom::on_bus(id, game, self.bus.clone(), self.capacity)
+    }
+
     pub async fn new_game(&self, id: Uuid, players: &[(Uuid, String)]) -> Res<()> {
-        let g = GameRoom::new(id, players);
-        self.put(g).await
+        self.put(self.room(id, Game::new(players))).await?;
+        self.track("Game Started", &[("players", players.len().into())]);
+        Ok(())
     }
 
+    /// Sends a game event to plausible. Rooms are not told apart so no id
+    /// ends up in the stats.
+    fn track(&self, name: &str, props: &[(&str, PropValue)]) {
+        if let Some(analytics) = &self.analytics {
+            let props = props
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), v.clone()))
+                .collect();
+            analytics.event(name, "/plai", props);
+        }
+    }
+
+    /// Adds a room and makes this instance its owner.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::Duplicated`] if the room is already here or owned by another
+    /// instance, [`Error::Unavailable`] if the owner cannot be checked
     pub async fn put(&self, gr: GameRoom) -> Res<()> {
+        let owned = self.bus.claim(gr.id).await.map_err(|e| {
+            warn!("Cannot claim room {}: {e}", gr.id);
+            Error::Unavailable
+        })?;
+        if !owned {
+            debug!("Room {} is owned by another instance", gr.id);
+            return Err(Error::Duplicated);
+        }
+
         let mut store = self.store.write().await;
         if let std::collections::hash_map::Entry::Vacant(e) = store.entry(gr.id) {
             e.insert(gr);
@@ -104,15 +358,131 @@ impl GameController {
         let game = store.get(id).ok_or(Error::NotFound)?;
         Ok(game.clone())
     }
+
+    /// Every live room
+    pub async fn rooms(&self) -> Vec<GameRoom> {
+        self.store.read().await.values().cloned().collect()
+    }
+
+    /// Tells every client that the server is going away
+    pub async fn notify_shutdown(&self) {
+        for room in self.store.read().await.values() {
+            room.tx.send(&ServerMsg::ShuttingDown);
+        }
+    }
+
+    /// Writes every room to `path`, replacing what was there. Returns the
+    /// number of rooms saved.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be written
+    pub async fn save(&self, path: &Path) -> std::io::Result<usize> {
+        let mut saved = vec![];
+        for room in self.rooms().await {
+            saved.push(SavedRoom {
+                id: room.id,
+                seq: room.seq.load(Ordering::SeqCst),
+                game: room.game.read().await.clone(),
+            });
+        }
+        write_rooms(path, &saved)?;
+        Ok(saved.len())
+    }
+
+    /// Loads the rooms saved in `path` and deletes them from the file, so an
+    /// old state is never restored twice. Rooms that cannot be restored, like
+    /// the ones still owned by another instance, stay in the file. A missing
+    /// file restores nothing.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read, parsed or rewritten
+    pub async fn restore(&self, path: &Path) -> std::io::Result<usize> {
+        let json = match std::fs::read(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+        let saved: Vec<SavedRoom> = serde_json::from_slice(&json).map_err(std::io::Error::other)?;
+
+        let mut restored = 0;
+        let mut failed = vec![];
+        for saved in saved {
+            let room = self.room(saved.id, saved.game.clone());
+            room.seq.store(saved.seq, Ordering::SeqCst);
+            match self.put(room).await {
+                Ok(()) => restored += 1,
+                Err(e) => {
+                    warn!("Cannot restore room {}: {e}", saved.id);
+                    failed.push(saved);
+                }
+            }
+        }
+        if failed.is_empty() {
+            std::fs::remove_file(path)?;
+        } else {
+            warn!("Keeping {} rooms in {path:?}", failed.len());
+            write_rooms(path, &failed)?;
+        }
+        Ok(restored)
+    }
+
+    /// Lets other instances claim every room of this one, once they are
+    /// saved on shutdown
+    pub async fn release_all(&self) {
+        for id in self.store.read().await.keys() {
+            if let Err(e) = self.bus.release(*id).await {
+                warn!("Cannot release room {id}: {e}");
+            }
+        }
+    }
+
+    /// Closes a room. Everyone in it is told the game ended and other
+    /// instances can take its id.
+    ///
+    /// # Errors
+    ///
+    /// If there is no room with `id`
+    pub async fn terminate(&self, id: &Uuid) -> Res<GameRoom> {
+        let room = self.store.write().await.remove(id).ok_or(Error::NotFound)?;
+        room.tx.send(&ServerMsg::GameEnded);
+        if let Err(e) = self.bus.release(*id).await {
+            warn!("Cannot release room {id}: {e}");
+        }
+        let actions = room.seq.load(Ordering::SeqCst);
+        self.track("Game Finished", &[("actions", actions.into())]);
+        info!("Room {id} terminated");
+        Ok(room)
+    }
+}
+
+/// Writes `rooms` to `path` in one step, replacing what was there
+fn write_rooms(path: &Path, rooms: &[SavedRoom]) -> std::io::Result<()> {
+    let json = serde_json::to_vec(rooms).map_err(std::io::Error::other)?;
+
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(&tmp, path)
 }
 
 #[cfg(test)]
 mod test {
     use rstest::{fixture, rstest};
+    use std::sync::Arc;
+    use std::time::Duration;
     use uuid::Uuid;
 
     use super::{GameController, GameRoom};
+    use crate::game::{Error as GError, Funding, TurnAction};
+    use crate::web::board::msg::ServerMsg;
+    use crate::web::bus::{BrokerBus, MemoryBroker};
     use crate::web::Error;
+    use leptos_plausible::ssr::Analytics;
+    use leptos_plausible::PlausibleSettings;
 
     #[fixture]
     fn game_room() -> GameRoom {
@@ -126,6 +496,48 @@ mod test {
         GameRoom::new(Uuid::new_v4(), &ps)
     }
 
+    #[rstest]
+    async fn turn_action_increases_sequence(game_room: GameRoom) {
+        let before = game_room.snapshot(None).await;
+
+        let diff = game_room
+            .turn_action(before.active_player, TurnAction::Funding(Funding::Family))
+            .await
+            .expect("Active player should be able to do a funding");
+
+        assert_eq!(diff.seq, before.seq + 1);
+        assert!(diff.active_player.is_some(), "Turn should have ended");
+        assert_eq!(diff.deck, Some(before.deck - 1));
+        assert_eq!(game_room.snapshot(None).await.seq, diff.seq);
+    }
+
+    #[rstest]
+    async fn failed_turn_action_keeps_sequence(game_room: GameRoom) {
+        let before = game_room.snapshot(None).await;
+        let other = before
+            .hands
+            .iter()
+            .find(|h| h.id != before.active_player)
+            .expect("Test setup has several players")
+            .id;
+
+        let res = game_room
+            .turn_action(other, TurnAction::Funding(Funding::Family))
+            .await;
+
+        assert_eq!(res, Err(GError::NotYourTurn));
+        assert_eq!(game_room.snapshot(None).await, before);
+    }
+
+    #[rstest]
+    async fn snapshot_hides_hands_to_spectators(game_room: GameRoom) {
+        let spectator = game_room.snapshot(None).await;
+        let player = game_room.snapshot(Some(spectator.active_player)).await;
+
+        assert!(spectator.hand.is_empty());
+        assert_eq!(player.hand.len(), 6);
+    }
+
     #[rstest]
     async fn controller_multiple_reads(game_room: GameRoom) {
         let mut gc = GameController::new().await;
@@ -140,4 +552,169 @@ mod test {
             assert_eq!(non_exist.err(), Some(Error::NotFound));
         }
     }
+
+    #[rstest]
+    async fn games_are_tracked() {
+        let (analytics, mut events) =
+            Analytics::channel(&PlausibleSettings::default(), "https://get.plai.cards");
+        let gc = GameController::with_capacity(4).with_analytics(analytics);
+        let id = Uuid::new_v4();
+        let players = [(Uuid::new_v4(), "a".into()), (Uuid::new_v4(), "b".into())];
+
+        gc.new_game(id, &players).await.expect("New room");
+        gc.terminate(&id).await.expect("Room exists");
+
+        let started = events.try_recv().expect("Start is tracked").payload;
+        assert_eq!(started.name, "Game Started");
+        assert_eq!(started.url, "https://get.plai.cards/plai");
+        let finished = events.try_recv().expect("End is tracked").payload;
+        assert_eq!(finished.name, "Game Finished");
+        assert!(events.try_recv().is_err());
+    }
+
+    #[rstest]
+    async fn terminated_room_is_gone(game_room: GameRoom) {
+        let gc = GameController::new().await;
+        gc.put(game_room.clone()).await.expect("New room");
+        let mut rx = game_room.tx.subscribe();
+
+        gc.terminate(&game_room.id).await.expect("Room exists");
+
+        assert_eq!(rx.recv().await, Ok(ServerMsg::GameEnded));
+        assert_eq!(gc.get(&game_room.id).await.err(), Some(Error::NotFound));
+        assert!(gc.rooms().await.is_empty());
+        assert_eq!(
+            gc.terminate(&game_room.id).await.err(),
+            Some(Error::NotFound)
+        );
+    }
+
+    async fn instance(node: &str, broker: &MemoryBroker) -> GameController {
+        let bus = BrokerBus::connect(
+            node.into(),
+            Arc::new(broker.clone()),
+            Duration::from_secs(30),
+        )
+        .await
+        .expect("Memory broker is always there");
+        GameController::with_bus(Arc::new(bus), 4)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn only_the_owner_keeps_a_room(game_room: GameRoom) {
+        let broker = MemoryBroker::default();
+        let mut instances = vec![];
+        for node in ["a", "b"] {
+            instances.push(instance(node, &broker).await);
+        }
+        let (a, b) = (&instances[0], &instances[1]);
+        let game = game_room.game.read().await.clone();
+
+        a.put(a.room(game_room.id, game.clone()))
+            .await
+            .expect("Nobody owns the room");
+        assert_eq!(
+            b.put(b.room(game_room.id, game.clone())).await,
+            Err(Error::Duplicated)
+        );
+        assert_eq!(b.get(&game_room.id).await.err(), Some(Error::NotFound));
+
+        a.terminate(&game_room.id).await.expect("Room exists");
+        assert!(b.put(b.room(game_room.id, game)).await.is_ok());
+    }
+
+    #[rstest]
+    async fn rooms_survive_a_restart(game_room: GameRoom) {
+        let path = std::env::temp_dir()
+            .join(format!("plaicards-{}", Uuid::new_v4()))
+            .join("rooms.json");
+        let before = game_room.snapshot(None).await;
+        game_room
+            .turn_action(before.active_player, TurnAction::Funding(Funding::Family))
+            .await
+            .expect("Active player should be able to do a funding");
+        let gc = GameController::new().await;
+        gc.put(game_room.clone()).await.expect("New room");
+        let mut rx = game_room.tx.subscribe();
+
+        gc.notify_shutdown().await;
+        assert_eq!(rx.recv().await, Ok(ServerMsg::ShuttingDown));
+        assert_eq!(gc.save(&path).await.expect("Writable file"), 1);
+
+        let restarted = GameController::new().await;
+        assert_eq!(restarted.restore(&path).await.expect("Readable file"), 1);
+        let restored = restarted.get(&game_room.id).await.expect("Room restored");
+        assert_eq!(
+            restored.snapshot(None).await,
+            game_room.snapshot(None).await
+        );
+        assert!(!path.exists(), "Saved state is only restored once");
+        assert_eq!(restarted.restore(&path).await.expect("Missing file"), 0);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn rooms_that_cannot_be_restored_stay_saved(game_room: GameRoom) {
+        let path = std::env::temp_dir()
+            .join(format!("plaicards-{}", Uuid::new_v4()))
+            .join("rooms.json");
+        let broker = MemoryBroker::default();
+        let (a, b) = (instance("a", &broker).await, instance("b", &broker).await);
+        let game = game_room.game.read().await.clone();
+        a.put(a.room(game_room.id, game)).await.expect("New room");
+        assert_eq!(a.save(&path).await.expect("Writable file"), 1);
+
+        assert_eq!(b.restore(&path).await.expect("Readable file"), 0);
+        assert!(path.exists(), "Room owned by a is kept");
+
+        a.release_all().await;
+        assert_eq!(b.restore(&path).await.expect("Readable file"), 1);
+        assert!(!path.exists(), "Every room was restored");
+    }
+
+    #[rstest]
+    async fn unknown_player_cannot_join(game_room: GameRoom) {
+        assert_eq!(game_room.join(Uuid::new_v4()).await, Err(Error::NotFound));
+    }
+
+    #[rstest]
+    async fn rejoin_within_grace_keeps_seat(game_room: GameRoom) {
+        let player = game_room.snapshot(None).await.active_player;
+        assert_eq!(game_room.join(player).await, Ok(false));
+
+        let departure = game_room.leave(player).await.expect("Last connection");
+        assert_eq!(game_room.snapshot(None).await.away, vec![player]);
+        assert_eq!(game_room.join(player).await, Ok(true));
+
+        assert_eq!(game_room.release(player, departure).await, None);
+        let snapshot = game_room.snapshot(None).await;
+        assert!(snapshot.away.is_empty());
+        assert_eq!(snapshot.active_player, player);
+    }
+
+    #[rstest]
+    async fn duplicated_connection_keeps_seat(game_room: GameRoom) {
+        let player = game_room.snapshot(None).await.active_player;
+        game_room.join(player).await.expect("Player is in the game");
+        game_room.join(player).await.expect("Player is in the game");
+
+        assert_eq!(game_room.leave(player).await, None);
+        assert!(game_room.leave(player).await.is_some());
+    }
+
+    #[rstest]
+    async fn released_active_player_is_skipped(game_room: GameRoom) {
+        let before = game_room.snapshot(None).await;
+        let player = before.active_player;
+        game_room.join(player).await.expect("Player is in the game");
+        let departure = game_room.leave(player).await.expect("Last connection");
+
+        let diff = game_room
+            .release(player, departure)
+            .await
+            .expect("Turn should be skipped");
+        assert_eq!(diff.seq, before.seq + 1);
+        assert!(diff.active_player.is_some_and(|p| p != player));
+    }
 }
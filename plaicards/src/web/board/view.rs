@@ -1,24 +1,30 @@
 use std::collections::hash_map::HashMap;
+use std::collections::HashSet;
 use std::rc::Rc;
 
+use crate::error_template::{error_page, AppError};
 use crate::game::Funding;
 use crate::web::common::Button;
 use crate::web::common::ButtonDisablable;
+use crate::web::i18n::{card_description, card_effect, card_title};
 use codee::string::FromToStringCodec;
 use data_encoding::BASE64URL_NOPAD;
-use leptos::ev::Event;
+use leptos::ev::{CloseEvent, Event};
 use leptos::logging::log;
 use leptos::*;
+use leptos_fluent::{move_tr, tr};
 use leptos_meta::*;
 use leptos_router::use_params_map;
 use leptos_router::*;
+use leptos_use::core::ConnectionReadyState;
 use leptos_use::{
-    use_websocket, use_websocket_with_options, UseWebSocketOptions, UseWebSocketReturn,
+    use_websocket, use_websocket_with_options, ReconnectLimit, UseWebSocketOptions,
+    UseWebSocketReturn,
 };
 use uuid::Uuid;
 
 use super::msg;
-use super::msg::{ClientMsg, ServerMsg, WsSerDe};
+use super::msg::{ClientMsg, Reaction, ServerMsg, Snapshot, WsSerDe, MAX_CHAT_LEN};
 
 fn from_param_uuid(params: Memo<ParamsMap>, param_name: &str) -> Option<Uuid> {
     let raw = params.with_untracked(|ps| {
@@ -30,6 +36,14 @@ fn from_param_uuid(params: Memo<ParamsMap>, param_name: &str) -> Option<Uuid> {
     from_url_uuid(&raw)
 }
 
+/// Websocket of a game. The room in the query lets a load balancer send
+/// every client of a room to the instance that owns it, like the lobby id
+/// in the path of the lobby websocket. The server ignores it, see the
+/// `web::bus` module.
+fn room_ws_url(game_id: Uuid) -> String {
+    format!("/game/ws?room={game_id}")
+}
+
 fn from_url_uuid(url_id: &str) -> Option<Uuid> {
     let res = Uuid::try_from(
         BASE64URL_NOPAD
@@ -78,6 +92,8 @@ type History = RwSignal<Vec<String>>;
 /// WebSocket encapsulation with the following features:
 ///
 /// * History of messages
+/// * Reconnects forever when the connection drops. The server sends
+///   [`ServerMsg::Hello`] again and the state is rebuilt from a new snapshot.
 /// * Contract enforcement
 ///   * Serializer from [`ClientMsg`]
 ///   * Deserializer from [`ServerMsg`]
@@ -99,21 +115,33 @@ type History = RwSignal<Vec<String>>;
 struct Ws {
     history: History,
     ctx: WsContext,
+    ready_state: Signal<ConnectionReadyState>,
 }
 
 impl Ws {
     pub fn new(url: &str) -> Self {
         let history: History = create_rw_signal(vec![format!("[init] Starting Ws to {}", &url)]);
 
-        let UseWebSocketReturn { message, send, .. } =
-            use_websocket_with_options::<String, String, FromToStringCodec>(
-                "/game/ws",
-                UseWebSocketOptions::default()
-                    .on_open(Self::callback_open(history))
-                    .on_message(Self::callback_message(history)),
-            );
+        let UseWebSocketReturn {
+            message,
+            send,
+            ready_state,
+            ..
+        } = use_websocket_with_options::<String, String, FromToStringCodec>(
+            url,
+            UseWebSocketOptions::default()
+                .on_open(Self::callback_open(history))
+                .on_message(Self::callback_message(history))
+                .on_close(Self::callback_close(history))
+                .reconnect_limit(ReconnectLimit::Infinite)
+                .reconnect_interval(2000),
+        );
         let ctx = WsContext::new(message, Rc::new(send.clone()));
-        Self { history, ctx }
+        Self {
+            history,
+            ctx,
+            ready_state,
+        }
     }
 
     fn callback_open(history: RwSignal<Vec<String>>) -> impl Fn(Event) {
@@ -121,6 +149,11 @@ impl Ws {
             history.update(|h| h.push(format!("[onopen]: event {:?}", e.type_())));
         }
     }
+    fn callback_close(history: RwSignal<Vec<String>>) -> impl Fn(CloseEvent) {
+        move |e: CloseEvent| {
+            history.update(|h| h.push(format!("[onclose]: code {}", e.code())));
+        }
+    }
     fn callback_message(history: RwSignal<Vec<String>>) -> impl Fn(&String) {
         move |m: &String| {
             history.update(|h| h.push(format!("[onmessage]: event {m}")));
@@ -137,15 +170,164 @@ impl Ws {
             .update(|history: &mut Vec<_>| history.push(format!("[send] {msg}")));
     }
 
+    pub fn is_connected(&self) -> bool {
+        self.ready_state.get() == ConnectionReadyState::Open
+    }
+
+    /// Like [`Self::message`] but it also notifies repeated messages
+    pub fn each_message(&self) -> Signal<Option<ServerMsg>> {
+        let ctx = self.ctx.clone();
+        Signal::derive(move || {
+            ctx.message
+                .with(|m| m.as_ref().and_then(|m| ServerMsg::from_str(m).ok()))
+        })
+    }
+
     pub fn message(&self) -> Memo<Option<ServerMsg>> {
         let ctx = self.ctx.clone();
         create_memo(move |_| {
-            ctx.message
-                .with(|m| m.clone().map(|m| ServerMsg::from_str(&m)))
+            ctx.message.with(|m| {
+                m.as_ref().and_then(|m| {
+                    ServerMsg::from_str(m)
+                        .map_err(|e| logging::warn!("Ignoring malformed message {m}: {e}"))
+                        .ok()
+                })
+            })
         })
     }
 }
 
+/// Client copy of the game, kept in sync with the server messages.
+///
+/// It's built from [`ServerMsg::StateSnapshot`] and then updated with the
+/// diffs. When a gap in the sequence numbers is detected, it asks the server
+/// for a new snapshot.
+#[derive(Clone, Copy)]
+struct GameState {
+    players: RwSignal<Vec<msg::Player>>,
+    hand: RwSignal<Vec<msg::Card>>,
+    hands: RwSignal<HashMap<Uuid, usize>>,
+    active_player: RwSignal<Option<Uuid>>,
+    round: RwSignal<u32>,
+    deck: RwSignal<usize>,
+    discard: RwSignal<Vec<msg::Card>>,
+    pending: RwSignal<Vec<msg::Decision>>,
+    /// Players whose connection dropped
+    away: RwSignal<HashSet<Uuid>>,
+    seq: StoredValue<Option<u64>>,
+}
+
+impl GameState {
+    fn new() -> Self {
+        Self {
+            players: create_rw_signal(vec![]),
+            hand: create_rw_signal(vec![]),
+            hands: create_rw_signal(HashMap::new()),
+            active_player: create_rw_signal(None),
+            round: create_rw_signal(0),
+            deck: create_rw_signal(0),
+            discard: create_rw_signal(vec![]),
+            pending: create_rw_signal(vec![]),
+            away: create_rw_signal(HashSet::new()),
+            seq: store_value(None),
+        }
+    }
+
+    /// Keeps the state updated with the messages of `ws`
+    fn sync(ws: &Ws) -> Self {
+        let state = Self::new();
+        let message = ws.message();
+        let websocket = ws.clone();
+        create_effect(move |_| {
+            if let Some(msg) = message() {
+                if !state.apply(msg) {
+                    logging::warn!("Missed game updates. Asking for a resync");
+                    websocket.send(ClientMsg::Resync);
+                }
+            }
+        });
+        state
+    }
+
+    /// Applies a message to the state.
+    ///
+    /// Returns `false` if the message does not follow the last seen update.
+    fn apply(&self, msg: ServerMsg) -> bool {
+        match msg {
+            ServerMsg::StateSnapshot(snapshot) => self.reset(snapshot),
+            ServerMsg::StateDiff(diff) => {
+                if self.seq.get_value().map(|s| s + 1) != Some(diff.seq) {
+                    return false;
+                }
+                self.seq.set_value(Some(diff.seq));
+                self.hands.update(|hs| {
+                    for h in diff.hands {
+                        hs.insert(h.id, h.num_cards);
+                    }
+                });
+                if let Some(p) = diff.active_player {
+                    self.active_player.set(Some(p));
+                }
+                if let Some(r) = diff.round {
+                    self.round.set(r);
+                }
+                if let Some(d) = diff.deck {
+                    self.deck.set(d);
+                }
+                if let Some(d) = diff.discard {
+                    self.discard.set(d);
+                }
+                if let Some(p) = diff.pending {
+                    self.pending.set(p);
+                }
+            }
+            ServerMsg::HandDiff {
+                seq,
+                added,
+                removed,
+            } => {
+                if self.seq.get_value() != Some(seq) {
+                    return false;
+                }
+                self.hand
+                    .update(|h| msg::apply_cards_diff(h, added, &removed));
+            }
+            ServerMsg::PlayerLeft { id } => self.away.update(|a| {
+                a.insert(id);
+            }),
+            ServerMsg::PlayerRejoined { id } => self.away.update(|a| {
+                a.remove(&id);
+            }),
+            _ => {}
+        }
+        true
+    }
+
+    fn reset(&self, snapshot: Snapshot) {
+        self.seq.set_value(Some(snapshot.seq));
+        self.players.set(snapshot.players);
+        self.hand.set(snapshot.hand);
+        self.hands.set(
+            snapshot
+                .hands
+                .into_iter()
+                .map(|h| (h.id, h.num_cards))
+                .collect(),
+        );
+        self.active_player.set(Some(snapshot.active_player));
+        self.round.set(snapshot.round);
+        self.deck.set(snapshot.deck);
+        self.discard.set(snapshot.discard);
+        self.pending.set(snapshot.pending);
+        self.away.set(snapshot.away.into_iter().collect());
+    }
+
+    fn num_cards(&self, player_id: Uuid) -> usize {
+        self.hands
+            .with(|hs| hs.get(&player_id).copied().unwrap_or_default())
+    }
+}
+
 /// Main board View
 #[component]
 pub fn Board() -> impl IntoView {
@@ -154,14 +336,14 @@ pub fn Board() -> impl IntoView {
     let player_id = move || from_param_uuid(params, "player_id");
 
     if id().is_none() || player_id().is_none() {
-        let lobby = format!("/lobby/{}/{}", Uuid::new_v4(), Uuid::new_v4());
-        return view! { <Redirect path=lobby/> }.into_view();
+        return error_page(AppError::InvalidLink);
     }
     let id = move || id().expect("Internal error with params");
     let player_id = move || player_id().expect("Internal error with params");
 
-    let ws = Ws::new("/game/ws");
+    let ws = Ws::new(&room_ws_url(id()));
     provide_context(ws.clone());
+    provide_context(GameState::sync(&ws));
 
     // Respond to events
     let websocket = ws.clone();
@@ -184,6 +366,7 @@ pub fn Board() -> impl IntoView {
         <PlayersHands current_player=player_id()/>
 
         <MiddleBoard/>
+        <ChatPanel current_player=player_id()/>
         <div class="flex justify-around mt-0.5">
           <div class="py-20">
             <ul>
@@ -202,20 +385,64 @@ pub fn Board() -> impl IntoView {
     .into_view()
 }
 
+/// Read-only board for people watching a game
+#[component]
+pub fn Spectate() -> impl IntoView {
+    let params = use_params_map();
+    let Some(id) = from_param_uuid(params, "id") else {
+        return error_page(AppError::InvalidLink);
+    };
+
+    let ws = Ws::new(&room_ws_url(id));
+    provide_context(ws.clone());
+    provide_context(GameState::sync(&ws));
+
+    let websocket = ws.clone();
+    create_effect(move |_| {
+        if websocket.message()() == Some(ServerMsg::Hello) {
+            websocket.send(ClientMsg::Spectate { game_id: id });
+        }
+    });
+
+    view! {
+      <div class="h-screen bg-gray-200">
+        <Nav/>
+        <SpectatorHands/>
+        <MiddleBoard/>
+      </div>
+    }
+    .into_view()
+}
+
 #[component]
 fn Nav() -> impl IntoView {
-    let players: RwSignal<Vec<msg::Player>> = create_rw_signal(vec![]);
+    let state = expect_context::<GameState>();
+    let spectators = create_rw_signal(0_usize);
     let ws = expect_context::<Ws>();
+    let connected = {
+        let ws = ws.clone();
+        move || ws.is_connected()
+    };
+    // The server said it is restarting. Cleared once a new one greets us
+    let restarting = create_rw_signal(false);
+    {
+        let ws = ws.clone();
+        create_effect(move |_| match ws.message()() {
+            Some(ServerMsg::ShuttingDown) => restarting.set(true),
+            Some(ServerMsg::Hello) => restarting.set(false),
+            _ => {}
+        });
+    }
 
-    let updated_players = move || {
-        if let Some(ServerMsg::Players(ps)) = ws.message()() {
-            players.set(ps);
+    let updated_spectators = move || {
+        if let Some(ServerMsg::Spectators(n)) = ws.message()() {
+            spectators.set(n);
         }
-        players.get()
+        spectators.get()
     };
 
     create_effect(move |_| {
-        logging::log!("Value of players updated in NAV {:?}", players());
+        logging::log!("Value of players updated in NAV {:?}", state.players.get());
     });
 
     view! {
@@ -223,7 +450,19 @@ fn Nav() -> impl IntoView {
         <div class="fixed top-2 z-50 content-center py-2 w-11/12 rounded-2xl bg-white/30 backdrop-blur-md">
           <div class="container grid grid-cols-3 justify-items-center items-center px-4 mx-auto text-white">
             <div class="justify-self-start">
-              <h1>Rounds: 12</h1>
+              <h1>{move_tr!("board-rounds", { "rounds" => state.round.get() })}</h1>
+              <Show when=move || !connected() fallback=|| view! {}>
+                <p class="text-sm">
+                  {move || {
+                      if restarting.get() {
+                          tr!("board-restarting")
+                      } else {
+                          tr!("board-reconnecting")
+                      }
+                  }}
+                </p>
+              </Show>
+              <p class="text-sm" title=move || tr!("board-spectators")>{move || format!("👀 {}", updated_spectators())}</p>
             </div>
 
             // <!-- Game Title -->
@@ -233,10 +472,11 @@ fn Nav() -> impl IntoView {
 
             // <!-- Players' Icons -->
             <div class="flex justify-self-end">
-              <For each=move || updated_players().into_iter().enumerate() key=|(_, p)| p.id let:ip>
+              <For each=move || state.players.get().into_iter().enumerate() key=|(_, p)| p.id let:ip>
                 <div class="flex relative group">
                   <span
                     class="mx-1 w-6 h-6 rounded-full"
+                    class=("opacity-30", move || state.away.with(|a| a.contains(&ip.1.id)))
                     class=("bg-blue", move || ip.0 == 0)
                     class=("bg-green", move || ip.0 == 1)
                     class=("bg-orange", move || ip.0 == 2)
@@ -257,29 +497,28 @@ fn Nav() -> impl IntoView {
 
 #[component]
 fn PlayersHands(current_player: Uuid) -> impl IntoView {
-    let ws = expect_context::<Ws>();
-    let players: RwSignal<Vec<msg::Player>> = create_rw_signal(vec![]);
-
-    let updated_players = move || {
-        if let Some(ServerMsg::Players(ps)) = ws.message()() {
-            // Order list starting by current player
-            let idx = ps
-                .iter()
-                .position(|p| p.id == current_player)
-                .unwrap_or_else(|| {
-                    panic!("Current player ID  {current_player} not available in the player list")
-                });
+    let state = expect_context::<GameState>();
 
-            let mut sorted_ps = ps[idx..].to_vec();
-            sorted_ps.extend(ps[..idx].to_vec());
-
-            players.set(sorted_ps);
+    // Order list starting by current player
+    let players = create_memo(move |_| {
+        let ps = state.players.get();
+        if ps.is_empty() {
+            return ps;
         }
-        players.get()
-    };
+        let idx = ps
+            .iter()
+            .position(|p| p.id == current_player)
+            .unwrap_or_else(|| {
+                panic!("Current player ID  {current_player} not available in the player list")
+            });
+
+        let mut sorted_ps = ps[idx..].to_vec();
+        sorted_ps.extend(ps[..idx].to_vec());
+        sorted_ps
+    });
 
     view! {
-      <Show when=move || !updated_players().is_empty() fallback=|| view! {}>
+      <Show when=move || !players().is_empty() fallback=|| view! {}>
 
         <PlayerDrawer player=players.get()[0].clone()/>
         <Show
@@ -312,11 +551,140 @@ fn PlayersHands(current_player: Uuid) -> impl IntoView {
     }
 }
 
+/// Every seat seen from the outside. Used by spectators.
+#[component]
+fn SpectatorHands() -> impl IntoView {
+    let state = expect_context::<GameState>();
+
+    view! {
+      <div class="flex flex-wrap gap-4 justify-around pt-24">
+        <For each=move || state.players.get() key=|p| p.id let:player>
+          <HandHorizontal player=player/>
+        </For>
+      </div>
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ChatLine {
+    /// `None` for notices of the game itself
+    from: Option<Uuid>,
+    to: Option<Uuid>,
+    text: String,
+}
+
+/// Talk to the table in public, bribe someone in private or throw a reaction
+#[component]
+fn ChatPanel(current_player: Uuid) -> impl IntoView {
+    let ws = expect_context::<Ws>();
+    let state = expect_context::<GameState>();
+
+    let lines = create_rw_signal(Vec::<ChatLine>::new());
+    let message = ws.each_message();
+    create_effect(move |_| {
+        let line = match message() {
+            Some(ServerMsg::Chat { from, to, text }) => ChatLine {
+                from: Some(from),
+                to,
+                text,
+            },
+            Some(ServerMsg::Reaction { from, reaction }) => ChatLine {
+                from: Some(from),
+                to: None,
+                text: reaction.emoji().to_string(),
+            },
+            Some(ServerMsg::SlowDown) => ChatLine {
+                from: None,
+                to: None,
+                text: tr!("board-slow-down"),
+            },
+            _ => return,
+        };
+        lines.update(|l| l.push(line));
+    });
+
+    let name = move |id: Uuid| {
+        state.players.with(|ps| {
+            ps.iter()
+                .find(|p| p.id == id)
+                .map(|p| p.name.clone())
+                .unwrap_or_default()
+        })
+    };
+
+    let to = create_rw_signal(None::<Uuid>);
+    let draft = create_rw_signal(String::new());
+    let chat_ws = ws.clone();
+    let on_submit = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        let text = draft.get_untracked();
+        if text.trim().is_empty() {
+            return;
+        }
+        chat_ws.send(ClientMsg::Chat {
+            to: to.get_untracked(),
+            text,
+        });
+        draft.set(String::new());
+    };
+
+    view! {
+      <div class="fixed right-4 bottom-48 z-30 p-2 w-72 text-sm bg-white rounded-lg shadow">
+        <ul class="overflow-y-auto h-40">
+          <For each=move || lines.get().into_iter().enumerate() key=|(i, _)| *i let:line>
+            <li class=("italic", move || line.1.to.is_some()) class=("text-gray", move || line.1.from.is_none())>
+              <b>
+                {move || line.1.from.map(name).unwrap_or_default()}
+                {move || line.1.to.map(|to| format!(" → {}", name(to))).unwrap_or_default()}
+              </b>
+              " "
+              {line.1.text}
+            </li>
+          </For>
+        </ul>
+        <div class="flex justify-around py-1">
+          {Reaction::ALL
+              .into_iter()
+              .map(|r| {
+                  let ws = ws.clone();
+                  view! {
+                    <button on:click=move |_| ws.send(ClientMsg::React(r))>{r.emoji()}</button>
+                  }
+              })
+              .collect_view()}
+        </div>
+        <form class="flex gap-1" on:submit=on_submit>
+          <select on:change=move |ev| to.set(Uuid::parse_str(&event_target_value(&ev)).ok())>
+            <option value="">{move_tr!("board-chat-everyone")}</option>
+            <For
+              each=move || state.players.get().into_iter().filter(move |p| p.id != current_player)
+              key=|p| p.id
+              let:p
+            >
+              <option value=p.id.to_string()>{p.name}</option>
+            </For>
+          </select>
+          <input
+            class="w-full"
+            maxlength=MAX_CHAT_LEN
+            prop:value=draft
+            on:input=move |ev| draft.set(event_target_value(&ev))
+          />
+          <button type="submit">{move_tr!("board-chat-send")}</button>
+        </form>
+      </div>
+    }
+}
+
 #[component]
 fn MiddleBoard() -> impl IntoView {
+    let state = expect_context::<GameState>();
+
     view! {
       <div class="flex justify-center my-8 space-x-4">
-        <div class="w-24 h-32 bg-gray-700"></div>
+        <div class="flex justify-center items-center w-24 h-32 text-white bg-gray-700" title=move || tr!("board-deck")>
+          {move || state.deck.get()}
+        </div>
         <div class="w-24 h-32 bg-gray-500"></div>
       </div>
     }
@@ -324,16 +692,9 @@ fn MiddleBoard() -> impl IntoView {
 
 #[component]
 fn HandVertical(player: msg::Player, left: bool) -> impl IntoView {
-    let ws = expect_context::<Ws>();
-    let cards_memory = create_rw_signal(0);
-    let cards = move || {
-        if let Some(ServerMsg::RivalHand { id, num_cards }) = ws.message()() {
-            if id == player.id {
-                cards_memory.set(num_cards);
-            }
-        }
-        cards_memory()
-    };
+    let state = expect_context::<GameState>();
+    let pid = player.id;
+    let cards = move || state.num_cards(pid);
 
     view! {
       <div class="absolute top-1/4" class=("left-5", move || left) class=("right-5", move || !left)>
@@ -359,17 +720,9 @@ fn HandVertical(player: msg::Player, left: bool) -> impl IntoView {
 
 #[component]
 fn HandHorizontal(player: msg::Player) -> impl IntoView {
-    let ws = expect_context::<Ws>();
-
-    let cards_memory = create_rw_signal(0);
-    let cards = move || {
-        if let Some(ServerMsg::RivalHand { id, num_cards }) = ws.message()() {
-            if id == player.id {
-                cards_memory.set(num_cards);
-            }
-        }
-        cards_memory()
-    };
+    let state = expect_context::<GameState>();
+    let pid = player.id;
+    let cards = move || state.num_cards(pid);
 
     view! {
       <div class="p-2 bg-white rounded">
@@ -393,25 +746,15 @@ fn HandHorizontal(player: msg::Player) -> impl IntoView {
 
 #[component]
 fn PlayerDrawer(player: msg::Player) -> impl IntoView {
-    let ws = expect_context::<Ws>();
-    let ws_message = ws.message();
-
-    let cards: RwSignal<Vec<msg::Card>> = create_rw_signal(vec![]);
-    let updated_hand = move || {
-        if let Some(ServerMsg::AddCard(c)) = ws_message() {
-            cards.update(|cs| cs.push(c));
-        }
-        cards()
-    };
+    let state = expect_context::<GameState>();
 
     let is_players_turn = create_rw_signal(false);
     provide_context(is_players_turn);
-    let check_player_turn = move || {
-        if let Some(ServerMsg::NextPlayer(pid)) = ws_message() {
-            is_players_turn.set(pid == player.id);
-        }
-        is_players_turn.get()
-    };
+    let pid = player.id;
+    let name = player.name.clone();
+    create_effect(move |_| {
+        is_players_turn.set(state.active_player.get() == Some(pid));
+    });
     create_effect(move |_| {
         if is_players_turn() {
             logging::log!("It's now your turn");
@@ -429,18 +772,18 @@ fn PlayerDrawer(player: msg::Player) -> impl IntoView {
             <PlayerActions player=player.clone()/>
           </div>
           <div>
-            <h2>Your Cards {player.name}</h2>
+            <h2>{move_tr!("board-your-cards", { "name" => name.clone() })}</h2>
           </div>
           <div class="justify-self-end">
-            <p>{move || if check_player_turn() { "Your turn" } else { "" }}</p>
+            <p>{move || if is_players_turn() { tr!("board-your-turn") } else { String::new() }}</p>
           </div>
         </div>
 
       <div class="grid justify-center">
       <div class="card-container pt-4">
       <For
-            each=move || updated_hand().into_iter().enumerate()
-            key=|(_, c)| c.title.clone()
+            each=move || state.hand.get().into_iter().enumerate()
+            key=|(i, c)| (*i, c.title.clone())
             children=move |(_, c)| view! { <FaceUpCard c=c/>}
     />
       </div>
@@ -448,7 +791,6 @@ fn PlayerDrawer(player: msg::Player) -> impl IntoView {
     </div>
     }
 }
-
 #[component]
 fn FaceUpCard(c: msg::Card) -> impl IntoView {
     let ws = expect_context::<Ws>();
@@ -468,9 +810,9 @@ fn FaceUpCard(c: msg::Card) -> impl IntoView {
             disabled=move || !(is_players_turn() && can_be_played)
             on:click=move |_| ws.send(ClientMsg::PlayCard(c()))
         >
-            <p class={format!("select-none uppercase text-gray-illustration font-extrabold mt-10 {}", get_color(&c().ctype))}>{c().title}</p>
-            <p class="select-none uppercase text-black font-bold mt-2">{c().effect}</p>
-            <p class="select-none text-dove-gray italic mt-2">{c().description}</p>
+            <p class={format!("select-none uppercase text-gray-illustration font-extrabold mt-10 {}", get_color(&c().ctype))}>{move || c.with_value(card_title)}</p>
+            <p class="select-none uppercase text-black font-bold mt-2">{move || c.with_value(card_effect)}</p>
+            <p class="select-none text-dove-gray italic mt-2">{move || c.with_value(card_description)}</p>
         </button>
     }
 }
@@ -492,7 +834,7 @@ fn PlayerActions(player: msg::Player) -> impl IntoView {
 
     view! {
       <ButtonDisablable
-        title="Family Funding".into()
+        title=move || tr!("board-family-funding")
         disabled=is_not_turn
         on:click=move |_| {
             funding_button.set(Some(Funding::Family));
@@ -500,7 +842,7 @@ fn PlayerActions(player: msg::Player) -> impl IntoView {
       />
 
       <ButtonDisablable
-        title="Regional Funding".into()
+        title=move || tr!("board-regional-funding")
         disabled=is_not_turn
         on:click=move |_| {
             funding_button.set(Some(Funding::Regional));
@@ -508,7 +850,7 @@ fn PlayerActions(player: msg::Player) -> impl IntoView {
       />
 
       <ButtonDisablable
-        title="VC Funding".into()
+        title=move || tr!("board-vc-funding")
         disabled=is_not_turn
         on:click=move |_| {
             funding_button.set(Some(Funding::VC));
@@ -11,74 +11,132 @@ use leptos::*;
 
 use axum::extract::ws::{WebSocket, WebSocketUpgrade};
 use futures::{
-    sink::SinkExt,
-    stream::{SplitSink, SplitStream, StreamExt},
+    sink::{Sink, SinkExt},
+    stream::{SplitSink, SplitStream, Stream, StreamExt},
 };
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::mpsc::{self, Sender};
-use tracing::instrument;
-use tracing::{debug, info, trace};
+use tracing::{debug, info, info_span, instrument, trace, Instrument};
 use uuid::Uuid;
 
 use crate::{
     game::TurnAction,
     web::{
         board::{msg, ssr::GameRoom},
+        bus::{Receiver, Topic},
+        metrics::METRICS,
+        rate_limit::RateLimiter,
         Error, Result as Res,
     },
 };
 
-use super::msg::{ClientMsg, ServerMsg, WsSerDe};
+use super::msg::{ClientMsg, ServerMsg, WsSerDe, MAX_CHAT_LEN};
 use super::ssr::GameController;
-use crate::game::{Error as GError, Game};
+use crate::game::{Card as GCard, Error as GError, Game};
+
+/// How often the server pings the client
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+/// Silence after which a client is considered gone. Browsers answer the
+/// pings automatically, so only dead connections reach it.
+const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(45);
+/// Chat messages and reactions a player can send in [`CHAT_WINDOW`]
+const CHAT_BURST: usize = 5;
+const CHAT_WINDOW: Duration = Duration::from_secs(10);
 
 pub async fn handler(ws: WebSocketUpgrade, State(gc): State<GameController>) -> Response {
     ws.on_upgrade(move |socket| handle_socket(socket, gc))
 }
 
-async fn send(ws: &Sender<ServerMsg>, msg: ServerMsg) {
-    //let msg = Message::Text(msg.to_str());
+async fn send(ws: &Sender<ServerMsg>, msg: ServerMsg) -> Res<()> {
     trace!("Sending: {:?}", &msg);
-    ws.send(msg).await.expect("Cant send message");
+    ws.send(msg).await.map_err(|_| Error::WebsocketError)
 }
 
 /// Wrapper to send messages back to the client or to the room
 #[derive(Clone)]
 struct WsSender {
-    room: tokio::sync::broadcast::Sender<ServerMsg>,
+    room: Topic<ServerMsg>,
     client: tokio::sync::mpsc::Sender<ServerMsg>,
 }
 
 impl WsSender {
-    pub fn new(
-        room: tokio::sync::broadcast::Sender<ServerMsg>,
-        client: tokio::sync::mpsc::Sender<ServerMsg>,
-    ) -> Self {
+    pub fn new(room: Topic<ServerMsg>, client: tokio::sync::mpsc::Sender<ServerMsg>) -> Self {
         Self { room, client }
     }
 
-    pub async fn to_room(&self, msg: ServerMsg) -> Res<()> {
+    pub fn to_room(&self, msg: ServerMsg) {
         trace!("[WS->Room]: {:?}", &msg);
-        match self.room.send(msg) {
-            Ok(_) => Ok(()),
-            Err(_) => Err(Error::WebsocketError),
-        }
+        self.room.send(&msg);
     }
 
     pub async fn to_client(&self, msg: ServerMsg) -> Res<()> {
-        trace!("[WS->Client]: {:?}", &msg);
-        self.client
-            .send(msg)
-            .await
-            .map_err(|_| Error::WebsocketError)
+        send(&self.client, msg).await
+    }
+}
+
+/// Checks a chat message and builds what the room will receive.
+///
+/// # Errors
+///
+/// The reply for the sender if the message is empty, too long or addressed to
+/// someone that is not playing.
+async fn chat(
+    room: &GameRoom,
+    from: Uuid,
+    to: Option<Uuid>,
+    text: &str,
+) -> Result<ServerMsg, ServerMsg> {
+    let text = text.trim();
+    if text.is_empty() || text.chars().count() > MAX_CHAT_LEN {
+        return Err(ServerMsg::BadRequest);
+    }
+    if let Some(to) = to {
+        if to == from || !room.has_player(to).await {
+            return Err(ServerMsg::BadRequest);
+        }
     }
+    Ok(ServerMsg::Chat {
+        from,
+        to,
+        text: text.to_string(),
+    })
 }
 
-async fn receive(ws: &mut SplitStream<WebSocket>) -> Option<ClientMsg> {
-    while let Some(Ok(message)) = ws.next().await {
+/// Sends accepted messages to the room and rejected ones back to the client
+async fn relay(sender: &WsSender, msg: Result<ServerMsg, ServerMsg>) -> Res<()> {
+    match msg {
+        Ok(msg) => {
+            sender.to_room(msg);
+            Ok(())
+        }
+        Err(reply) => sender.to_client(reply).await,
+    }
+}
+
+/// Waits for the next valid message of the client.
+///
+/// Malformed frames are answered with [`ServerMsg::BadRequest`] and skipped.
+/// Returns `None` once the connection is closed or nothing, not even a pong,
+/// arrived during [`HEARTBEAT_TIMEOUT`].
+async fn receive<St>(ws: &mut St, client: &Sender<ServerMsg>) -> Option<ClientMsg>
+where
+    St: Stream<Item = Result<Message, axum::Error>> + Unpin,
+{
+    while let Ok(Some(Ok(message))) = tokio::time::timeout(HEARTBEAT_TIMEOUT, ws.next()).await {
         trace!("[Client->WS]: {:?}", &message);
         if let Message::Text(msg) = message {
-            let msg = ClientMsg::from_str(&msg);
-            return Some(msg);
+            match ClientMsg::from_str(&msg) {
+                Ok(msg) => {
+                    METRICS.received();
+                    return Some(msg);
+                }
+                Err(e) => {
+                    debug!("Malformed message {msg:?}: {e}");
+                    send(client, ServerMsg::BadRequest).await.ok()?;
+                }
+            }
         }
     }
     None
@@ -89,145 +147,134 @@ async fn receive(ws: &mut SplitStream<WebSocket>) -> Option<ClientMsg> {
 /// It allows to send and receive information
 async fn handle_socket(socket: WebSocket, gc: GameController) {
     // By splitting, we can send and receive at the same time.
-    let (mut sink, mut receiver) = socket.split();
+    let (sink, receiver) = socket.split();
+    serve(sink, receiver, gc).await;
+}
+
+/// Board protocol over any pair of message sink and stream.
+///
+/// Nothing sent by the client can panic the task: bad frames get a
+/// [`ServerMsg::BadRequest`] and failing to reach the client ends the
+/// connection.
+async fn serve<Si, St>(mut sink: Si, mut receiver: St, gc: GameController)
+where
+    Si: Sink<Message> + Unpin + Send + 'static,
+    St: Stream<Item = Result<Message, axum::Error>> + Unpin + Send + 'static,
+{
+    let _connection = METRICS.connection();
 
     // create an mpsc so we can send messages to the sink from multiple threads
     let (sender, mut rx) = mpsc::channel::<ServerMsg>(2);
 
-    // spawn a task that forwards messages from the mpsc to the sink
+    // spawn a task that forwards messages from the mpsc to the sink and
+    // keeps the connection alive
     tokio::spawn(async move {
-        while let Some(message) = rx.recv().await {
-            let msg = Message::Text(message.to_str());
+        let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+        loop {
+            let msg = tokio::select! {
+                message = rx.recv() => match message {
+                    Some(message) => Message::Text(message.to_str()),
+                    None => break,
+                },
+                _ = heartbeat.tick() => Message::Ping(vec![]),
+            };
+            let is_text = matches!(msg, Message::Text(_));
             if sink.send(msg).await.is_err() {
                 break;
             }
+            if is_text {
+                METRICS.sent();
+            }
         }
     });
 
     ////////////////////////////////
     // Initialization with client
     ////////////////////////////////
-    send(&sender, ServerMsg::Hello).await;
-    let mut gid: Option<Uuid> = None;
-    let mut pid: Option<Uuid> = None;
+    if send(&sender, ServerMsg::Hello).await.is_err() {
+        return;
+    }
 
-    // First connection must be the game_id and player_id
-    match receive(&mut receiver).await {
+    // First message must be the game_id and, for players, the player_id
+    match receive(&mut receiver, &sender).await {
         Some(ClientMsg::Connect { game_id, player_id }) => {
-            gid = Some(game_id);
-            pid = Some(player_id);
+            play(receiver, sender, gc, game_id, player_id).await;
         }
-        _ => send(&sender, ServerMsg::BadRequest).await,
+        Some(ClientMsg::Spectate { game_id }) => {
+            spectate(receiver, sender, gc, game_id).await;
+        }
+        Some(_) => {
+            let _ = send(&sender, ServerMsg::BadRequest).await;
+        }
+        None => debug!("Client left before connecting"),
     };
+}
 
-    let game_id = gid.expect("Wrong Game id");
-    let player_id = pid.expect("wrong player_id");
+/// Handles a client that is seated in the game
+#[instrument(skip(receiver, sender, gc))]
+async fn play<St>(
+    mut receiver: St,
+    sender: Sender<ServerMsg>,
+    gc: GameController,
+    game_id: Uuid,
+    player_id: Uuid,
+) where
+    St: Stream<Item = Result<Message, axum::Error>> + Unpin + Send + 'static,
+{
+    let Ok(room) = gc.get(&game_id).await else {
+        debug!("No game was found with ID {game_id}");
+        let _ = send(&sender, ServerMsg::BadRequest).await;
+        return;
+    };
 
-    let room = gc
-        .get(&game_id)
-        .await
-        .expect("No game was found with this ID");
+    let rejoined = match room.join(player_id).await {
+        Ok(rejoined) => rejoined,
+        Err(_) => {
+            debug!("Player {player_id} is not part of game {game_id}");
+            let _ = send(&sender, ServerMsg::BadRequest).await;
+            return;
+        }
+    };
 
     debug!("New client in room {:?}", room.id);
+    let grace = gc.seat_grace();
     let sender = WsSender::new(room.tx.clone(), sender);
-
-    // Send current game setup. This way if it reconnects the client obtains
-    // a complete view on the game.
-    {
-        let game = room.game.read().await;
-        let ps = &game.players;
-
-        sender
-            .to_client(ServerMsg::Players(
-                ps.iter().map(msg::Player::from).collect(),
-            ))
-            .await
-            .expect("WSERR");
-
-        for p in ps {
-            if p.id == player_id {
-                for card in p.hand.card_iter() {
-                    let c = msg::Card::from(card);
-                    sender
-                        .to_client(ServerMsg::AddCard(c))
-                        .await
-                        .expect("WSERR");
-                }
-            } else {
-                let hand = ServerMsg::RivalHand {
-                    id: p.id,
-                    num_cards: p.hand.len(),
-                };
-                sender.to_client(hand).await.expect("WSERR");
-            }
-        }
-        // Forward next player to room
-        sender
-            .to_client(ServerMsg::NextPlayer(game.active_player().id))
-            .await
-            .expect("WSERR");
+    if rejoined {
+        sender.to_room(ServerMsg::PlayerRejoined { id: player_id });
+    }
+    let spectators = ServerMsg::Spectators(room.spectators.load(Ordering::SeqCst));
+    if sender.to_client(spectators).await.is_err() {
+        return;
     }
 
     //
     // Normal operation
     //
 
-    // Forward received room messages to client
-    let s = sender.clone();
-    let mut rx = room.tx.subscribe();
-    let mut send_task = tokio::spawn(async move {
-        while let Ok(msg) = rx.recv().await {
-            // Send the messages
-            // in any websocket error, break loop
-            if s.to_client(msg).await.is_err() {
-                break;
-            }
-        }
-    });
+    // Forward room updates to the client. It starts with a full snapshot of
+    // the game so a reconnecting client obtains a complete view of it.
+    let (resync, resync_rx) = mpsc::channel::<()>(1);
+    let mut send_task = tokio::spawn(forward_room(
+        room.clone(),
+        Some(player_id),
+        room.tx.subscribe(),
+        resync_rx,
+        sender.clone(),
+    ));
 
     // Handle incoming messages from client
     let s = sender.clone();
     let r = room.clone();
     let mut recv_task = tokio::spawn(async move {
-        while let Some(msg) = receive(&mut receiver).await {
-            info!("Message received: {:?}", msg);
-            match msg {
-                ClientMsg::Connect { .. } => {}
-                ClientMsg::DoFunding(funding) => {
-                    let mut game = r.game.write().await;
-                    match game.turn_action(player_id, TurnAction::Funding(funding)) {
-                        Ok(()) => {}
-                        Err(e) => {
-                            s.to_client(e.into()).await.expect("WSERR");
-                            continue;
-                        }
-                    }
-                    update_player_state(&s, &game, &player_id)
-                        .await
-                        .expect("WSERR");
-                    // Forward next player to room
-                    s.to_room(ServerMsg::NextPlayer(game.active_player().id))
-                        .await
-                        .expect("WSERR");
-                }
-                ClientMsg::PlayCard(c) => {
-                    let mut game = r.game.write().await;
-                    match game.turn_action(player_id, TurnAction::SpecialCard(&c.into())) {
-                        Ok(()) => {}
-                        Err(e) => {
-                            tracing::error!("Played card gave error");
-                            s.to_client(e.into()).await.expect("WSERR");
-                            continue;
-                        }
-                    }
-                    update_player_state(&s, &game, &player_id)
-                        .await
-                        .expect("WSERR");
-                    // Forward next player to room
-                    s.to_room(ServerMsg::NextPlayer(game.active_player().id))
-                        .await
-                        .expect("WSERR");
-                }
+        let mut limiter = RateLimiter::new(CHAT_BURST, CHAT_WINDOW);
+        while let Some(msg) = receive(&mut receiver, &s.client).await {
+            let span = info_span!("client_msg", kind = msg.kind());
+            let keep = on_player_msg(&s, &r, &resync, &mut limiter, player_id, msg)
+                .instrument(span)
+                .await;
+            if !keep {
+                debug!("Cannot reach the client anymore");
+                break;
             }
         }
     });
@@ -238,27 +285,609 @@ async fn handle_socket(socket: WebSocket, gc: GameController) {
         _ = (&mut recv_task) => send_task.abort(),
     }
 
-    // Send "user left" message (similar to "joined" above).
-    tracing::debug!("Player left");
-    let _ = sender.to_room(ServerMsg::PlayerLeft).await;
+    // Keep the seat for a while in case the player comes back
+    if let Some(departure) = room.leave(player_id).await {
+        tracing::debug!("Player left");
+        sender.to_room(ServerMsg::PlayerLeft { id: player_id });
+        tokio::spawn(async move {
+            tokio::time::sleep(grace).await;
+            if let Some(diff) = room.release(player_id, departure).await {
+                debug!("Player {player_id} lost the seat. Skipping the turn");
+                room.tx.send(&ServerMsg::StateDiff(diff));
+            }
+        });
+    }
 }
 
-/// Sends an update for the current player to all players
-async fn update_player_state(s: &WsSender, game: &Game, player_id: &Uuid) -> Res<()> {
-    // Updating user hand
-    // FIXME does not work well for removal nor adding more than one card
-    // Send all cards anew?
-    let p = game.get_player(*player_id);
-    if let Some(card) = p.hand.card_iter().last() {
-        let c = msg::Card::from(card);
-        s.to_client(ServerMsg::AddCard(c)).await?;
-    }
-    // Forwarding new state to clients
-    let hand = ServerMsg::RivalHand {
-        id: p.id,
-        num_cards: p.hand.len(),
+/// Reacts to a message of a seated player.
+///
+/// Returns `false` once the client cannot be reached anymore.
+async fn on_player_msg(
+    s: &WsSender,
+    r: &GameRoom,
+    resync: &mpsc::Sender<()>,
+    limiter: &mut RateLimiter,
+    player_id: Uuid,
+    msg: ClientMsg,
+) -> bool {
+    let res = match msg {
+        ClientMsg::Connect { .. } | ClientMsg::Spectate { .. } => return true,
+        ClientMsg::Resync => {
+            let _ = resync.try_send(());
+            return true;
+        }
+        ClientMsg::Chat { to, text } => {
+            let msg = if limiter.allow(Instant::now()) {
+                chat(r, player_id, to, &text).await
+            } else {
+                Err(ServerMsg::SlowDown)
+            };
+            return relay(s, msg).await.is_ok();
+        }
+        ClientMsg::React(reaction) => {
+            let msg = if limiter.allow(Instant::now()) {
+                Ok(ServerMsg::Reaction {
+                    from: player_id,
+                    reaction,
+                })
+            } else {
+                Err(ServerMsg::SlowDown)
+            };
+            return relay(s, msg).await.is_ok();
+        }
+        ClientMsg::DoFunding(funding) => {
+            r.turn_action(player_id, TurnAction::Funding(funding)).await
+        }
+        ClientMsg::PlayCard(c) => match GCard::try_from(c) {
+            Ok(card) => {
+                r.turn_action(player_id, TurnAction::SpecialCard(&card))
+                    .await
+            }
+            Err(reply) => return s.to_client(reply).await.is_ok(),
+        },
+    };
+    let sent = match res {
+        Ok(diff) => {
+            s.to_room(ServerMsg::StateDiff(diff));
+            Ok(())
+        }
+        Err(e) => {
+            tracing::error!("Turn action gave error {e:?}");
+            s.to_client(e.into()).await
+        }
+    };
+    sent.is_ok()
+}
+
+/// Handles a read-only client.
+///
+/// Spectators receive the public view of every seat (players, hand sizes and
+/// active player) and all room events, but never the contents of a hand.
+#[instrument(skip(receiver, sender, gc))]
+async fn spectate<St>(
+    mut receiver: St,
+    sender: Sender<ServerMsg>,
+    gc: GameController,
+    game_id: Uuid,
+) where
+    St: Stream<Item = Result<Message, axum::Error>> + Unpin + Send + 'static,
+{
+    let Ok(room) = gc.get(&game_id).await else {
+        debug!("No game was found with ID {game_id}");
+        let _ = send(&sender, ServerMsg::BadRequest).await;
+        return;
     };
-    s.to_room(hand).await.expect("ws error");
 
+    debug!("New spectator in room {:?}", room.id);
+    let sender = WsSender::new(room.tx.clone(), sender);
+
+    // Subscribe before announcing ourselves so we also get the new count
+    let (resync, resync_rx) = mpsc::channel::<()>(1);
+    let mut send_task = tokio::spawn(forward_room(
+        room.clone(),
+        None,
+        room.tx.subscribe(),
+        resync_rx,
+        sender.clone(),
+    ));
+    let spectators = room.spectators.fetch_add(1, Ordering::SeqCst) + 1;
+    sender.to_room(ServerMsg::Spectators(spectators));
+
+    // Spectators cannot act. They can only ask for a resync.
+    let client = sender.client.clone();
+    let mut recv_task = tokio::spawn(async move {
+        while let Some(msg) = receive(&mut receiver, &client).await {
+            if msg == ClientMsg::Resync {
+                let _ = resync.try_send(());
+            }
+        }
+    });
+
+    tokio::select! {
+        _ = (&mut send_task) => recv_task.abort(),
+        _ = (&mut recv_task) => send_task.abort(),
+    }
+
+    tracing::debug!("Spectator left");
+    let spectators = room.spectators.fetch_sub(1, Ordering::SeqCst) - 1;
+    sender.to_room(ServerMsg::Spectators(spectators));
+}
+
+/// Forwards room messages to a single client, keeping track of what it has seen.
+///
+/// It starts by sending a [`ServerMsg::StateSnapshot`]. Room diffs are public
+/// so the private part, the hand of `viewer`, is computed here for each
+/// client. A resync request, or falling behind the room channel, sends a
+/// fresh snapshot and restarts the tracking. Chat lost while lagging is not
+/// recovered.
+async fn forward_room(
+    room: GameRoom,
+    viewer: Option<Uuid>,
+    mut rx: Receiver<ServerMsg>,
+    mut resync: mpsc::Receiver<()>,
+    client: WsSender,
+) -> Res<()> {
+    let snapshot = room.snapshot(viewer).await;
+    let mut seq = snapshot.seq;
+    let mut hand = snapshot.hand.clone();
+    client.to_client(ServerMsg::StateSnapshot(snapshot)).await?;
+
+    loop {
+        tokio::select! {
+            msg = rx.recv() => {
+                let msg = match msg {
+                    Ok(msg) => msg,
+                    // Too slow for the room. What was lost is only known by
+                    // the game, so start again from a fresh snapshot
+                    Err(RecvError::Lagged(n)) => {
+                        METRICS.lagged(n);
+                        debug!("Client fell {n} messages behind the room. Resyncing");
+                        let snapshot = room.snapshot(viewer).await;
+                        seq = snapshot.seq;
+                        hand = snapshot.hand.clone();
+                        client.to_client(ServerMsg::StateSnapshot(snapshot)).await?;
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                match msg {
+                    ServerMsg::StateDiff(diff) => {
+                        // Already included in the last snapshot
+                        if diff.seq <= seq {
+                            continue;
+                        }
+                        seq = diff.seq;
+                        client.to_client(ServerMsg::StateDiff(diff)).await?;
+
+                        if let Some(player_id) = viewer {
+                            let current = room.hand(player_id).await;
+                            let (added, removed) = msg::diff_cards(&hand, &current);
+                            if !(added.is_empty() && removed.is_empty()) {
+                                client
+                                    .to_client(ServerMsg::HandDiff { seq, added, removed })
+                                    .await?;
+                            }
+                            hand = current;
+                        }
+                    }
+                    // The server is going away. Closing lets the client reconnect elsewhere
+                    ServerMsg::ShuttingDown => {
+                        client.to_client(ServerMsg::ShuttingDown).await?;
+                        break;
+                    }
+                    // Private chat only reaches both ends of the conversation
+                    ServerMsg::Chat { from, to: Some(to), .. }
+                        if viewer != Some(from) && viewer != Some(to) => {}
+                    other => client.to_client(other).await?,
+                }
+            }
+            Some(()) = resync.recv() => {
+                let snapshot = room.snapshot(viewer).await;
+                seq = snapshot.seq;
+                hand = snapshot.hand.clone();
+                client.to_client(ServerMsg::StateSnapshot(snapshot)).await?;
+            }
+        }
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::ws::Message;
+    use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+    use futures::{SinkExt, StreamExt};
+    use rstest::rstest;
+    use std::time::{Duration, Instant};
+    use uuid::Uuid;
+
+    use super::{serve, CHAT_BURST};
+    use crate::web::board::msg::{ClientMsg, Reaction, ServerMsg, WsSerDe, MAX_CHAT_LEN};
+    use crate::web::board::GameController;
+
+    /// Client side of an in-memory websocket connected to [`serve`]
+    struct FakeClient {
+        tx: UnboundedSender<Result<Message, axum::Error>>,
+        rx: UnboundedReceiver<Message>,
+    }
+
+    impl FakeClient {
+        fn connect(gc: GameController) -> Self {
+            let (tx, from_client) = unbounded();
+            let (to_client, rx) = unbounded();
+            tokio::spawn(serve(to_client, from_client, gc));
+            Self { tx, rx }
+        }
+
+        /// Connects and takes the seat of `player_id`, skipping the welcome messages
+        async fn play(gc: GameController, game_id: Uuid, player_id: Uuid) -> Self {
+            let mut client = Self::connect(gc);
+            assert_eq!(client.recv().await, Some(ServerMsg::Hello));
+            client.send(ClientMsg::Connect { game_id, player_id }).await;
+            assert!(matches!(
+                client.recv().await,
+                Some(ServerMsg::Spectators(_))
+            ));
+            assert!(matches!(
+                client.recv().await,
+                Some(ServerMsg::StateSnapshot(_))
+            ));
+            client
+        }
+
+        async fn send_raw(&mut self, frame: Message) {
+            self.tx.send(Ok(frame)).await.expect("Fake socket closed");
+        }
+
+        async fn send(&mut self, msg: ClientMsg) {
+            self.send_raw(Message::Text(msg.to_str())).await;
+        }
+
+        /// Next message from the server. `None` if it closed the connection.
+        async fn recv(&mut self) -> Option<ServerMsg> {
+            loop {
+                let msg = tokio::time::timeout(Duration::from_secs(1), self.rx.next())
+                    .await
+                    .expect("Server did not answer")?;
+                if let Message::Text(t) = msg {
+                    return Some(ServerMsg::from_str(&t).expect("Server sent a malformed message"));
+                }
+            }
+        }
+    }
+
+    async fn game_with_players() -> (GameController, Uuid, Vec<Uuid>) {
+        let gc = GameController::new().await;
+        let game_id = Uuid::new_v4();
+        let players: Vec<_> = (0..3).map(|i| (Uuid::new_v4(), format!("p{i}"))).collect();
+        gc.new_game(game_id, &players)
+            .await
+            .expect("Game should be created");
+        (gc, game_id, players.into_iter().map(|(id, _)| id).collect())
+    }
+
+    async fn game_controller() -> (GameController, Uuid, Uuid) {
+        let (gc, game_id, players) = game_with_players().await;
+        (gc, game_id, players[0])
+    }
+
+    #[rstest]
+    #[case::empty(Message::Text(String::new()))]
+    #[case::garbage(Message::Text("}{".to_string()))]
+    #[case::unknown_tag(Message::Text(r#"{"t":"Cheat"}"#.to_string()))]
+    #[case::wrong_content(Message::Text(r#"{"t":"PlayCard","c":42}"#.to_string()))]
+    #[case::bad_uuid(Message::Text(r#"{"t":"Spectate","c":{"game_id":"nope"}}"#.to_string()))]
+    #[tokio::test]
+    async fn malformed_frame_before_connect_is_bad_request(#[case] frame: Message) {
+        let (gc, game_id, _) = game_controller().await;
+        let mut client = FakeClient::connect(gc);
+        assert_eq!(client.recv().await, Some(ServerMsg::Hello));
+
+        client.send_raw(frame).await;
+        assert_eq!(client.recv().await, Some(ServerMsg::BadRequest));
+
+        // The connection is still usable
+        client.send(ClientMsg::Spectate { game_id }).await;
+        assert!(matches!(
+            client.recv().await,
+            Some(ServerMsg::StateSnapshot(_))
+        ));
+    }
+
+    #[rstest]
+    #[case::garbage(Message::Text("\u{0}\u{ffff}".to_string()))]
+    #[case::truncated(Message::Text(r#"{"t":"DoFunding","c":"#.to_string()))]
+    #[case::unknown_funding(Message::Text(r#"{"t":"DoFunding","c":"Bank"}"#.to_string()))]
+    #[tokio::test]
+    async fn malformed_frame_while_playing_is_bad_request(#[case] frame: Message) {
+        let (gc, game_id, player_id) = game_controller().await;
+        let mut client = FakeClient::connect(gc);
+        assert_eq!(client.recv().await, Some(ServerMsg::Hello));
+        client.send(ClientMsg::Connect { game_id, player_id }).await;
+        assert_eq!(client.recv().await, Some(ServerMsg::Spectators(0)));
+        assert!(matches!(
+            client.recv().await,
+            Some(ServerMsg::StateSnapshot(_))
+        ));
+
+        // Non text frames are ignored
+        client.send_raw(Message::Binary(vec![0xde, 0xad])).await;
+        client.send_raw(frame).await;
+        assert_eq!(client.recv().await, Some(ServerMsg::BadRequest));
+
+        client.send(ClientMsg::Resync).await;
+        assert!(matches!(
+            client.recv().await,
+            Some(ServerMsg::StateSnapshot(_))
+        ));
+    }
+
+    #[rstest]
+    #[case::regional(r#"{"t":"DoFunding","c":"Regional"}"#, ServerMsg::NotImplemented)]
+    #[case::vc(r#"{"t":"DoFunding","c":"VC"}"#, ServerMsg::NotImplemented)]
+    #[case::unknown_type(
+        r#"{"t":"PlayCard","c":{"i":"x","t":"X","e":"NoEffect","d":"","ty":"Project"}}"#,
+        ServerMsg::BadRequest
+    )]
+    #[case::not_special(
+        r#"{"t":"PlayCard","c":{"i":"x","t":"X","e":"NoEffect","d":"","ty":"Adversary"}}"#,
+        ServerMsg::BadRequest
+    )]
+    #[case::not_in_hand(
+        r#"{"t":"PlayCard","c":{"i":"x","t":"X","e":"DrawTwo","d":"","ty":"Special"}}"#,
+        ServerMsg::BadMove
+    )]
+    #[tokio::test]
+    async fn unsupported_actions_are_refused(#[case] frame: &str, #[case] reply: ServerMsg) {
+        let (gc, game_id, player_id) = game_controller().await;
+        let mut client = FakeClient::connect(gc);
+        assert_eq!(client.recv().await, Some(ServerMsg::Hello));
+        client.send(ClientMsg::Connect { game_id, player_id }).await;
+        assert_eq!(client.recv().await, Some(ServerMsg::Spectators(0)));
+        assert!(matches!(
+            client.recv().await,
+            Some(ServerMsg::StateSnapshot(_))
+        ));
+
+        client.send_raw(Message::Text(frame.to_string())).await;
+        assert_eq!(client.recv().await, Some(reply));
+
+        // The room is still alive
+        client.send(ClientMsg::Resync).await;
+        assert!(matches!(
+            client.recv().await,
+            Some(ServerMsg::StateSnapshot(_))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn unknown_game_closes_connection() {
+        let (gc, _, player_id) = game_controller().await;
+        let mut client = FakeClient::connect(gc);
+        assert_eq!(client.recv().await, Some(ServerMsg::Hello));
+
+        let game_id = Uuid::new_v4();
+        client.send(ClientMsg::Connect { game_id, player_id }).await;
+        assert_eq!(client.recv().await, Some(ServerMsg::BadRequest));
+        assert_eq!(client.recv().await, None);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn client_gone_does_not_panic() {
+        let (gc, game_id, player_id) = game_controller().await;
+        let (mut tx, from_client) = unbounded();
+        let (to_client, rx) = unbounded();
+        // Nobody will read what the server sends
+        drop(rx);
+        let handle = tokio::spawn(serve(to_client, from_client, gc));
+
+        tx.send(Ok(Message::Text(
+            ClientMsg::Connect { game_id, player_id }.to_str(),
+        )))
+        .await
+        .expect("Fake socket closed");
+        drop(tx);
+
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("Handler should finish")
+            .expect("Handler should not panic");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn reconnect_takes_back_the_seat() {
+        let (gc, game_id, player_id) = game_controller().await;
+        let mut spectator = FakeClient::connect(gc.clone());
+        assert_eq!(spectator.recv().await, Some(ServerMsg::Hello));
+        spectator.send(ClientMsg::Spectate { game_id }).await;
+        assert!(matches!(
+            spectator.recv().await,
+            Some(ServerMsg::StateSnapshot(_))
+        ));
+        assert_eq!(spectator.recv().await, Some(ServerMsg::Spectators(1)));
+
+        let mut player = FakeClient::connect(gc.clone());
+        assert_eq!(player.recv().await, Some(ServerMsg::Hello));
+        player.send(ClientMsg::Connect { game_id, player_id }).await;
+        assert_eq!(player.recv().await, Some(ServerMsg::Spectators(1)));
+        drop(player);
+        assert_eq!(
+            spectator.recv().await,
+            Some(ServerMsg::PlayerLeft { id: player_id })
+        );
+
+        let mut player = FakeClient::connect(gc);
+        assert_eq!(player.recv().await, Some(ServerMsg::Hello));
+        player.send(ClientMsg::Connect { game_id, player_id }).await;
+        assert_eq!(
+            spectator.recv().await,
+            Some(ServerMsg::PlayerRejoined { id: player_id })
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn seat_is_released_after_the_grace_period() {
+        let (gc, game_id, player_id) = game_controller().await;
+        let gc = gc.with_seat_grace(Duration::from_millis(50));
+        let mut spectator = FakeClient::connect(gc.clone());
+        assert_eq!(spectator.recv().await, Some(ServerMsg::Hello));
+        spectator.send(ClientMsg::Spectate { game_id }).await;
+        assert!(matches!(
+            spectator.recv().await,
+            Some(ServerMsg::StateSnapshot(_))
+        ));
+        assert_eq!(spectator.recv().await, Some(ServerMsg::Spectators(1)));
+
+        drop(FakeClient::play(gc, game_id, player_id).await);
+        assert_eq!(
+            spectator.recv().await,
+            Some(ServerMsg::PlayerLeft { id: player_id })
+        );
+
+        // The active player is skipped long before the default grace
+        assert!(matches!(
+            spectator.recv().await,
+            Some(ServerMsg::StateDiff(_))
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test(start_paused = true)]
+    async fn silent_client_is_disconnected() {
+        let (gc, game_id, player_id) = game_controller().await;
+        let mut client = FakeClient::connect(gc.clone());
+        client.send(ClientMsg::Connect { game_id, player_id }).await;
+
+        // Never answers the pings. Time advances on its own while idle.
+        while client.rx.next().await.is_some() {}
+
+        let room = gc.get(&game_id).await.expect("Game should exist");
+        assert_eq!(room.snapshot(None).await.away, vec![player_id]);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn private_chat_only_reaches_both_ends() {
+        let (gc, game_id, players) = game_with_players().await;
+        let mut alice = FakeClient::play(gc.clone(), game_id, players[0]).await;
+        let mut bob = FakeClient::play(gc.clone(), game_id, players[1]).await;
+        let mut carol = FakeClient::play(gc, game_id, players[2]).await;
+
+        alice
+            .send(ClientMsg::Chat {
+                to: Some(players[1]),
+                text: "psst".into(),
+            })
+            .await;
+        let private = Some(ServerMsg::Chat {
+            from: players[0],
+            to: Some(players[1]),
+            text: "psst".into(),
+        });
+        assert_eq!(bob.recv().await, private);
+        assert_eq!(alice.recv().await, private);
+
+        alice
+            .send(ClientMsg::Chat {
+                to: None,
+                text: "  hi all ".into(),
+            })
+            .await;
+        // Carol never got the private message
+        assert_eq!(
+            carol.recv().await,
+            Some(ServerMsg::Chat {
+                from: players[0],
+                to: None,
+                text: "hi all".into(),
+            })
+        );
+    }
+
+    #[rstest]
+    #[case::empty("   ".to_string(), None)]
+    #[case::too_long("a".repeat(MAX_CHAT_LEN + 1), None)]
+    #[case::stranger("hi".to_string(), Some(Uuid::new_v4()))]
+    #[tokio::test]
+    async fn invalid_chat_is_rejected(#[case] text: String, #[case] to: Option<Uuid>) {
+        let (gc, game_id, player_id) = game_controller().await;
+        let mut client = FakeClient::play(gc, game_id, player_id).await;
+
+        client.send(ClientMsg::Chat { to, text }).await;
+        assert_eq!(client.recv().await, Some(ServerMsg::BadRequest));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn chat_flood_is_slowed_down() {
+        let (gc, game_id, player_id) = game_controller().await;
+        let mut client = FakeClient::play(gc, game_id, player_id).await;
+        let reaction = ServerMsg::Reaction {
+            from: player_id,
+            reaction: Reaction::Bribe,
+        };
+
+        for _ in 0..CHAT_BURST {
+            client.send(ClientMsg::React(Reaction::Bribe)).await;
+            assert_eq!(client.recv().await, Some(reaction.clone()));
+        }
+        client.send(ClientMsg::React(Reaction::Bribe)).await;
+        assert_eq!(client.recv().await, Some(ServerMsg::SlowDown));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn lagging_client_gets_a_snapshot() {
+        let gc = GameController::with_capacity(2);
+        let game_id = Uuid::new_v4();
+        let players = vec![
+            (Uuid::new_v4(), "p0".to_string()),
+            (Uuid::new_v4(), "p1".to_string()),
+        ];
+        gc.new_game(game_id, &players)
+            .await
+            .expect("Game should be created");
+        let player_id = players[0].0;
+        let mut client = FakeClient::play(gc.clone(), game_id, player_id).await;
+
+        // The forwarder cannot run until the flood ends, so it falls behind
+        let room = gc.get(&game_id).await.expect("Game should exist");
+        for i in 0..20 {
+            room.tx.send(&ServerMsg::Chat {
+                from: players[1].0,
+                to: None,
+                text: format!("flood {i}"),
+            });
+        }
+
+        assert!(matches!(
+            client.recv().await,
+            Some(ServerMsg::StateSnapshot(_))
+        ));
+        // Only what still fits in the channel comes after the snapshot
+        for i in 18..20 {
+            assert_eq!(
+                client.recv().await,
+                Some(ServerMsg::Chat {
+                    from: players[1].0,
+                    to: None,
+                    text: format!("flood {i}"),
+                })
+            );
+        }
+
+        // And the client is still in the room
+        client.send(ClientMsg::React(Reaction::Bribe)).await;
+        assert_eq!(
+            client.recv().await,
+            Some(ServerMsg::Reaction {
+                from: player_id,
+                reaction: Reaction::Bribe,
+            })
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use futures::future::{self, BoxFuture};
+use futures::stream::{self, BoxStream, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use super::{LocalBus, Result, RoomBus};
+
+/// Start of the channels of room topics in the broker
+const ROOMS: &str = "plaicards:room:";
+/// Start of the keys with the owner of each room
+const OWNERS: &str = "plaicards:owner:";
+
+/// Pub/sub server shared by every instance, like Redis
+pub trait Broker: Debug + Send + Sync {
+    /// Sends `payload` to everyone listening to `channel`
+    fn publish(&self, channel: String, payload: String) -> BoxFuture<'static, Result<()>>;
+
+    /// Every `(channel, payload)` published from now on in channels starting
+    /// with `prefix`
+    fn listen(
+        &self,
+        prefix: &str,
+    ) -> BoxFuture<'static, Result<BoxStream<'static, (String, String)>>>;
+
+    /// Stores `value` in `key` for `ttl` unless it holds another value. If it
+    /// already holds `value` it is kept for `ttl` more. Returns what `key`
+    /// holds afterwards.
+    fn lease(
+        &self,
+        key: String,
+        value: String,
+        ttl: Duration,
+    ) -> BoxFuture<'static, Result<String>>;
+
+    /// Deletes `key` if it still holds `value`
+    fn delete_if(&self, key: String, value: String) -> BoxFuture<'static, Result<()>>;
+}
+
+/// Bus of an instance among several, connected by a [`Broker`]
+///
+/// Owning a room is a lease of `lease` that this instance keeps extending
+/// while it holds the room. The rooms of an instance that died without
+/// releasing them can be claimed by others once their lease expires.
+#[derive(Debug)]
+pub struct BrokerBus {
+    /// Name of this instance in the ownership keys
+    node: String,
+    broker: Arc<dyn Broker>,
+    lease: Duration,
+    /// Rooms claimed by this instance, whose lease is extended
+    owned: Arc<Mutex<HashSet<Uuid>>>,
+    /// Subscribers of this instance, fed with what the broker delivers
+    local: LocalBus,
+    /// Messages waiting to be published, in order
+    outbox: mpsc::UnboundedSender<(String, String)>,
+}
+
+impl BrokerBus {
+    /// Starts relaying room messages between the broker and this instance
+    /// and extending the lease of its rooms
+    ///
+    /// # Errors
+    ///
+    /// If the broker cannot be reached
+    pub async fn connect(node: String, broker: Arc<dyn Broker>, lease: Duration) -> Result<Self> {
+        let local = LocalBus::default();
+        let mut inbox = broker.listen(ROOMS).await?;
+        let subscribers = local.clone();
+        tokio::spawn(async move {
+            while let Some((channel, payload)) = inbox.next().await {
+                if let Some(topic) = channel.strip_prefix(ROOMS) {
+                    subscribers.publish(topic, payload);
+                }
+            }
+            error!("Lost the broker. Rooms stop receiving messages");
+        });
+
+        // A single publisher keeps the order of the messages of a room
+        let (outbox, mut pending) = mpsc::unbounded_channel::<(String, String)>();
+        let publisher = broker.clone();
+        tokio::spawn(async move {
+            while let Some((channel, payload)) = pending.recv().await {
+                if let Err(e) = publisher.publish(channel, payload).await {
+                    warn!("Room message lost: {e}");
+                }
+            }
+        });
+
+        // Extends the leases well before they expire, until the bus is gone
+        let owned = Arc::new(Mutex::new(HashSet::new()));
+        let rooms = Arc::downgrade(&owned);
+        let (leaser, holder) = (broker.clone(), node.clone());
+        tokio::spawn(async move {
+            let mut every = tokio::time::interval((lease / 3).max(Duration::from_millis(1)));
+            every.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                every.tick().await;
+                let Some(owned) = rooms.upgrade() else {
+                    break;
+                };
+                let held: Vec<Uuid> = lock(&owned).iter().copied().collect();
+                for room in held {
+                    let key = format!("{OWNERS}{room}");
+                    match leaser.lease(key, holder.clone(), lease).await {
+                        Ok(owner) if owner == holder => {}
+                        Ok(owner) => {
+                            error!("Room {room} was taken by {owner} after its lease expired");
+                            lock(&owned).remove(&room);
+                        }
+                        Err(e) => warn!("Cannot extend the lease of room {room}: {e}"),
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            node,
+            broker,
+            lease,
+            owned,
+            local,
+            outbox,
+        })
+    }
+
+    #[must_use]
+    pub fn node(&self) -> &str {
+        &self.node
+    }
+}
+
+impl RoomBus for BrokerBus {
+    fn publish(&self, topic: &str, payload: String) {
+        // Subscribers of this instance also get it from the broker, so they
+        // see the same order as everyone else
+        let _ = self.outbox.send((format!("{ROOMS}{topic}"), payload));
+    }
+
+    fn subscribe(&self, topic: &str, capacity: usize) -> broadcast::Receiver<String> {
+        self.local.subscribe(topic, capacity)
+    }
+
+    fn claim(&self, room: Uuid) -> BoxFuture<'static, Result<bool>> {
+        let owner = self
+            .broker
+            .lease(format!("{OWNERS}{room}"), self.node.clone(), self.lease);
+        let node = self.node.clone();
+        let owned = self.owned.clone();
+        Box::pin(async move {
+            let claimed = owner.await? == node;
+            if claimed {
+                lock(&owned).insert(room);
+            }
+            Ok(claimed)
+        })
+    }
+
+    fn release(&self, room: Uuid) -> BoxFuture<'static, Result<()>> {
+        lock(&self.owned).remove(&room);
+        self.broker
+            .delete_if(format!("{OWNERS}{room}"), self.node.clone())
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    // The sets stay valid even if a holder of the lock panicked
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Broker living in the process. Clones share it, like connections to the
+/// same server. Stands in for a real one in tests.
+#[derive(Clone, Debug)]
+pub struct MemoryBroker {
+    messages: broadcast::Sender<(String, String)>,
+    /// Values and when they expire
+    keys: Arc<Mutex<HashMap<String, (String, Instant)>>>,
+}
+
+impl Default for MemoryBroker {
+    fn default() -> Self {
+        Self {
+            messages: broadcast::channel(1024).0,
+            keys: Arc::default(),
+        }
+    }
+}
+
+impl Broker for MemoryBroker {
+    fn publish(&self, channel: String, payload: String) -> BoxFuture<'static, Result<()>> {
+        // Nobody listening is fine
+        let _ = self.messages.send((channel, payload));
+        Box::pin(future::ready(Ok(())))
+    }
+
+    fn listen(
+        &self,
+        prefix: &str,
+    ) -> BoxFuture<'static, Result<BoxStream<'static, (String, String)>>> {
+        let prefix = prefix.to_string();
+        let messages = stream::unfold(self.messages.subscribe(), |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(msg) => return Some((msg, rx)),
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("Broker listener lost {n} messages");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+        .filter(move |(channel, _)| future::ready(channel.starts_with(&prefix)));
+        Box::pin(future::ready(Ok(messages.boxed())))
+    }
+
+    fn lease(
+        &self,
+        key: String,
+        value: String,
+        ttl: Duration,
+    ) -> BoxFuture<'static, Result<String>> {
+        let now = Instant::now();
+        let mut keys = lock(&self.keys);
+        let held = match keys.get(&key) {
+            Some((held, expires)) if *expires > now => held.clone(),
+            _ => value.clone(),
+        };
+        if held == value {
+            keys.insert(key, (value, now + ttl));
+        }
+        drop(keys);
+        Box::pin(future::ready(Ok(held)))
+    }
+
+    fn delete_if(&self, key: String, value: String) -> BoxFuture<'static, Result<()>> {
+        let mut keys = lock(&self.keys);
+        if keys.get(&key).is_some_and(|(held, _)| *held == value) {
+            keys.remove(&key);
+        }
+        drop(keys);
+        Box::pin(future::ready(Ok(())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    use super::{BrokerBus, MemoryBroker};
+    use crate::web::bus::{Receiver, RoomBus, Topic};
+
+    const LEASE: Duration = Duration::from_secs(3);
+
+    async fn node(name: &str, broker: &MemoryBroker) -> Arc<BrokerBus> {
+        let bus = BrokerBus::connect(name.into(), Arc::new(broker.clone()), LEASE)
+            .await
+            .expect("Memory broker is always there");
+        Arc::new(bus)
+    }
+
+    async fn next(rx: &mut Receiver<String>) -> String {
+        tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("Message did not arrive")
+            .expect("Topic is open")
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn instances_share_room_messages() {
+        let broker = MemoryBroker::default();
+        let a = Topic::<String>::new(node("a", &broker).await, "game:1".into(), 4);
+        let b = Topic::<String>::new(node("b", &broker).await, "game:1".into(), 4);
+        let other = Topic::<String>::new(node("c", &broker).await, "game:2".into(), 4);
+        let mut on_a = a.subscribe();
+        let mut on_b = b.subscribe();
+        let mut elsewhere = other.subscribe();
+
+        a.send(&"first".to_string());
+        b.send(&"second".to_string());
+        other.send(&"unrelated".to_string());
+
+        for rx in [&mut on_a, &mut on_b] {
+            let mut got = vec![next(rx).await, next(rx).await];
+            got.sort();
+            assert_eq!(got, ["first", "second"]);
+        }
+        assert_eq!(next(&mut elsewhere).await, "unrelated");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn only_one_instance_owns_a_room() {
+        let broker = MemoryBroker::default();
+        let a = node("a", &broker).await;
+        let b = node("b", &broker).await;
+        let room = Uuid::new_v4();
+
+        assert_eq!(a.claim(room).await, Ok(true));
+        assert_eq!(b.claim(room).await, Ok(false));
+        assert_eq!(a.claim(room).await, Ok(true), "Claiming again is fine");
+
+        b.release(room).await.expect("Broker is there");
+        assert_eq!(b.claim(room).await, Ok(false), "Only the owner releases");
+        a.release(room).await.expect("Broker is there");
+        assert_eq!(b.claim(room).await, Ok(true));
+    }
+
+    #[rstest]
+    #[tokio::test(start_paused = true)]
+    async fn owners_keep_extending_their_lease() {
+        let broker = MemoryBroker::default();
+        let a = node("a", &broker).await;
+        let b = node("b", &broker).await;
+        let room = Uuid::new_v4();
+        assert_eq!(a.claim(room).await, Ok(true));
+
+        tokio::time::sleep(LEASE * 4).await;
+        assert_eq!(b.claim(room).await, Ok(false), "Lease is extended");
+
+        // An instance that dies does not release its rooms
+        drop(a);
+        tokio::time::sleep(LEASE * 2).await;
+        assert_eq!(b.claim(room).await, Ok(true), "Lease expired");
+    }
+}
@@ -0,0 +1,277 @@
+//! Pub/sub of the messages of game rooms and lobbies.
+//!
+//! Rooms publish on a [`Topic`] of a [`RoomBus`] and every client of the
+//! room subscribes to it. [`LocalBus`] keeps the messages in the process,
+//! which is all a single instance needs. [`BrokerBus`] sends them through a
+//! [`Broker`] shared by every instance, like Redis, so clients of a lobby
+//! connected to different instances see each other.
+//!
+//! A [`Game`](crate::game::Game) lives in a single process, so each game
+//! room is owned by the instance that claimed it with [`RoomBus::claim`] and
+//! only that one mutates it. Other instances do not serve the room: a board
+//! websocket that reaches them gets a `BadRequest`. The load balancer must
+//! route every board connection to the owner, hashing on the `room` query
+//! parameter of `/game/ws`. The server does not read it, it is only there
+//! for the load balancer.
+
+use futures::future::{self, BoxFuture};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::config;
+
+mod broker;
+#[cfg(feature = "redis")]
+mod redis_broker;
+
+pub use self::broker::{Broker, BrokerBus, MemoryBroker};
+#[cfg(feature = "redis")]
+pub use self::redis_broker::RedisBroker;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum BusError {
+    #[error("cannot reach the broker: {0}")]
+    Broker(String),
+}
+
+pub type Result<T> = core::result::Result<T, BusError>;
+
+/// Delivers the messages of room topics to their subscribers.
+///
+/// Messages are already encoded. Use a [`Topic`] to send and receive typed
+/// ones.
+pub trait RoomBus: Debug + Send + Sync {
+    /// Sends `payload` to the subscribers of `topic`, in every instance.
+    /// Nobody listening is fine.
+    fn publish(&self, topic: &str, payload: String);
+
+    /// Receives what is published on `topic` from now on. Subscribers more
+    /// than `capacity` messages behind lag, like any broadcast channel.
+    fn subscribe(&self, topic: &str, capacity: usize) -> broadcast::Receiver<String>;
+
+    /// Makes this instance the owner of `room`.
+    ///
+    /// Returns `false` if another instance owns it. Claiming a room this
+    /// instance already owns is fine.
+    fn claim(&self, room: Uuid) -> BoxFuture<'static, Result<bool>>;
+
+    /// Lets other instances claim `room`. Rooms not released are claimable
+    /// once this instance stops extending its ownership.
+    fn release(&self, room: Uuid) -> BoxFuture<'static, Result<()>>;
+}
+
+/// Messages that can travel through a [`RoomBus`]
+pub trait Payload: Clone + Send + Sized + 'static {
+    fn encode(&self) -> String;
+    fn decode(payload: String) -> Option<Self>;
+}
+
+impl Payload for String {
+    fn encode(&self) -> String {
+        self.clone()
+    }
+
+    fn decode(payload: String) -> Option<Self> {
+        Some(payload)
+    }
+}
+
+/// Typed end of a topic, to publish and subscribe
+#[derive(Debug)]
+pub struct Topic<T> {
+    bus: Arc<dyn RoomBus>,
+    name: String,
+    capacity: usize,
+    msg: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Topic<T> {
+    fn clone(&self) -> Self {
+        Self {
+            bus: self.bus.clone(),
+            name: self.name.clone(),
+            capacity: self.capacity,
+            msg: PhantomData,
+        }
+    }
+}
+
+impl<T: Payload> Topic<T> {
+    #[must_use]
+    pub fn new(bus: Arc<dyn RoomBus>, name: String, capacity: usize) -> Self {
+        Self {
+            bus,
+            name,
+            capacity,
+            msg: PhantomData,
+        }
+    }
+
+    pub fn send(&self, msg: &T) {
+        self.bus.publish(&self.name, msg.encode());
+    }
+
+    #[must_use]
+    pub fn subscribe(&self) -> Receiver<T> {
+        Receiver {
+            rx: self.bus.subscribe(&self.name, self.capacity),
+            msg: PhantomData,
+        }
+    }
+}
+
+/// Subscription to a [`Topic`]
+#[derive(Debug)]
+pub struct Receiver<T> {
+    rx: broadcast::Receiver<String>,
+    msg: PhantomData<fn() -> T>,
+}
+
+impl<T: Payload> Receiver<T> {
+    /// Next message, like [`broadcast::Receiver::recv`]. Messages that cannot
+    /// be decoded, from an instance running another version, are skipped.
+    ///
+    /// # Errors
+    ///
+    /// If this subscriber lagged or the topic is gone
+    pub async fn recv(&mut self) -> core::result::Result<T, RecvError> {
+        loop {
+            let payload = self.rx.recv().await?;
+            match T::decode(payload) {
+                Some(msg) => return Ok(msg),
+                None => warn!("Skipping a room message that cannot be decoded"),
+            }
+        }
+    }
+}
+
+/// Bus of a single instance. Every room is owned by it.
+#[derive(Clone, Debug, Default)]
+pub struct LocalBus {
+    topics: Arc<Mutex<HashMap<String, broadcast::Sender<String>>>>,
+}
+
+impl LocalBus {
+    fn topics(&self) -> MutexGuard<'_, HashMap<String, broadcast::Sender<String>>> {
+        // Senders stay valid even if a holder of the lock panicked
+        self.topics
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl RoomBus for LocalBus {
+    fn publish(&self, topic: &str, payload: String) {
+        let mut topics = self.topics();
+        // Forget topics without subscribers
+        if topics
+            .get(topic)
+            .is_some_and(|tx| tx.send(payload).is_err())
+        {
+            topics.remove(topic);
+        }
+    }
+
+    fn subscribe(&self, topic: &str, capacity: usize) -> broadcast::Receiver<String> {
+        self.topics()
+            .entry(topic.to_string())
+            .or_insert_with(|| broadcast::channel(capacity).0)
+            .subscribe()
+    }
+
+    fn claim(&self, _room: Uuid) -> BoxFuture<'static, Result<bool>> {
+        Box::pin(future::ready(Ok(true)))
+    }
+
+    fn release(&self, _room: Uuid) -> BoxFuture<'static, Result<()>> {
+        Box::pin(future::ready(Ok(())))
+    }
+}
+
+/// Bus described by the configuration
+///
+/// # Errors
+///
+/// If the broker cannot be reached
+pub async fn open(config: &config::Bus) -> Result<Arc<dyn RoomBus>> {
+    let Some(url) = &config.redis_url else {
+        return Ok(Arc::new(LocalBus::default()));
+    };
+    let node = config
+        .node
+        .clone()
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    let broker = connect(url).await?;
+    let lease = Duration::from_secs(config.lease_secs);
+    Ok(Arc::new(BrokerBus::connect(node, broker, lease).await?))
+}
+
+#[cfg(feature = "redis")]
+async fn connect(url: &str) -> Result<Arc<dyn Broker>> {
+    Ok(Arc::new(RedisBroker::connect(url).await?))
+}
+
+#[cfg(not(feature = "redis"))]
+async fn connect(_url: &str) -> Result<Arc<dyn Broker>> {
+    Err(BusError::Broker("built without the redis feature".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use std::sync::Arc;
+    use tokio::sync::broadcast::error::RecvError;
+    use uuid::Uuid;
+
+    use super::{LocalBus, RoomBus, Topic};
+
+    fn topic(bus: &LocalBus, capacity: usize) -> Topic<String> {
+        Topic::new(Arc::new(bus.clone()), "game:test".into(), capacity)
+    }
+
+    #[rstest]
+    async fn subscribers_get_what_is_published() {
+        let bus = LocalBus::default();
+        let topic = topic(&bus, 4);
+        let mut first = topic.subscribe();
+        let mut second = topic.subscribe();
+
+        topic.send(&"hello".to_string());
+
+        assert_eq!(first.recv().await, Ok("hello".to_string()));
+        assert_eq!(second.recv().await, Ok("hello".to_string()));
+    }
+
+    #[rstest]
+    async fn slow_subscribers_lag() {
+        let bus = LocalBus::default();
+        let topic = topic(&bus, 2);
+        let mut rx = topic.subscribe();
+
+        for i in 0..5 {
+            topic.send(&i.to_string());
+        }
+
+        assert_eq!(rx.recv().await, Err(RecvError::Lagged(3)));
+        assert_eq!(rx.recv().await, Ok("3".to_string()));
+    }
+
+    #[rstest]
+    async fn topics_without_subscribers_are_forgotten() {
+        let bus = LocalBus::default();
+        let topic = topic(&bus, 2);
+        drop(topic.subscribe());
+
+        topic.send(&"nobody".to_string());
+
+        assert!(bus.topics().is_empty());
+        assert_eq!(bus.claim(Uuid::new_v4()).await, Ok(true));
+    }
+}
@@ -0,0 +1,123 @@
+use futures::future::BoxFuture;
+use futures::stream::{BoxStream, StreamExt};
+use redis::aio::MultiplexedConnection;
+use redis::{Client, RedisError, Script};
+use std::fmt::{self, Debug};
+use std::time::Duration;
+
+use super::{Broker, BusError, Result};
+
+/// Sets the key with a TTL of `ARGV[2]` ms unless another value holds it,
+/// extends it if it holds this one and returns its value, in one step
+const LEASE: &str = r"
+local held = redis.call('GET', KEYS[1])
+if not held then
+    redis.call('SET', KEYS[1], ARGV[1], 'PX', ARGV[2])
+    return ARGV[1]
+end
+if held == ARGV[1] then
+    redis.call('PEXPIRE', KEYS[1], ARGV[2])
+end
+return held
+";
+const DELETE_IF: &str = r"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+";
+
+/// [`Broker`] over Redis pub/sub. Only with the `redis` feature.
+#[derive(Clone)]
+pub struct RedisBroker {
+    client: Client,
+    /// Shared by every command. Subscriptions need their own connection.
+    conn: MultiplexedConnection,
+}
+
+impl Debug for RedisBroker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisBroker").finish_non_exhaustive()
+    }
+}
+
+impl RedisBroker {
+    /// # Errors
+    ///
+    /// If `url` is not valid or the server cannot be reached
+    pub async fn connect(url: &str) -> Result<Self> {
+        let client = Client::open(url).map_err(broker_error)?;
+        let conn = client
+            .get_multiplexed_tokio_connection()
+            .await
+            .map_err(broker_error)?;
+        Ok(Self { client, conn })
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn broker_error(e: RedisError) -> BusError {
+    BusError::Broker(e.to_string())
+}
+
+impl Broker for RedisBroker {
+    fn publish(&self, channel: String, payload: String) -> BoxFuture<'static, Result<()>> {
+        let mut conn = self.conn.clone();
+        Box::pin(async move {
+            redis::cmd("PUBLISH")
+                .arg(channel)
+                .arg(payload)
+                .query_async::<_, ()>(&mut conn)
+                .await
+                .map_err(broker_error)
+        })
+    }
+
+    fn listen(
+        &self,
+        prefix: &str,
+    ) -> BoxFuture<'static, Result<BoxStream<'static, (String, String)>>> {
+        let client = self.client.clone();
+        let pattern = format!("{prefix}*");
+        Box::pin(async move {
+            let mut pubsub = client.get_async_pubsub().await.map_err(broker_error)?;
+            pubsub.psubscribe(pattern).await.map_err(broker_error)?;
+            let messages = pubsub.into_on_message().filter_map(|msg| async move {
+                let payload = msg.get_payload::<String>().ok()?;
+                Some((msg.get_channel_name().to_string(), payload))
+            });
+            Ok(messages.boxed())
+        })
+    }
+
+    fn lease(
+        &self,
+        key: String,
+        value: String,
+        ttl: Duration,
+    ) -> BoxFuture<'static, Result<String>> {
+        let mut conn = self.conn.clone();
+        let millis = u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX);
+        Box::pin(async move {
+            Script::new(LEASE)
+                .key(key)
+                .arg(value)
+                .arg(millis)
+                .invoke_async::<_, String>(&mut conn)
+                .await
+                .map_err(broker_error)
+        })
+    }
+
+    fn delete_if(&self, key: String, value: String) -> BoxFuture<'static, Result<()>> {
+        let mut conn = self.conn.clone();
+        Box::pin(async move {
+            Script::new(DELETE_IF)
+                .key(key)
+                .arg(value)
+                .invoke_async::<_, ()>(&mut conn)
+                .await
+                .map_err(broker_error)
+        })
+    }
+}
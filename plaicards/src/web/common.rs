@@ -2,37 +2,37 @@ use leptos::*;
 use leptos_meta::*;
 use tailwind_fuse::*;
 
-use crate::web::plausible::components::A;
+use leptos_plausible::components::A;
 
 #[component]
 pub fn Button(
-    #[prop(into)] title: String,
+    #[prop(into)] title: TextProp,
     #[prop(into, default = "green".into())] color: String,
     #[prop(into, default = "".into())] class: String,
 ) -> impl IntoView {
     view! {
       <button class=format!(
           "flex w-full justify-center rounded-md bg-{color}-600 px-3 py-1.5 text-sm font-semibold leading-6 text-white shadow-sm hover:bg-{color}-500 disabled:opacity-75 {class}",
-      )>{title}</button>
+      )>{move || title.get()}</button>
     }
 }
 
 #[component]
-pub fn ButtonDisablable(title: String, disabled: Signal<bool>) -> impl IntoView {
+pub fn ButtonDisablable(#[prop(into)] title: TextProp, disabled: Signal<bool>) -> impl IntoView {
     view! {
       <button
         class="py-2 px-4 ml-2 font-bold text-white bg-orange-500 rounded focus:outline-none disabled:bg-orange-900 disabled:opacity-50 enabled:hover:bg-orange-700 focus:shadow-outline"
         class=("cursor-not-allowed", disabled)
         disabled=disabled
       >
-        {title}
+        {move || title.get()}
       </button>
     }
 }
 
 #[component]
 pub fn ButtonLink(
-    #[prop(into)] title: String,
+    #[prop(into)] title: TextProp,
     #[prop(into)] href: String,
     #[prop(into, default = "green".into())] color: String,
 ) -> impl IntoView {
@@ -44,14 +44,14 @@ pub fn ButtonLink(
         )
       >
 
-        {title}
+        {move || title.get()}
       </A>
     }
 }
 
 #[component]
 pub fn ButtonLinkSecond(
-    #[prop(into)] title: String,
+    #[prop(into)] title: TextProp,
     #[prop(into)] href: String,
     #[prop(into, default = "_self".into())] target: String,
     #[prop(into, default = "green".into())] color: String,
@@ -66,7 +66,7 @@ pub fn ButtonLinkSecond(
         )
       >
 
-        {title}
+        {move || title.get()}
       </A>
     }
 }
@@ -93,12 +93,12 @@ pub fn ListDecoration() -> impl IntoView {
 }
 
 #[component]
-pub fn H3(#[prop(into)] txt: String) -> impl IntoView {
+pub fn H3(#[prop(into)] txt: TextProp) -> impl IntoView {
     view! {
 
       <div class="lg:pl-10 pl-4 mt-10 bg-orange">
         <h3 class=tw_merge!("uppercase","w-fit","px-4","bg-white","text-xl", "font-bold", "tracking-tight", "text-orange", "sm:text-2xl","leading-none")>
-        {txt}
+        {move || txt.get()}
         </h3>
       </div>
     }
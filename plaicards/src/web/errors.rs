@@ -14,9 +14,13 @@ pub enum Error {
     NotFound,
     Duplicated,
     LoginFail,
-    ServerError { e: ServerFnError },
+    ServerError {
+        e: ServerFnError,
+    },
     WebsocketError,
     GameError(GameError),
+    /// The server is shutting down
+    Unavailable,
 
     // Auth Errors
     AuthFailNoAuthTokenCookie,
@@ -0,0 +1,59 @@
+//! Probes for the orchestrator.
+//!
+//! `/healthz` answers while the process is alive. `/readyz` starts failing as
+//! soon as the server is shutting down so no new traffic is routed to it.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Default)]
+pub struct Health {
+    draining: Arc<AtomicBool>,
+}
+
+impl Health {
+    pub fn start_draining(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        !self.draining.load(Ordering::SeqCst)
+    }
+}
+
+pub async fn healthz() -> &'static str {
+    "ok"
+}
+
+pub async fn readyz(State(health): State<Health>) -> (StatusCode, &'static str) {
+    if health.is_ready() {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "draining")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::State;
+    use axum::http::StatusCode;
+    use rstest::rstest;
+
+    use super::{readyz, Health};
+
+    #[rstest]
+    async fn not_ready_while_draining() {
+        let health = Health::default();
+        assert_eq!(readyz(State(health.clone())).await.0, StatusCode::OK);
+
+        health.start_draining();
+
+        assert_eq!(
+            readyz(State(health)).await.0,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+}
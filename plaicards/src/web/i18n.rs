@@ -0,0 +1,213 @@
+//! Translations of the website, from the Fluent resources in `locales/`.
+//!
+//! Static texts use `tr!` and `move_tr!` with their key. Cards are looked up
+//! by the keys built from their ids, see [`card_title`].
+
+use leptos::*;
+use leptos_fluent::{expect_i18n, move_tr};
+
+use super::board::msg;
+use crate::game::CardEffect;
+
+/// Key of the title of a card, with its `description` as attribute
+fn card_key(id: &str) -> String {
+    format!("card-{id}")
+}
+
+fn effect_key(effect: CardEffect) -> String {
+    format!("effect-{}", effect.id())
+}
+
+/// Title of `card` in the active language
+#[must_use]
+pub fn card_title(card: &msg::Card) -> String {
+    expect_i18n().tr(&card_key(&card.id))
+}
+
+/// Description of `card` in the active language
+#[must_use]
+pub fn card_description(card: &msg::Card) -> String {
+    expect_i18n().tr(&format!("{}.description", card_key(&card.id)))
+}
+
+/// Effect of `card` in the active language, empty for cards without one
+#[must_use]
+pub fn card_effect(card: &msg::Card) -> String {
+    if card.effect == CardEffect::NoEffect {
+        return String::new();
+    }
+    expect_i18n().tr(&effect_key(card.effect))
+}
+
+/// Picks the language of the website among the ones in `locales/`
+#[component]
+pub fn LanguageSwitcher() -> impl IntoView {
+    let i18n = expect_i18n();
+
+    let activate = move |ev| {
+        let id = event_target_value(&ev);
+        if let Some(lang) = i18n.languages.iter().find(|l| l.id.to_string() == id) {
+            lang.activate();
+        }
+    };
+
+    view! {
+      <div class="my-4 text-center text-gray-700">
+        <label for="language" class="mr-2">
+          {move_tr!("language")}
+        </label>
+        <select id="language" on:change=activate>
+          {i18n
+              .languages
+              .iter()
+              .map(|lang| {
+                  view! {
+                    <option value=lang.id.to_string() prop:selected=move || lang.is_active()>
+                      {lang.name}
+                    </option>
+                  }
+              })
+              .collect_view()}
+        </select>
+      </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use rstest::{fixture, rstest};
+
+    use super::{card_key, effect_key};
+    use crate::game::get_cards_available;
+
+    /// Language the website falls back to, the reference for the others
+    const FALLBACK: &str = "en";
+
+    fn root() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    }
+
+    /// Whether `s` can be the id of a message or an attribute
+    fn is_id(s: &str) -> bool {
+        s.starts_with(|c: char| c.is_ascii_alphabetic())
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    /// Messages and their attributes in a Fluent resource, as `id` and
+    /// `id.attribute`
+    fn keys(ftl: &str) -> BTreeSet<String> {
+        let mut keys = BTreeSet::new();
+        let mut message = None;
+        for line in ftl.lines() {
+            let Some((name, _)) = line.split_once('=') else {
+                continue;
+            };
+            let name = name.trim();
+            if !line.starts_with(char::is_whitespace) && is_id(name) {
+                keys.insert(name.to_string());
+                message = Some(name.to_string());
+            } else if let (Some(msg), Some(attr)) = (&message, name.strip_prefix('.')) {
+                if is_id(attr) {
+                    keys.insert(format!("{msg}.{attr}"));
+                }
+            }
+        }
+        keys
+    }
+
+    /// Keys of every language in `locales/`
+    #[fixture]
+    fn locales() -> BTreeMap<String, BTreeSet<String>> {
+        let mut locales = BTreeMap::new();
+        for lang in fs::read_dir(root().join("locales")).expect("Locales exist") {
+            let lang = lang.expect("Readable locale").path();
+            let mut lang_keys = BTreeSet::new();
+            for resource in fs::read_dir(&lang).expect("Readable locale") {
+                let resource = resource.expect("Readable resource").path();
+                let ftl = fs::read_to_string(&resource).expect("Readable resource");
+                lang_keys.extend(keys(&ftl));
+            }
+            let name = lang.file_name().expect("Locale name").to_string_lossy();
+            locales.insert(name.into_owned(), lang_keys);
+        }
+        locales
+    }
+
+    /// Literal keys of every `tr!` and `move_tr!` in the sources under `dir`
+    fn used_keys(dir: &Path, keys: &mut BTreeSet<String>) {
+        for entry in fs::read_dir(dir).expect("Readable sources") {
+            let path = entry.expect("Readable source").path();
+            if path.is_dir() {
+                used_keys(&path, keys);
+                continue;
+            }
+            let source = fs::read_to_string(&path).expect("Readable source");
+            let mut rest = source.as_str();
+            while let Some(start) = rest.find("tr!(") {
+                rest = rest[start + 4..].trim_start();
+                let key = rest.strip_prefix('"').and_then(|r| r.split('"').next());
+                if let Some(key) = key.filter(|k| k.split('.').all(is_id)) {
+                    keys.insert(key.to_string());
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    fn every_key_exists_in_every_locale(locales: BTreeMap<String, BTreeSet<String>>) {
+        let fallback = &locales[FALLBACK];
+        assert!(locales.len() > 1, "More languages than {FALLBACK}");
+
+        for (lang, keys) in &locales {
+            let missing: Vec<_> = fallback.difference(keys).collect();
+            let unknown: Vec<_> = keys.difference(fallback).collect();
+            assert!(missing.is_empty(), "Missing in {lang}: {missing:?}");
+            assert!(unknown.is_empty(), "Only in {lang}: {unknown:?}");
+        }
+    }
+
+    #[rstest]
+    fn every_card_has_its_texts(locales: BTreeMap<String, BTreeSet<String>>) {
+        let fallback = &locales[FALLBACK];
+
+        for card in get_cards_available() {
+            let key = card_key(&card.id());
+            assert!(fallback.contains(&key), "{} has no title", card.title());
+            assert!(
+                fallback.contains(&format!("{key}.description")),
+                "{} has no description",
+                card.title()
+            );
+            let effect = effect_key(card.effect());
+            assert!(fallback.contains(&effect), "{effect} has no text");
+        }
+    }
+
+    #[rstest]
+    fn views_use_existing_keys(locales: BTreeMap<String, BTreeSet<String>>) {
+        let mut used = BTreeSet::new();
+        used_keys(&root().join("src"), &mut used);
+
+        let unknown: Vec<_> = used.difference(&locales[FALLBACK]).collect();
+        assert!(!used.is_empty(), "Views are translated");
+        assert!(unknown.is_empty(), "Unknown keys: {unknown:?}");
+    }
+
+    #[rstest]
+    fn card_ids_are_unique() {
+        let mut cards = BTreeMap::new();
+
+        for card in get_cards_available() {
+            let valid = !card.id().is_empty() && is_id(&card_key(&card.id()));
+            assert!(valid, "{} has no valid id", card.title());
+            // Copies of a card share its id
+            let first = cards.entry(card.id()).or_insert_with(|| card.clone());
+            assert_eq!(*first, card, "{} is used by two cards", card.id());
+        }
+    }
+}
@@ -7,7 +7,7 @@ use leptos_meta::*;
 
 use super::page::Newsletter;
 use crate::web::common::{Button, ButtonLinkSecond};
-use crate::web::plausible::components::expect_plausible_context;
+use leptos_plausible::components::expect_plausible_context;
 
 // Shows a random card
 #[component]
@@ -203,7 +203,7 @@ fn Criminals() -> impl IntoView {
       <FlipCard image="card-criminals" class="animate-fade-slide-in-right"/>
       <div class="lg:max-w-lg animate-fade-slide-in-left">
         <p class="mt-2 text-3xl text-center text-black uppercase">
-          {move || tr!("showcase-antitrust")}
+          {move || tr!("showcase-criminals")}
         </p>
         <p class="mt-6 text-xl text-center text-gray-600">
           {move || tr!("showcase-criminals.description")}
@@ -6,5 +6,5 @@ use leptos::*;
 use leptos_router::*;
 
 pub use self::card_showcase::Showcase;
-pub use self::page::HomePage;
+pub use self::page::{HomePage, SIGNUP_EVENT};
 pub use self::rules::RulePage;
@@ -1,25 +1,29 @@
 use chrono::prelude::*;
 use leptos::ev::{keydown, load, visibilitychange};
-use std::fs::OpenOptions;
-use std::io::prelude::*;
 use std::time::Duration;
 use web_sys::Event;
 
 use leptos::*;
 use leptos_animation::*;
+use leptos_fluent::{expect_i18n, move_tr, tr};
 use leptos_meta::*;
 use leptos_router::ActionForm;
 use leptos_use::*;
 
 use crate::web::common::{ButtonLink, ButtonLinkSecond};
-use crate::web::plausible::components::{
-    track_active_elements, EndPage, PageView, TrackElement, A,
+use crate::web::lobby::CreateGame;
+use leptos_plausible::components::{
+    expect_plausible_context, track_active_elements, EndPage, PageView, A,
 };
 
 use leptos::html::{Div, Input};
 use leptos::logging::{debug_warn, log};
 
-use crate::web::plausible::experiments::{use_experiment, Experiment, ExperimentView, Variant};
+use leptos_plausible::engagement::{track_form_funnel, Engagement, ScrollDepth};
+use leptos_plausible::experiments::{Experiment, ExperimentView, Variant};
+
+/// Plausible event of the visitors that signed up to the newsletter
+pub const SIGNUP_EVENT: &str = "Newsletter Signup";
 
 /// Renders the home page of the app
 #[allow(clippy::module_name_repetitions)]
@@ -39,21 +43,26 @@ pub fn HomePage() -> impl IntoView {
       // <LogoCloud />
       // <WordCloud/>
       <Newsletter/>
+      <ScrollDepth/>
+      <Engagement/>
     }
 }
 
 #[server(EmailAlert, "/api")]
 pub async fn add_email_alert(email: String) -> Result<String, ServerFnError> {
-    let mut file = OpenOptions::new()
-        .append(true)
-        .create(true)
-        .open("data/crowdfunding-emails.txt")
-        .unwrap();
-
-    let date_as_string = Utc::now().to_string();
-    if let Err(e) = writeln!(file, "{},{}", date_as_string, email) {
-        eprintln!("Couldn't write to file: {}", e);
-    }
+    use axum::extract::ConnectInfo;
+    use axum::http::HeaderMap;
+    use leptos_plausible::{ssr::client_ip, PlausibleSettings};
+    use std::net::SocketAddr;
+
+    // Limited per visitor, not per reverse proxy
+    let ConnectInfo(peer) = leptos_axum::extract::<ConnectInfo<SocketAddr>>().await?;
+    let headers = leptos_axum::extract::<HeaderMap>().await?;
+    let trusted = use_context::<PlausibleSettings>()
+        .map(|s| s.trusted_proxies)
+        .unwrap_or_default();
+    let ip = client_ip(&headers, peer.ip(), &trusted);
+    crate::web::subscriptions::ssr::subscriptions()?.subscribe(&email, ip, Utc::now())?;
     Ok("OK".into())
 }
 
@@ -107,6 +116,7 @@ fn Hand() -> impl IntoView {
 #[component]
 fn Hero() -> impl IntoView {
     let add_email = create_server_action::<EmailAlert>();
+    let create_game = create_server_action::<CreateGame>();
     let value = add_email.value();
     let is_ok = move || value().is_some_and(|v| v == Ok("OK".into()));
     let has_error = move || value.with(|val| matches!(val, Some(Err(_))));
@@ -148,7 +158,6 @@ fn Hero() -> impl IntoView {
         }
     });
 
-    let variant = use_experiment().expect("Error seting up experiment");
     view! {
       <div class="overflow-hidden content-center pt-4 bg-white sm:py-8 lg:py-60">
         <div class="px-6 mx-auto max-w-7xl lg:px-8">
@@ -156,14 +165,7 @@ fn Hero() -> impl IntoView {
           <div class="grid grid-cols-1 place-items-center mx-auto max-w-2xl md:gap-y-10 lg:grid-cols-2 lg:gap-y-20 lg:mx-0 lg:max-w-none">
             <div class="z-50 lg:pr-8">
               <div class="lg:max-w-lg">
-                <TrackElement name="ExperimentView"/>
-                <Show
-                  when=move || { variant.0().map_or_else(|| false, |v| v.selected == 1) }
-
-                  fallback=move || view! { <HeroText/> }
-                >
-                  <HeroTextB/>
-                </Show>
+                <ExperimentView name="HeroText" variants=vec![HeroText.into(), HeroTextB.into()]/>
                 <div class="mt-2 text-lg">
                   // <ActionForm action=add_email>
                   // <div class="grid grid-rows-2 gap-4 px-10 mt-6">
@@ -207,10 +209,20 @@ fn Hero() -> impl IntoView {
                   // </div>
                   // </ActionForm>
                   <div class="px-10 mt-6">
-                    <ButtonLink title="Explore the cards" href="/cards"/>
+                    <ButtonLink title=move || tr!("hero-explore-cards") href="/cards"/>
+                  </div>
+                  <div class="px-10 mt-6">
+                    <ButtonLinkSecond title=move || tr!("hero-how-to-plai") href="/how-to-plai"/>
                   </div>
                   <div class="px-10 mt-6">
-                    <ButtonLinkSecond title="How to Plai" href="/how-to-plai"/>
+                    <ActionForm action=create_game>
+                      <button
+                        type="submit"
+                        class="flex justify-center py-1.5 px-3 w-full text-sm font-semibold leading-6 text-white bg-emerald-600 rounded-md shadow-sm hover:bg-emerald-500"
+                      >
+                        {move_tr!("hero-create-game")}
+                      </button>
+                    </ActionForm>
                   </div>
                 </div>
               </div>
@@ -240,26 +252,59 @@ fn Hero() -> impl IntoView {
     }
 }
 
+/// Stands for the highlighted part of a message until it is rendered
+const MARK: &str = "\u{E000}";
+
+/// `text`, translated with [`MARK`] as its `mark` variable, with `mark` in
+/// its place highlighted
+#[component]
+fn Marked(#[prop(into)] text: TextProp, #[prop(into)] mark: TextProp) -> impl IntoView {
+    move || {
+        let text = text.get();
+        match text.split_once(MARK) {
+            Some((before, after)) => view! {
+              {before.to_string()}
+              <mark>{mark.get().to_string()}</mark>
+              {after.to_string()}
+            }
+            .into_view(),
+            None => text.to_string().into_view(),
+        }
+    }
+}
+
 #[component]
 fn HeroText() -> impl IntoView {
     view! {
-      <p class="text-lg leading-8 text-gray-600">Seize control of <mark>your career</mark></p>
+      <p class="text-lg leading-8 text-gray-600">
+        <Marked
+          text=move || tr!("hero-career", { "mark" => MARK })
+          mark=move || tr!("hero-career.mark")
+        />
+      </p>
       <p class="mt-2 text-3xl font-bold tracking-tight text-center text-transparent text-gray-900 bg-clip-text bg-gradient-to-r from-orange-700 via-blue-500 to-green-400 sm:text-4xl bg-300% animate-gradient">
-        PAVE YOUR OWN PATH*
+        {move_tr!("hero-path")}
       </p>
 
-      <p class="mt-10 text-lg leading-8 text-gray-600">Guide your <mark>tech startup</mark>to</p>
+      <p class="mt-10 text-lg leading-8 text-gray-600">
+        <Marked
+          text=move || tr!("hero-startup", { "mark" => MARK })
+          mark=move || tr!("hero-startup.mark")
+        />
+      </p>
       <div class="bg-clip-text bg-repeat bg-texture-paper bg-parallax">
         <p class="flex mt-2 text-5xl font-black tracking-tight text-center text-transparent bg-clip-text sm:text-6xl">
-          MARKET DOMINANCE
+          {move_tr!("hero-dominance")}
         </p>
       </div>
 
       <div class="mt-8 sm:mt-20">
-        <p class="text-lg leading-8 text-gray-600">PLAI, the board game for tech practitioners</p>
+        <p class="text-lg leading-8 text-gray-600">{move_tr!("hero-board-game")}</p>
         <p class="text-lg leading-8 text-gray-600">
-          <mark>Next sprint</mark>
-          on Kickstarter
+          <Marked
+            text=move || tr!("hero-sprint", { "mark" => MARK })
+            mark=move || tr!("hero-sprint.mark")
+          />
         </p>
 
       </div>
@@ -271,10 +316,10 @@ fn HeroTextB() -> impl IntoView {
     view! {
       <div class="container flex flex-col items-center px-6 mx-auto">
         <h1 class="mt-2 text-4xl font-bold tracking-tight text-center text-transparent text-gray-900 bg-clip-text bg-gradient-to-r from-orange-700 via-blue-500 to-green-400 md:text-6xl bg-300% animate-gradient">
-          PLAI: The Ultimate Board Game for Tech Enthusiasts
+          {move_tr!("hero-b-title")}
         </h1>
         <p class="mb-6 text-xl text-center md:text-2xl">
-          Navigate the Tech Startup World and Dominate the Market
+          {move_tr!("hero-b-subtitle")}
         </p>
 
       </div>
@@ -723,9 +768,18 @@ fn LogoCloud() -> impl IntoView {
 pub fn Newsletter() -> impl IntoView {
     let add_email = create_server_action::<EmailAlert>();
     let value = add_email.value();
+    let plausible = expect_plausible_context();
+    let submitted = track_form_funnel("newsletter", &["email-form-bottom"]);
+    create_effect(move |_| {
+        if value.with(|v| matches!(v, Some(Ok(_)))) {
+            plausible.event(SIGNUP_EVENT).send_local();
+            submitted();
+        }
+    });
 
     let UseIntervalReturn { counter, .. } = use_interval(1500);
-    let email_adjectives = [
+    // Attributes of `newsletter-email` shown in turns
+    let email_kinds = [
         "gdpr-friendly",
         "best",
         "msn",
@@ -734,27 +788,28 @@ pub fn Newsletter() -> impl IntoView {
         "work",
         "tinet",
         "active",
-        "friends'",
+        "friends",
         "spam",
-        "AOL",
+        "aol",
         "test",
         "professional",
         "apple",
         "hotmail",
     ];
+    let i18n = expect_i18n();
     view! {
       <div class="overflow-hidden relative py-16 bg-gray-900 sm:py-24 lg:py-32 isolate">
         <div class="px-6 mx-auto max-w-7xl lg:px-8">
           <div class="grid grid-cols-1 gap-x-8 gap-y-16 mx-auto max-w-2xl lg:grid-cols-2 lg:max-w-none">
             <div class="max-w-xl lg:max-w-lg">
-              <h2 class="text-3xl font-bold tracking-tight text-white sm:text-4xl">Want the game?</h2>
-              <p class="mt-4 text-lg leading-8 text-gray-300">
-                We want to "release🚀" PLAI to production, but we need a big tech fam to print them.
-              </p>
+              <h2 class="text-3xl font-bold tracking-tight text-white sm:text-4xl">
+                {move_tr!("newsletter-title")}
+              </h2>
+              <p class="mt-4 text-lg leading-8 text-gray-300">{move_tr!("newsletter-pitch")}</p>
               <ActionForm action=add_email>
                 <div class="flex gap-x-4 mt-6 max-w-md">
                   <label for="email-address" class="sr-only">
-                    Email address
+                    {move_tr!("newsletter-email-label")}
                   </label>
                   <input
                     data-id="plausible-email-form-bottom"
@@ -764,25 +819,32 @@ pub fn Newsletter() -> impl IntoView {
                     autocomplete="email"
                     required
                     class="flex-auto py-2 px-3.5 min-w-0 text-white rounded-md border-0 ring-1 ring-inset shadow-sm sm:text-sm sm:leading-6 focus:ring-2 focus:ring-inset focus:ring-green-500 bg-white/5 ring-white/10"
-                   placeholder=move || {
-                   format!(
-                   "Enter your {} email",
-                   email_adjectives[(counter()) as usize
-                   % email_adjectives.len()],
-                   )
-                   }
+                    placeholder=move || {
+                        let kind = email_kinds[counter() as usize % email_kinds.len()];
+                        let kind = i18n.tr(&format!("newsletter-email.{kind}"));
+                        tr!("newsletter-email", { "kind" => kind })
+                    }
                   />
                   <button
                     type="submit"
                     class="flex-none py-2.5 px-3.5 text-sm font-semibold text-white bg-green-700 rounded-md shadow-sm hover:bg-green-600 plausible-event-name=Subscribe+Bottom focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-green-500"
                   >
-                    "I want them! "
+                    {move_tr!("newsletter-submit")}
+                    " "
                     {move || {
                         if value().is_some_and(|v| v == Ok("OK".into())) { "✔️" } else { "" }
                     }}
 
                   </button>
                 </div>
+                <p class="mt-3 text-sm text-gray-300">
+                  {move || match value() {
+                      Some(Ok(_)) => tr!("newsletter-confirm"),
+                      Some(Err(ServerFnError::ServerError(msg))) => msg,
+                      Some(Err(_)) => tr!("newsletter-error"),
+                      None => String::new(),
+                  }}
+                </p>
               </ActionForm>
             </div>
             <dl class="grid grid-cols-1 gap-x-8 gap-y-10 sm:grid-cols-2 lg:pt-2">
@@ -806,9 +868,9 @@ pub fn Newsletter() -> impl IntoView {
 
                 </div>
                 <div>
-                <dt class="font-semibold text-white">"Don't miss it"</dt>
+                <dt class="font-semibold text-white">{move_tr!("newsletter-dont-miss")}</dt>
                 <dd class="mt-2 leading-7 text-gray-400">
-                    "It'll be the breakthrough of the nanosecond. Stay ahead of our moonshot moment."
+                  {move_tr!("newsletter-dont-miss.description")}
                 </dd>
                 </div>
               </div>
@@ -830,9 +892,10 @@ pub fn Newsletter() -> impl IntoView {
                   </svg>
                 </div>
                 <div>
-                  <dt class="font-semibold text-white">No spam</dt>
+                  <dt class="font-semibold text-white">{move_tr!("newsletter-no-spam")}</dt>
                   <dd class="mt-2 leading-7 text-gray-400">
-                  "I'll send just 2 personal emails 🧔📧. For spam and to see how cool the game is, follow our instagram at "
+                  {move_tr!("newsletter-no-spam.description")}
+                  " "
                   <a
                     href="https://www.instagram.com/plai_cards/"
                     target="_blank"
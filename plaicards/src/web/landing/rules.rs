@@ -1,14 +1,13 @@
 use leptos::*;
 use leptos_animation::*;
+use leptos_fluent::{move_tr, tr};
 use leptos_meta::*;
 use leptos_router::ActionForm;
 use leptos_use::*;
 
 use crate::web::common::*;
 use crate::web::landing::page::Newsletter;
-use crate::web::plausible::components::{
-    track_active_elements, EndPage, PageView, TrackElement, A,
-};
+use leptos_plausible::components::{track_active_elements, EndPage, PageView, TrackElement, A};
 
 /// Renders the home page of the app
 #[allow(clippy::module_name_repetitions)]
@@ -18,7 +17,7 @@ pub fn RulePage() -> impl IntoView {
     track_active_elements();
 
     view! {
-      <Title text="How to PLAI"/>
+      <Title text=move || tr!("rules-title")/>
 
       <PageTitle />
 
@@ -38,11 +37,11 @@ fn PageTitle() -> impl IntoView {
     view! {
       <div class="mx-auto px-4 sm:px-6 md:max-w-2xl md:px-4 lg:max-w-4xl lg:px-12 text-lg tracking-tight text-slate-700">
         <h1 class="text-8xl uppercase font-bold text-black">PLAI</h1>
-        <h2 class="text-4xl uppercase font-bold text-black">THE TECH "WORKER'S" CARD GAME</h2>
+        <h2 class="text-4xl uppercase font-bold text-black">{move_tr!("rules-subtitle")}</h2>
 
-        <p class="mt-4 text-orange-400 font-semibold">2 to 5 players</p>
-        <p class="mt-1 text-orange-400 font-semibold">Ages: 19+</p>
-        <p class="mt-1 text-orange-400 font-semibold">Playtime: 7-59 min</p>
+        <p class="mt-4 text-orange-400 font-semibold">{move_tr!("rules-players")}</p>
+        <p class="mt-1 text-orange-400 font-semibold">{move_tr!("rules-ages")}</p>
+        <p class="mt-1 text-orange-400 font-semibold">{move_tr!("rules-playtime")}</p>
       </div>
     }
 }
@@ -53,19 +52,13 @@ fn Objective() -> impl IntoView {
     <section id="objective" aria-label="Objective" class="pt-10 md:pt-12 lg:py-16">
       <div class="mx-auto px-4 sm:px-6 md:max-w-2xl md:px-4 lg:max-w-4xl lg:px-12 text-lg tracking-tight text-slate-700">
       <div class="lg:pl-4">
-          <h1 class="uppercase text-3xl font-bold tracking-tight text-gray-900 sm:text-4xl">"GAME'S OBJECTIVE"</h1>
+          <h1 class="uppercase text-3xl font-bold tracking-tight text-gray-900 sm:text-4xl">{move_tr!("rules-objective")}</h1>
           <p class="mt-6 mx-8 text-xl leading-8 text-gray-700">
-            "Be the only startup alive and leave the others out of
-business, or destroy all startups with your cooperative
-Open Source Project."
+            {move_tr!("rules-objective.summary")}
           </p>
       </div>
-      <p class="mt-4">"Lead your AI startup to dominate the competition, aiming to
-become the ultimate greenwashing monopoly in this ever-
-changing landscape. "<b>"The number of cards in your hand are
-your company’s assets"</b>", they will allow you to mess with the
-other startups. Do not let the number reach zero, but beware
-if you have too many."</p>
+      <p class="mt-4">{move_tr!("rules-objective.lead")} " " <b>{move_tr!("rules-objective.assets")}</b>", "
+        {move_tr!("rules-objective.assets-use")}</p>
       </div>
     </section>
 
@@ -78,34 +71,32 @@ fn Start() -> impl IntoView {
     <section id="objective" aria-label="Objective" class="pt-4 md:pt-8 lg:py-10">
       <div class="mx-auto px-4 sm:px-6 md:max-w-2xl md:px-4 lg:max-w-4xl lg:px-12 text-lg tracking-tight text-slate-700">
       <div class="lg:pl-4">
-          <h1 class="uppercase text-3xl font-bold tracking-tight text-gray-900 sm:text-4xl">"Starting the game"</h1>
+          <h1 class="uppercase text-3xl font-bold tracking-tight text-gray-900 sm:text-4xl">{move_tr!("rules-start")}</h1>
       </div>
 
-          <p class="mt-4">"The bottom left of some cards have roman numerals, depending on the number of players pick the following:"</p>
+          <p class="mt-4">{move_tr!("rules-start.numerals")}</p>
 
     <div class="bg-white py-8 sm:py-12">
       <div class="mx-auto max-w-7xl px-6 lg:px-8">
         <dl class="grid grid-cols-1 gap-x-8 gap-y-16 text-center lg:grid-cols-3">
           <div class="mx-auto flex max-w-xs flex-col gap-y-4">
-            <dt class="text-base leading-7 text-gray-600">2 <i>plaiers</i></dt>
-            <dd class="order-first text-xl font-semibold tracking-tight text-gray-900 sm:text-2xl">Numeral I</dd>
+            <dt class="text-base leading-7 text-gray-600">{move_tr!("rules-start.two-players")}</dt>
+            <dd class="order-first text-xl font-semibold tracking-tight text-gray-900 sm:text-2xl">{move_tr!("rules-start.two-players-cards")}</dd>
           </div>
           <div class="mx-auto flex max-w-xs flex-col gap-y-4">
-            <dt class="text-base leading-7 text-gray-600">3-4 <i>plaiers</i></dt>
-            <dd class="order-first text-xl font-semibold tracking-tight text-gray-900 sm:text-2xl">Numerals I & II</dd>
+            <dt class="text-base leading-7 text-gray-600">{move_tr!("rules-start.four-players")}</dt>
+            <dd class="order-first text-xl font-semibold tracking-tight text-gray-900 sm:text-2xl">{move_tr!("rules-start.four-players-cards")}</dd>
           </div>
           <div class="mx-auto flex max-w-xs flex-col gap-y-4">
-            <dt class="text-base leading-7 text-gray-600">5 <i>plaiers</i></dt>
-            <dd class="order-first text-xl font-semibold tracking-tight text-gray-900 sm:text-2xl">All cards</dd>
+            <dt class="text-base leading-7 text-gray-600">{move_tr!("rules-start.five-players")}</dt>
+            <dd class="order-first text-xl font-semibold tracking-tight text-gray-900 sm:text-2xl">{move_tr!("rules-start.five-players-cards")}</dd>
           </div>
         </dl>
       </div>
     </div>
 
 
-    <p>"Remove Market Event cards from the deck. Shuffle, cut and
-        deal 6 cards to each player. Add the Market Event cards back
-        into the deck, shuffle and put the deck face down on the table."</p>
+    <p>{move_tr!("rules-start.deal")}</p>
 
       </div>
     </section>
@@ -119,24 +110,22 @@ fn Turn() -> impl IntoView {
     <section id="turn" aria-label="Player's turn" class="pt-4 md:pt-8 lg:py-10">
       <div class="mx-auto px-4 sm:px-6 md:max-w-2xl md:px-4 lg:max-w-4xl lg:px-12 text-lg tracking-tight text-slate-700 grid gap-y-4">
       <div class="lg:pl-4">
-          <h1 class="uppercase text-3xl font-bold tracking-tight text-gray-900 sm:text-4xl">"Your turn"</h1>
+          <h1 class="uppercase text-3xl font-bold tracking-tight text-gray-900 sm:text-4xl">{move_tr!("rules-turn")}</h1>
       </div>
 
 
       <p class="mt-4">
-        "During your turn you can do " <b>one</b> of the following actions:
+        {move_tr!("rules-turn.actions")}
       </p>
 
       <ul class="pl-8 list-none">
-        <li><ListDecoration/> Do a funding</li>
-        <li><ListDecoration/> Pl<i>ai</i> a special card</li>
-        <li><ListDecoration/> Fight</li>
+        <li><ListDecoration/> {move_tr!("rules-turn.funding")}</li>
+        <li><ListDecoration/> {move_tr!("rules-turn.special")}</li>
+        <li><ListDecoration/> {move_tr!("rules-turn.fight")}</li>
       </ul>
 
       <p class="mt-6">
-      "By the end of turn, if any startup has run out of assets their
-company is declared bankrupt and can't continue on the market.
-Any eliminated player becomes an Open Source Maintainer."
+        {move_tr!("rules-turn.bankrupt")}
       </p>
 
       <Funding />
@@ -150,159 +139,149 @@ Any eliminated player becomes an Open Source Maintainer."
 #[component]
 fn Funding() -> impl IntoView {
     view! {
-          <H3 txt="Funding"/>
+      <H3 txt=move || tr!("rules-funding")/>
 
-          <ul class="mt-6 px-12 space-y-4">
-            <li class="flex">
-            <CardWireframe />
+      <ul class="px-12 mt-6 space-y-4">
+        <li class="flex">
+          <CardWireframe/>
           <span class="ml-8">
-          <p class="font-semibold">Family Funding</p>
-          <p>Draw one card from the deck.</p>
-          <p>Nobody can attack you</p>
+            <p class="font-semibold">{move_tr!("rules-funding.family")}</p>
+            <p>{move_tr!("rules-funding.family-draw")}</p>
+            <p>{move_tr!("rules-funding.family-safe")}</p>
           </span>
-          </li>
-            <li class="flex">
-            <TwoCardWireframe />
+        </li>
+        <li class="flex">
+          <TwoCardWireframe/>
           <span class="ml-8">
-          <p class="font-semibold">Regional pitch</p>
-          <p>Draw two cards from the deck.</p>
-          <p>The next player can attack you</p>
+            <p class="font-semibold">{move_tr!("rules-funding.regional")}</p>
+            <p>{move_tr!("rules-funding.regional-draw")}</p>
+            <p>{move_tr!("rules-funding.regional-attack")}</p>
           </span>
-          </li>
-
-            <li class="flex">
-
-            <ThreeCardWireframe/>
+        </li>
+        <li class="flex">
+          <ThreeCardWireframe/>
           <span class="ml-8">
-          <p class="font-semibold">Venture capital</p>
-          <p>Draw three cards.</p>
-          <p>Anyone can attack you</p>
-
+            <p class="font-semibold">{move_tr!("rules-funding.vc")}</p>
+            <p>{move_tr!("rules-funding.vc-draw")}</p>
+            <p>{move_tr!("rules-funding.vc-attack")}</p>
           </span>
-          </li>
-          </ul>
+        </li>
+      </ul>
 
-          <p class="mt-6">
-            During a Regional pitch or a Venture capital, do not look at
-    the drawn cards right away, leave them face down in case
-    another startup wants to attack you.
-          </p>
-          <p class="mt-4">
-            If no one attacks, keep the assets raised during the funding.
-          </p>
-        }
+      <p class="mt-6">{move_tr!("rules-funding.face-down")}</p>
+      <p class="mt-4">{move_tr!("rules-funding.keep")}</p>
+    }
 }
 
 #[component]
 fn Fight() -> impl IntoView {
     view! {
-          <H3 txt="Fight"/>
-          <p>Steal assets from other startups.</p>
-
-              <ul class="pl-8 pt-4 list-none">
-                <li><ListDecoration/><b>Hostile Takeover</b>: if you have 9 or more cards, you
-    can challenge any player at your turn.</li>
-                <li><ListDecoration/><b>During a Funding</b>: you or another startup does a
-    Regional pitch or a Venture capital</li>
-              </ul>
-
-            <div class="bg-gray tracking-tight p-1 pl-4 font-bold text-black">
-              You can play up to four battle cards face down.
-            </div>
+      <H3 txt=move || tr!("rules-fight")/>
+      <p>{move_tr!("rules-fight.steal")}</p>
+
+      <ul class="pt-4 pl-8 list-none">
+        <li>
+          <ListDecoration/>
+          <b>{move_tr!("rules-fight.takeover")}</b>
+          ": "
+          {move_tr!("rules-fight.takeover-when")}
+        </li>
+        <li>
+          <ListDecoration/>
+          <b>{move_tr!("rules-fight.during-funding")}</b>
+          ": "
+          {move_tr!("rules-fight.during-funding-when")}
+        </li>
+      </ul>
 
-            <p>Use Adversaries if you are attacking and Use Cases if you
-    are on the defensive. In both cases you can add Buzzwords to
-    increase the number of your attack or defense.</p>
+      <div class="p-1 pl-4 font-bold tracking-tight text-black bg-gray">
+        {move_tr!("rules-fight.four-cards")}
+      </div>
 
-          <p>Specific special cards can be played in the middle of battle,
-            such as cards that negate other "card's" effects. These Special
-            cards do not need to be in the four picked cards at the
-            beginning of the battle.
-          </p>
+      <p>{move_tr!("rules-fight.cards")}</p>
+      <p>{move_tr!("rules-fight.specials")}</p>
+      <p>{move_tr!("rules-fight.bluff")}</p>
 
-          <p>You can bluff and only attack or defend yourself with
-          Buzzword cards. However, if the other startup has Use Cases
-          or Adversary cards you will have lost the battle.</p>
+      <div class="p-1 pl-4 font-bold tracking-tight text-black bg-gray">
+        {move_tr!("rules-fight.bribe")}
+      </div>
 
-            <div class="bg-gray tracking-tight p-1 pl-4 font-bold text-black">
-             You can always bribe other players for help.
-            </div>
+      <H4>{move_tr!("rules-fight.order")}</H4>
 
-          <H4>Order of events during a fight</H4>
-
-          <ul class="ml-8 grid gap-y-6 list-decimal">
-            <li>The attacker(s) chooses their cards to play and places
-    them face down. The defender decides if they want to
-    defend, and if so, put their cards face down.</li>
-            <li>Defense cards are revealed and their effects are applied. Then, do the same for the "attacker's" cards.</li>
-            <li>Add up the stregnth of each played card. Whoever
-    has more points wins, or in the event of a draw, the
-    defense is the winner.</li>
-            <li>The winner takes the drawn cards if it is a Funding, or
-    the entire losing hand if it is a Hostile Takeover.</li>
-            <li>Discard all the played cards.</li>
-          </ul>
-        }
+      <ul class="grid gap-y-6 ml-8 list-decimal">
+        <li>{move_tr!("rules-fight.order-cards")}</li>
+        <li>{move_tr!("rules-fight.order-reveal")}</li>
+        <li>{move_tr!("rules-fight.order-strength")}</li>
+        <li>{move_tr!("rules-fight.order-winner")}</li>
+        <li>{move_tr!("rules-fight.order-discard")}</li>
+      </ul>
+    }
 }
 
 #[component]
 fn OpenSource() -> impl IntoView {
     view! {
-          <H3 txt="Open Source Project"/>
-
-          <p class="mt-6">
-            Your startup is bankrupt, but its not your end. You morph
-    into an Open Source Maintainer and can do one of the
-    following actions during your next turns:
-          </p>
-
-          <ul class="pl-8 pt-4 list-none">
-            <li><ListDecoration/> Family funding</li>
-            <li><ListDecoration/> Pl<i>ai</i> a special card</li>
-            <li><ListDecoration/> Help fight other startups</li>
-          </ul>
-          <p class="mt-6">
-          Open Source Maintainers must act in collaboration and
-    talk in public. They can only have a maximum of 6 assets
-    between them and any additional ones must be discarded.
-          </p>
-        }
+      <H3 txt=move || tr!("rules-open-source")/>
+
+      <p class="mt-6">{move_tr!("rules-open-source.bankrupt")}</p>
+
+      <ul class="pt-4 pl-8 list-none">
+        <li>
+          <ListDecoration/>
+          " "
+          {move_tr!("rules-open-source.family")}
+        </li>
+        <li>
+          <ListDecoration/>
+          " "
+          {move_tr!("rules-open-source.special")}
+        </li>
+        <li>
+          <ListDecoration/>
+          " "
+          {move_tr!("rules-open-source.help")}
+        </li>
+      </ul>
+      <p class="mt-6">{move_tr!("rules-open-source.together")}</p>
+    }
 }
 
 #[component]
 fn EndOfGame() -> impl IntoView {
     view! {
-        <section id="end-game" aria-label="End of the game" class="pt-10 md:pt-12 lg:py-16 pb-10">
-          <div class="mx-auto px-4 sm:px-6 md:max-w-2xl md:px-4 lg:max-w-4xl lg:px-12 text-lg tracking-tight text-slate-700">
+      <section id="end-game" aria-label="End of the game" class="pt-10 pb-10 md:pt-12 lg:py-16">
+        <div class="px-4 mx-auto text-lg tracking-tight sm:px-6 md:px-4 md:max-w-2xl lg:px-12 lg:max-w-4xl text-slate-700">
           <div class="lg:pl-4">
-              <h1 class="uppercase text-3xl font-bold tracking-tight text-gray-900 sm:text-4xl">End of game</h1>
+            <h1 class="text-3xl font-bold tracking-tight text-gray-900 uppercase sm:text-4xl">
+              {move_tr!("rules-end")}
+            </h1>
           </div>
 
+          <div class="grid grid-cols-1 gap-y-2 gap-x-4 mt-6 lg:grid-cols-3">
+            <div>
+              <H4>{move_tr!("rules-end.trough")}</H4>
+            </div>
+            <div class="lg:col-span-2">{move_tr!("rules-end.trough-when")}</div>
+            <div class="h-1 col-span-full">
+              <hr class="h-px border-0 bg-orange"/>
+            </div>
 
+            <div>
+              <H4>{move_tr!("rules-end.monopoly")}</H4>
+            </div>
+            <div class="lg:col-span-2">{move_tr!("rules-end.monopoly-when")}</div>
+            <div class="h-1 col-span-full">
+              <hr class="h-px border-0 bg-orange"/>
+            </div>
 
-          <div class="mt-6 grid lg:grid-cols-3  grid-cols-1 gap-y-2 gap-x-4">
-
-              <div><H4>Trough of Disillusionment</H4></div>
-              <div class="lg:col-span-2">There are no more cards available in the draw pile. No one will remember you.</div>
-              <div class="h-1 col-span-full"><hr class="h-px bg-orange border-0"/></div>
-
-              <div><H4>Monopoly</H4></div>
-              <div class="lg:col-span-2">When one startup is left, it must resist
-    a coordinated attack from the Open
-    Source maintainers. If the company wins,
-    congrats, you are now a corporation</div>
-
-              <div class="h-1 col-span-full"><hr class="h-px bg-orange border-0"/></div>
-              <div><H4>Teamwork Makes the Dreamwork</H4></div>
-              <div class="lg:col-span-2">The Open Source Project won the final battle against the only startup left.
-    "It's" a collective win for all (except for the losing player).</div>
-
-           </div>
-
+            <div>
+              <H4>{move_tr!("rules-end.teamwork")}</H4>
+            </div>
+            <div class="lg:col-span-2">{move_tr!("rules-end.teamwork-when")}</div>
           </div>
-        </section>
-
-        }
+        </div>
+      </section>
+    }
 }
 
 #[component]
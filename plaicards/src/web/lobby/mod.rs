@@ -8,14 +8,13 @@ use leptos_router::*;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
-use std::sync::Mutex;
 use uuid::Uuid;
 
 use super::Ctx;
 use super::Result as Res;
 
 mod view;
-pub use self::view::Lobby;
+pub use self::view::{JoinLobby, Lobby};
 
 cfg_if! {
     if #[cfg(feature = "ssr")] {
@@ -27,12 +26,14 @@ cfg_if! {
 
 #[must_use]
 pub fn from_url_uuid(url_id: &str) -> Uuid {
-    let res = Uuid::try_from(
-        BASE64URL_NOPAD
-            .decode(url_id.as_bytes())
-            .unwrap_or_default(),
-    );
-    res.unwrap_or_else(|_| Uuid::new_v4())
+    parse_url_uuid(url_id).unwrap_or_else(Uuid::new_v4)
+}
+
+/// Decodes an id from a URL. `None` if it's not a valid one.
+#[must_use]
+pub fn parse_url_uuid(url_id: &str) -> Option<Uuid> {
+    let bytes = BASE64URL_NOPAD.decode(url_id.as_bytes()).ok()?;
+    Uuid::try_from(bytes).ok()
 }
 
 fn to_url_uuid(id: Uuid) -> String {
@@ -68,10 +69,12 @@ pub async fn add_player(
         Err(_) => lobbys.create().await?,
     };
 
-    let p = lobby.update_player(Player {
-        id: player_uuid,
-        name,
-    })?;
+    let p = lobby
+        .update_player(Player {
+            id: player_uuid,
+            name,
+        })
+        .await?;
 
     //Redirect to correct URI
     let redirect = format!("/lobby/{}/{}", to_url_uuid(lobby.id), to_url_uuid(p.id));
@@ -82,6 +85,21 @@ pub async fn add_player(
     Ok(())
 }
 
+/// Opens a new lobby and sends its creator there
+#[server(CreateGame, "/api/lobby")]
+pub async fn create_game() -> Result<(), ServerFnError> {
+    use self::ssr::*;
+    let lobby = lobbys()?.create().await?;
+
+    let redirect = format!(
+        "/lobby/{}/{}",
+        to_url_uuid(lobby.id),
+        to_url_uuid(Uuid::new_v4())
+    );
+    leptos_axum::redirect(&redirect);
+    Ok(())
+}
+
 #[server(GetPlayers, "/api/lobby")]
 pub async fn get_players(lobby_id: String) -> Result<Vec<Player>, ServerFnError> {
     use self::ssr::*;
@@ -93,14 +111,14 @@ pub async fn get_players(lobby_id: String) -> Result<Vec<Player>, ServerFnError>
         Err(_) => lobbys.create().await?,
     };
 
-    let players = lobby.players.lock().unwrap().clone();
+    let players = lobby.players().await;
 
     Ok(players)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{from_url_uuid, to_url_uuid};
+    use super::{from_url_uuid, parse_url_uuid, to_url_uuid};
     use uuid::Uuid;
 
     #[test]
@@ -115,4 +133,12 @@ mod tests {
             "Something wrong with UUID to URL encode and decode"
         );
     }
+
+    #[test]
+    fn invalid_url_uuid_is_rejected() {
+        assert_eq!(parse_url_uuid("not-a-uuid"), None);
+        assert_eq!(parse_url_uuid(""), None);
+        // Valid base64 but not 16 bytes
+        assert_eq!(parse_url_uuid("AAAA"), None);
+    }
 }
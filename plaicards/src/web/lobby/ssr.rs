@@ -1,26 +1,30 @@
 use axum::extract::FromRef;
 use leptos::use_context;
 use leptos::ServerFnError;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::sync::Mutex;
-use tokio::sync::broadcast;
+use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use super::Ctx;
 use super::Player;
-use crate::web::Result as Res;
+use crate::web::bus::{LocalBus, RoomBus, Topic};
+use crate::web::{Error, Result as Res};
 
 pub fn lobbys() -> Result<LobbyController, ServerFnError> {
     use_context::<LobbyController>()
         .ok_or_else(|| ServerFnError::ServerError("Database missing".into()))
 }
 
+/// Messages a lobby keeps for clients that are behind, unless configured
+pub const DEFAULT_CAPACITY: usize = 10;
+
 #[derive(Clone, Debug)]
 pub struct Lobby {
     pub id: Uuid,
-    pub players: Arc<Mutex<Vec<Player>>>,
+    pub players: Arc<RwLock<Vec<Player>>>,
     // Channel to send messages to all connected clients
-    pub tx: broadcast::Sender<String>,
+    pub tx: Topic<String>,
 }
 
 impl Default for Lobby {
@@ -37,18 +41,28 @@ impl Lobby {
 
     #[must_use]
     pub fn from_id(id: Uuid) -> Self {
-        let (tx, _rx) = broadcast::channel(10);
+        Self::with_capacity(id, DEFAULT_CAPACITY)
+    }
+
+    #[must_use]
+    pub fn with_capacity(id: Uuid, capacity: usize) -> Self {
+        Self::on_bus(id, Arc::new(LocalBus::default()), capacity)
+    }
+
+    /// Lobby whose messages travel through `bus`
+    #[must_use]
+    pub fn on_bus(id: Uuid, bus: Arc<dyn RoomBus>, capacity: usize) -> Self {
         Self {
             id,
             players: Arc::default(),
-            tx,
+            tx: Topic::new(bus, format!("lobby:{id}"), capacity),
         }
     }
 }
 
 impl Lobby {
-    pub fn update_player(&self, player: Player) -> Res<Player> {
-        let mut store = self.players.lock().unwrap();
+    pub async fn update_player(&self, player: Player) -> Res<Player> {
+        let mut store = self.players.write().await;
         match store.iter_mut().find(|p| player.id == p.id) {
             Some(p) => p.name = player.name.clone(),
             None => store.push(player.clone()),
@@ -56,26 +70,62 @@ impl Lobby {
 
         Ok(player)
     }
+
+    pub async fn players(&self) -> Vec<Player> {
+        self.players.read().await.clone()
+    }
 }
 
 #[derive(FromRef, Clone, Debug)]
 pub struct LobbyController {
-    lobby_store: Arc<Mutex<Vec<Lobby>>>,
+    lobby_store: Arc<RwLock<Vec<Lobby>>>,
+    bus: Arc<dyn RoomBus>,
+    /// Capacity of the channel of new lobbies
+    capacity: usize,
+    /// No new lobbies are opened. Set while shutting down.
+    closed: Arc<AtomicBool>,
 }
 
 impl LobbyController {
     pub async fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_bus(Arc::new(LocalBus::default()), capacity)
+    }
+
+    #[must_use]
+    pub fn with_bus(bus: Arc<dyn RoomBus>, capacity: usize) -> Self {
         Self {
             lobby_store: Arc::default(),
+            bus,
+            capacity,
+            closed: Arc::default(),
+        }
+    }
+
+    /// Stops opening new lobbies. Existing ones keep working.
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    fn ensure_open(&self) -> Res<()> {
+        if self.closed.load(Ordering::SeqCst) {
+            Err(Error::Unavailable)
+        } else {
+            Ok(())
         }
     }
 }
 
 impl LobbyController {
     pub async fn create(&self) -> Res<Lobby> {
-        let lobby = Lobby::new();
+        self.ensure_open()?;
+        let lobby = Lobby::on_bus(Uuid::new_v4(), self.bus.clone(), self.capacity);
 
-        let mut store = self.lobby_store.lock().unwrap();
+        let mut store = self.lobby_store.write().await;
 
         store.push(lobby.clone());
 
@@ -86,15 +136,45 @@ impl LobbyController {
     ///
     /// If the lobby does not exists, it creates a new one.
     pub async fn get_lobby(&self, lobby_id: Uuid) -> Res<Lobby> {
-        let mut store = self.lobby_store.lock().unwrap();
+        let mut store = self.lobby_store.write().await;
 
         match store.iter().find(|l| l.id == lobby_id) {
             Some(l) => Ok(l.clone()),
             None => {
-                let lobby = Lobby::from_id(lobby_id);
+                self.ensure_open()?;
+                let lobby = Lobby::on_bus(lobby_id, self.bus.clone(), self.capacity);
                 store.push(lobby.clone());
                 Ok(lobby)
             }
         }
     }
+
+    /// Every open lobby
+    pub async fn lobbies(&self) -> Vec<Lobby> {
+        self.lobby_store.read().await.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use uuid::Uuid;
+
+    use super::LobbyController;
+    use crate::web::Error;
+
+    #[rstest]
+    async fn closed_controller_opens_no_lobbies() {
+        let lobbies = LobbyController::with_capacity(1);
+        let open = lobbies.create().await.expect("Still open");
+
+        lobbies.close();
+
+        assert_eq!(lobbies.create().await.err(), Some(Error::Unavailable));
+        assert_eq!(
+            lobbies.get_lobby(Uuid::new_v4()).await.err(),
+            Some(Error::Unavailable)
+        );
+        assert!(lobbies.get_lobby(open.id).await.is_ok());
+    }
 }
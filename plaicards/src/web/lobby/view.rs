@@ -3,6 +3,7 @@ use crate::web::common::ButtonLink;
 use codee::string::FromToStringCodec;
 use data_encoding::BASE64URL_NOPAD;
 use leptos::*;
+use leptos_fluent::{move_tr, tr};
 use leptos_meta::*;
 use leptos_router::use_params_map;
 use leptos_router::*;
@@ -15,44 +16,52 @@ use std::sync::Arc;
 use std::sync::Mutex;
 use uuid::Uuid;
 
-use super::from_url_uuid;
+use super::parse_url_uuid;
 use super::to_url_uuid;
+use crate::error_template::{error_page, AppError};
 
 use super::Player;
 
-fn from_param_uuid(params: Memo<ParamsMap>, param_name: &str) -> (String, Uuid, bool) {
-    let raw = params.with(|ps| {
+fn from_param_uuid(params: Memo<ParamsMap>, param_name: &str) -> Option<Uuid> {
+    params.with_untracked(|ps| {
         ps.get(param_name)
-            .map(std::borrow::ToOwned::to_owned)
-            .unwrap_or_default()
-    });
-
-    let uuid = from_url_uuid(&raw);
-    let url = to_url_uuid(uuid);
-    let need_reload = url != raw;
-    (url, uuid, need_reload)
+            .map(String::as_str)
+            .and_then(parse_url_uuid)
+    })
 }
+
+/// Entry point for the invited players. Gives them a seat in the lobby.
+#[component]
+#[must_use]
+pub fn JoinLobby() -> impl IntoView {
+    let params = use_params_map();
+    let Some(id) = from_param_uuid(params, "id") else {
+        return error_page(AppError::InvalidLink);
+    };
+
+    let url = format!("/lobby/{}/{}", to_url_uuid(id), to_url_uuid(Uuid::new_v4()));
+    view! { <Redirect path=url/> }.into_view()
+}
+
 #[component]
 #[must_use]
 pub fn Lobby() -> impl IntoView {
     let params = use_params_map();
 
-    // FIXME Every time we use this, we call this function
-    // and if the user_id is wrong, it returns each time a different
-    // random UUID breaking several things...
-    //
-    // We can't have this as a variable and then do the move
-    let id = move || from_param_uuid(params, "id");
-    let player_id = move || from_param_uuid(params, "player_id");
-
-    if id().2 || player_id().2 {
-        let url = move || format!("/lobby/{}/{}", id().0, player_id().0);
-        return view! { <Redirect path=url()/> }.into_view();
-    }
+    let (Some(lobby_id), Some(player_uuid)) = (
+        from_param_uuid(params, "id"),
+        from_param_uuid(params, "player_id"),
+    ) else {
+        return error_page(AppError::InvalidLink);
+    };
+    let id = move || (to_url_uuid(lobby_id), lobby_id);
+    let player_id = move || (to_url_uuid(player_uuid), player_uuid);
 
     let (players, set_players) = create_signal(vec![]);
     let (to_game, set_to_game) = create_signal(false);
     let game_url = move || format!("/plai/{}/{}", id().0, player_id().0);
+    let watch_url = move || format!("/watch/{}", id().0);
+    let invite_url = move || format!("/lobby/{}", id().0);
 
     // WebSocket
     let ws_url = format!("/lobby/{}/ws", id().1);
@@ -111,14 +120,14 @@ pub fn Lobby() -> impl IntoView {
           <div class="px-6 my-2 lg:px-8">
             <div class="flex flex-col justify-center py-2">
               <label for="name" class="block text-sm font-medium leading-6 text-gray-900">
-                Your Startup Name
+                {move_tr!("lobby-startup-name")}
               </label>
               <input
                 type="text"
                 id="name"
                 name="name"
                 class="py-1.5 pr-20 pl-7 text-gray-900 rounded-md border-0 ring-1 ring-inset ring-gray-300 sm:text-sm sm:leading-6 focus:ring-2 focus:ring-inset focus:ring-indigo-600 placeholder:text-gray-400"
-                placeholder="Try something edgy like MetaTrust"
+                placeholder=move || tr!("lobby-startup-name.placeholder")
                 on:input=move |ev| {
                     set_name(event_target_value(&ev));
                 }
@@ -132,7 +141,7 @@ pub fn Lobby() -> impl IntoView {
                 class="flex justify-center py-1.5 px-3 w-full text-sm font-semibold leading-6 text-white bg-emerald-600 rounded-md shadow-sm hover:bg-emerald-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600"
                 on:click=new_player
               >
-                "Create my startup"
+                {move_tr!("lobby-create-startup")}
               </button>
             </div>
           </div>
@@ -144,9 +153,21 @@ pub fn Lobby() -> impl IntoView {
               // when=move || { 2 <= players().len() && players().len() <= 6 }
               // fallback=|| view! { "Choose between 1 to 5 players." }
               // >
-              <Button title="👩🏾‍💼 PLAI 👨🏾‍💼".to_string() on:click=start_game/>
+              <Button title="👩🏾‍💼 PLAI 👨🏾‍💼" on:click=start_game/>
             // </Show>
             </div>
+            <div class="my-4 text-sm text-gray-600">
+              <p>{move_tr!("lobby-invite")}</p>
+              <a href=invite_url target="_blank" class="font-mono underline">
+                {invite_url}
+              </a>
+            </div>
+            <div class="my-4 text-sm text-gray-600">
+              <p>{move_tr!("lobby-watch")}</p>
+              <a href=watch_url target="_blank" class="font-mono underline">
+                {watch_url}
+              </a>
+            </div>
           </div>
         </div>
       </div>
@@ -157,7 +178,7 @@ pub fn Lobby() -> impl IntoView {
 fn PlayerList(ps: ReadSignal<Vec<Player>>) -> impl IntoView {
     view! {
       <div class="py-2">
-        <h4 class="p-4 text-xl">Current players</h4>
+        <h4 class="p-4 text-xl">{move_tr!("lobby-players")}</h4>
         <ul role="list" class="divide-y divide-gray-100">
           <For each=move || ps.get().into_iter() key=|p| p.name.clone() let:p>
             <li class="py-2">
@@ -11,12 +11,12 @@ use leptos::*;
 
 use axum::extract::ws::{WebSocket, WebSocketUpgrade};
 use futures::{
-    sink::SinkExt,
-    stream::{SplitSink, SplitStream, StreamExt},
+    sink::{Sink, SinkExt},
+    stream::{SplitSink, SplitStream, Stream, StreamExt},
 };
 use tokio::sync::mpsc::{self, Sender};
 use tracing::instrument;
-use tracing::{debug, info, trace};
+use tracing::{debug, info, trace, warn};
 use uuid::Uuid;
 
 use crate::web::board::GameController;
@@ -27,11 +27,11 @@ use super::Res;
 
 pub async fn handler(
     ws: WebSocketUpgrade,
-    Path(lobby_id): Path<Uuid>,
+    Path(id): Path<Uuid>,
     State(lc): State<LobbyController>,
     State(gc): State<GameController>,
 ) -> Response {
-    ws.on_upgrade(move |socket| handle_socket(socket, lobby_id, lc, gc))
+    ws.on_upgrade(move |socket| handle_socket(socket, id, lc, gc))
 }
 
 /// Currently this handles all the websocket connections for the lobby
@@ -39,14 +39,35 @@ pub async fn handler(
 /// It allows to send and receive information
 async fn handle_socket(socket: WebSocket, lobby_id: Uuid, lc: LobbyController, gc: GameController) {
     // By splitting, we can send and receive at the same time.
-    let (mut sender, mut receiver) = socket.split();
-    // TODO get path params for lobby_id
-    //
-    let lobby = lc.get_lobby(lobby_id).await.unwrap();
+    let (sender, receiver) = socket.split();
+    serve(sender, receiver, lobby_id, lc, gc).await;
+}
+
+/// Lobby protocol over any pair of message sink and stream.
+///
+/// Malformed frames are logged and ignored, and any error sending to the
+/// client ends the connection. Nothing here should panic the task.
+async fn serve<Si, St>(
+    mut sender: Si,
+    mut receiver: St,
+    lobby_id: Uuid,
+    lc: LobbyController,
+    gc: GameController,
+) where
+    Si: Sink<Message> + Unpin + Send + 'static,
+    St: Stream<Item = Result<Message, axum::Error>> + Unpin + Send + 'static,
+{
+    let lobby = match lc.get_lobby(lobby_id).await {
+        Ok(l) => l,
+        Err(e) => {
+            warn!("Cannot get lobby {lobby_id}: {e:?}");
+            return;
+        }
+    };
 
     let mut rx = lobby.tx.subscribe();
 
-    let _ = lobby.tx.send("A new player joined".to_string());
+    lobby.tx.send(&"A new player joined".to_string());
 
     // Spawn the first task that will receive broadcast messages and send text
     // messages over the websocket to our client.
@@ -63,24 +84,35 @@ async fn handle_socket(socket: WebSocket, lobby_id: Uuid, lc: LobbyController, g
     let lb = lobby.clone();
 
     let mut recv_task = tokio::spawn(async move {
-        while let Some(Ok(Message::Text(msg))) = receiver.next().await {
+        while let Some(Ok(message)) = receiver.next().await {
+            let Message::Text(msg) = message else {
+                continue;
+            };
             info!("Message received: {:?}", msg);
-            if msg.starts_with("REFRESH_PLAIERS") {
-                let slice = &msg[15..];
-                let p: Player = serde_json::from_str(slice).expect("malformed player");
-                let () = add_player(&lb, p).await.expect("Failed with p");
+            if let Some(slice) = msg.strip_prefix("REFRESH_PLAIERS") {
+                let p: Player = match serde_json::from_str(slice) {
+                    Ok(p) => p,
+                    Err(e) => {
+                        warn!("Malformed player {slice:?}: {e}");
+                        continue;
+                    }
+                };
+                if let Err(e) = add_player(&lb, p).await {
+                    warn!("Cannot add player: {e:?}");
+                    continue;
+                }
                 // Refresh player list to all
                 let updated_players = get_players(&lb).await;
-                let _ = tx.send(updated_players);
+                tx.send(&updated_players);
             } else if msg.starts_with("START_GAME") {
-                let players = lobby.players.lock().unwrap().clone();
+                let players = lb.players().await;
                 let players: Vec<(Uuid, String)> =
                     players.iter().map(|p| (p.id, p.name.clone())).collect();
-                gc
-                    .new_game(lobby_id, &players)
-                    .await
-                    .expect("ERROR adding new game");
-                let _ = tx.send("TO_GAME".to_string());
+                if let Err(e) = gc.new_game(lobby_id, &players).await {
+                    warn!("Cannot create game for lobby {lobby_id}: {e:?}");
+                    continue;
+                }
+                tx.send(&"TO_GAME".to_string());
             } else {
                 continue;
             }
@@ -96,18 +128,84 @@ async fn handle_socket(socket: WebSocket, lobby_id: Uuid, lc: LobbyController, g
     // Send "user left" message (similar to "joined" above).
     let msg = "Player left.".to_string();
     tracing::debug!("{msg}");
-    let _ = lobby.tx.send(msg);
+    lobby.tx.send(&msg);
 }
 
 #[instrument]
 async fn get_players(lobby: &Lobby) -> String {
-    let players = lobby.players.lock().unwrap().clone();
-    let json = serde_json::to_string(&players).unwrap();
+    let players = lobby.players().await;
+    let json = serde_json::to_string(&players).unwrap_or_else(|_| "[]".to_string());
     format!("PLAYERS{json}")
 }
 
 #[instrument]
 async fn add_player(lobby: &Lobby, p: Player) -> Res<()> {
-    let p = lobby.update_player(p)?;
+    let p = lobby.update_player(p).await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::ws::Message;
+    use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+    use futures::{SinkExt, StreamExt};
+    use rstest::rstest;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    use super::serve;
+    use crate::web::board::GameController;
+    use crate::web::lobby::ssr::LobbyController;
+
+    type Client = (
+        UnboundedSender<Result<Message, axum::Error>>,
+        UnboundedReceiver<Message>,
+    );
+
+    async fn connect(lobby_id: Uuid, lc: LobbyController) -> Client {
+        let (to_server, from_client) = unbounded();
+        let (to_client, from_server) = unbounded();
+        let gc = GameController::new().await;
+        tokio::spawn(serve(to_client, from_client, lobby_id, lc, gc));
+        (to_server, from_server)
+    }
+
+    async fn next_text(rx: &mut UnboundedReceiver<Message>) -> String {
+        loop {
+            let msg = tokio::time::timeout(Duration::from_secs(1), rx.next())
+                .await
+                .expect("Lobby did not answer")
+                .expect("Lobby closed the connection");
+            if let Message::Text(t) = msg {
+                return t;
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::garbage("}{")]
+    #[case::empty("")]
+    #[case::no_json("REFRESH_PLAIERS")]
+    #[case::bad_json("REFRESH_PLAIERS{\"id\": 3}")]
+    #[case::unknown_command("DELETE_EVERYTHING")]
+    #[tokio::test]
+    async fn malformed_frames_keep_connection(#[case] frame: &str) {
+        let lc = LobbyController::new().await;
+        let (mut tx, mut rx) = connect(Uuid::new_v4(), lc).await;
+        assert_eq!(next_text(&mut rx).await, "A new player joined");
+
+        tx.send(Ok(Message::Text(frame.to_string())))
+            .await
+            .expect("Fake socket closed");
+        tx.send(Ok(Message::Binary(vec![0xff, 0x00])))
+            .await
+            .expect("Fake socket closed");
+
+        let player = format!("{{\"id\":\"{}\",\"name\":\"p1\"}}", Uuid::new_v4());
+        tx.send(Ok(Message::Text(format!("REFRESH_PLAIERS{player}"))))
+            .await
+            .expect("Fake socket closed");
+
+        assert!(next_text(&mut rx).await.starts_with("PLAYERS"));
+    }
+}
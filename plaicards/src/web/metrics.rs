@@ -0,0 +1,250 @@
+//! Counters of the server, served in the Prometheus text format on `/metrics`.
+//!
+//! Rates like messages per second are left to the scraper, for example
+//! `rate(plaicards_ws_messages_received_total[1m])`.
+
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::web::ssr::AppState;
+
+/// Upper bounds, in seconds, of the buckets of the action latency
+const LATENCY_BUCKETS: [f64; 8] = [0.0005, 0.001, 0.0025, 0.005, 0.01, 0.05, 0.1, 0.5];
+
+pub static METRICS: Metrics = Metrics::new();
+
+#[derive(Debug)]
+pub struct Metrics {
+    ws_received: AtomicU64,
+    ws_sent: AtomicU64,
+    ws_connections: AtomicU64,
+    /// Room messages lost by receivers that fell behind the broadcast channel
+    lagged: AtomicU64,
+    action_latency: Histogram,
+}
+
+impl Metrics {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            ws_received: AtomicU64::new(0),
+            ws_sent: AtomicU64::new(0),
+            ws_connections: AtomicU64::new(0),
+            lagged: AtomicU64::new(0),
+            action_latency: Histogram::new(),
+        }
+    }
+
+    /// A client message was read from a websocket
+    pub fn received(&self) {
+        self.ws_received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A message reached the websocket of a client
+    pub fn sent(&self) {
+        self.ws_sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A receiver of a room skipped `n` messages because it was too slow
+    pub fn lagged(&self, n: u64) {
+        self.lagged.fetch_add(n, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn lagged_total(&self) -> u64 {
+        self.lagged.load(Ordering::Relaxed)
+    }
+
+    /// Counts an open websocket until the returned guard is dropped
+    #[must_use]
+    pub fn connection(&'static self) -> ConnectionGuard {
+        self.ws_connections.fetch_add(1, Ordering::Relaxed);
+        ConnectionGuard(self)
+    }
+
+    /// Time taken by a turn action, including the wait for the game lock
+    pub fn action(&self, took: Duration) {
+        self.action_latency.observe(took);
+    }
+
+    /// Renders every metric plus the gauges only known by the controllers
+    #[must_use]
+    pub fn render(&self, rooms: usize, lobbies: usize) -> String {
+        let mut out = String::new();
+        gauge(&mut out, "plaicards_rooms", "Live game rooms", rooms as u64);
+        gauge(
+            &mut out,
+            "plaicards_lobbies",
+            "Open lobbies",
+            lobbies as u64,
+        );
+        gauge(
+            &mut out,
+            "plaicards_ws_connections",
+            "Open websockets of game rooms",
+            self.ws_connections.load(Ordering::Relaxed),
+        );
+        counter(
+            &mut out,
+            "plaicards_ws_messages_received_total",
+            "Messages received from game clients",
+            self.ws_received.load(Ordering::Relaxed),
+        );
+        counter(
+            &mut out,
+            "plaicards_ws_messages_sent_total",
+            "Messages sent to game clients",
+            self.ws_sent.load(Ordering::Relaxed),
+        );
+        counter(
+            &mut out,
+            "plaicards_broadcast_lagged_total",
+            "Room messages skipped by clients that fell behind",
+            self.lagged.load(Ordering::Relaxed),
+        );
+        self.action_latency.render(
+            &mut out,
+            "plaicards_turn_action_seconds",
+            "Latency of turn actions",
+        );
+        out
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keeps a websocket counted as open
+#[derive(Debug)]
+pub struct ConnectionGuard(&'static Metrics);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.ws_connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug)]
+struct Histogram {
+    /// Observations of each bucket alone. Made cumulative when rendering.
+    buckets: [AtomicU64; LATENCY_BUCKETS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const fn new() -> Self {
+        const ZERO: AtomicU64 = AtomicU64::new(0);
+        Self {
+            buckets: [ZERO; LATENCY_BUCKETS.len()],
+            count: AtomicU64::new(0),
+            sum_micros: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, took: Duration) {
+        let secs = took.as_secs_f64();
+        if let Some(i) = LATENCY_BUCKETS.iter().position(|b| secs <= *b) {
+            self.buckets[i].fetch_add(1, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        let micros = u64::try_from(took.as_micros()).unwrap_or(u64::MAX);
+        self.sum_micros.fetch_add(micros, Ordering::Relaxed);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn render(&self, out: &mut String, name: &str, help: &str) {
+        let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} histogram");
+        let mut cumulative = 0;
+        for (bound, bucket) in LATENCY_BUCKETS.iter().zip(&self.buckets) {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let _ = writeln!(out, "{name}_bucket{{le=\"{bound}\"}} {cumulative}");
+        }
+        let count = self.count.load(Ordering::Relaxed);
+        let sum = self.sum_micros.load(Ordering::Relaxed) as f64 / 1e6;
+        let _ = writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {count}");
+        let _ = writeln!(out, "{name}_sum {sum}");
+        let _ = writeln!(out, "{name}_count {count}");
+    }
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, value: u64) {
+    let _ = writeln!(
+        out,
+        "# HELP {name} {help}\n# TYPE {name} gauge\n{name} {value}"
+    );
+}
+
+fn counter(out: &mut String, name: &str, help: &str, value: u64) {
+    let _ = writeln!(
+        out,
+        "# HELP {name} {help}\n# TYPE {name} counter\n{name} {value}"
+    );
+}
+
+pub async fn handler(State(state): State<AppState>) -> impl IntoResponse {
+    let rooms = state.gc.rooms().await.len();
+    let lobbies = state.lobby.lobbies().await.len();
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        METRICS.render(rooms, lobbies),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use std::time::Duration;
+
+    use super::Metrics;
+
+    static TEST_METRICS: Metrics = Metrics::new();
+
+    #[rstest]
+    fn renders_prometheus_text() {
+        let metrics = Metrics::new();
+        metrics.received();
+        metrics.received();
+        metrics.sent();
+        metrics.lagged(4);
+        metrics.action(Duration::from_micros(700));
+        metrics.action(Duration::from_secs(2));
+
+        let out = metrics.render(3, 1);
+
+        assert!(out.contains("# TYPE plaicards_rooms gauge\nplaicards_rooms 3\n"));
+        assert!(out.contains("plaicards_lobbies 1\n"));
+        assert!(out.contains("plaicards_ws_messages_received_total 2\n"));
+        assert!(out.contains("plaicards_ws_messages_sent_total 1\n"));
+        assert!(out.contains("plaicards_broadcast_lagged_total 4\n"));
+        assert!(out.contains("plaicards_turn_action_seconds_bucket{le=\"0.0005\"} 0\n"));
+        assert!(out.contains("plaicards_turn_action_seconds_bucket{le=\"0.001\"} 1\n"));
+        assert!(out.contains("plaicards_turn_action_seconds_bucket{le=\"0.5\"} 1\n"));
+        assert!(out.contains("plaicards_turn_action_seconds_bucket{le=\"+Inf\"} 2\n"));
+        assert!(out.contains("plaicards_turn_action_seconds_count 2\n"));
+        assert!(out.contains("plaicards_turn_action_seconds_sum 2.0007\n"));
+    }
+
+    #[rstest]
+    fn connections_are_counted_while_open() {
+        let first = TEST_METRICS.connection();
+        let second = TEST_METRICS.connection();
+        assert!(TEST_METRICS
+            .render(0, 0)
+            .contains("plaicards_ws_connections 2\n"));
+
+        drop(first);
+        drop(second);
+        assert!(TEST_METRICS
+            .render(0, 0)
+            .contains("plaicards_ws_connections 0\n"));
+    }
+}
@@ -1,9 +1,19 @@
+pub mod admin;
 pub mod board;
+#[cfg(feature = "ssr")]
+pub mod bus;
 pub mod common;
 mod errors;
+#[cfg(feature = "ssr")]
+pub mod health;
+pub mod i18n;
 pub mod landing;
 pub mod lobby;
-pub mod plausible;
+#[cfg(feature = "ssr")]
+pub mod metrics;
+#[cfg(feature = "ssr")]
+pub mod rate_limit;
+pub mod subscriptions;
 
 /// Context to pass variables around
 #[derive(Clone, Debug)]
@@ -32,20 +42,133 @@ pub use errors::{Error, Result};
 #[cfg(feature = "ssr")]
 pub mod ssr {
 
+    use crate::app::App;
+    use crate::config::Config;
+    use crate::fileserv::file_and_error_handler;
+    use crate::web::health::{healthz, readyz, Health};
+    use crate::web::lobby::lobby_handler;
     use crate::web::lobby::ssr::LobbyController;
-    use axum::extract::FromRef;
-    use leptos::LeptosOptions;
+    use crate::web::metrics::handler as metrics_handler;
+    use crate::web::subscriptions::ssr::Subscriptions;
+    use axum::body::Body as AxumBody;
+    use axum::extract::{FromRef, Path, RawQuery, State};
+    use axum::http::Request;
+    use axum::response::{IntoResponse, Response};
+    use axum::routing::{get, post};
+    use axum::Router;
+    use leptos::{provide_context, LeptosOptions};
+    use leptos_axum::{
+        handle_server_fns_with_context, render_app_to_stream_with_context, LeptosRoutes,
+    };
+    use leptos_plausible::ssr::{self as plausible, Analytics};
+    use leptos_plausible::EVENTS_PATH;
     use leptos_router::RouteListing;
 
-    use super::board::GameController;
+    use super::board::{board_handler, GameController};
 
     /// This takes advantage of Axum's `SubStates` feature by deriving `FromRef`. This is the only way to have more than one
     /// item in Axum's State. Leptos requires you to have leptosOptions in your State struct for the leptos route handlers
     #[derive(FromRef, Debug, Clone)]
     pub struct AppState {
         pub leptos_options: LeptosOptions,
+        pub config: Config,
         pub lobby: LobbyController,
         pub gc: GameController,
+        pub health: Health,
+        pub subscriptions: Subscriptions,
+        pub analytics: Analytics,
         pub routes: Vec<RouteListing>,
     }
+
+    impl AppState {
+        /// Makes the state reachable from the server functions and components
+        fn provide(&self) {
+            provide_context(self.lobby.clone());
+            provide_context(self.gc.clone());
+            provide_context(self.config.clone());
+            provide_context(self.config.plausible.clone());
+            provide_context(self.subscriptions.clone());
+        }
+    }
+
+    async fn server_fn_handler(
+        State(app_state): State<AppState>,
+        path: Path<String>,
+        raw_query: RawQuery,
+        request: Request<AxumBody>,
+    ) -> impl IntoResponse {
+        handle_server_fns_with_context(move || app_state.provide(), request).await
+    }
+
+    async fn leptos_routes_handler(
+        State(app_state): State<AppState>,
+        req: Request<AxumBody>,
+    ) -> Response {
+        let handler = render_app_to_stream_with_context(
+            app_state.leptos_options.clone(),
+            move || app_state.provide(),
+            App,
+        );
+        handler(req).await.into_response()
+    }
+
+    /// Every route of the server: pages, server functions, websockets,
+    /// analytics events, metrics and health probes.
+    ///
+    /// # Panics
+    ///
+    /// If two routes name the same path segment differently, like
+    /// `/lobby/:id` and `/lobby/:lobby_id/ws`
+    #[must_use]
+    pub fn router(state: AppState) -> Router {
+        let routes = state.routes.clone();
+        Router::new()
+            .route(
+                "/api/*fn_name",
+                get(server_fn_handler).post(server_fn_handler),
+            )
+            .route("/lobby/:id/ws", get(lobby_handler))
+            .route("/game/ws", get(board_handler))
+            .route(EVENTS_PATH, post(plausible::handler))
+            .route("/metrics", get(metrics_handler))
+            .route("/healthz", get(healthz))
+            .route("/readyz", get(readyz))
+            .leptos_routes_with_handler(routes, get(leptos_routes_handler))
+            .fallback(file_and_error_handler)
+            .with_state(state)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::sync::Arc;
+
+        use leptos_axum::generate_route_list;
+
+        use crate::web::subscriptions::ssr::{LogMailer, MemoryStore};
+
+        use super::*;
+
+        #[tokio::test]
+        async fn every_route_fits_in_the_router() {
+            let config = Config::default();
+            let state = AppState {
+                leptos_options: LeptosOptions::builder().output_name("plaicards").build(),
+                lobby: LobbyController::new().await,
+                gc: GameController::new().await,
+                health: Health::default(),
+                subscriptions: Subscriptions::new(
+                    Arc::new(MemoryStore::default()),
+                    Arc::new(LogMailer),
+                    &config.public_url,
+                    &config.subscriptions,
+                ),
+                analytics: Analytics::new(&config.plausible, &config.public_url),
+                routes: generate_route_list(App),
+                config,
+            };
+
+            // Panics on conflicting routes
+            let _ = router(state);
+        }
+    }
 }
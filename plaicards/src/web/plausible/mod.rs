@@ -1,5 +0,0 @@
-pub mod components;
-mod event;
-pub mod experiments;
-
-pub use event::Plausible;
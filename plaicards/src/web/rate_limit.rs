@@ -0,0 +1,112 @@
+//! Sliding window rate limits for anything a client can spam
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+/// Allows up to `max` events in any `window` of time
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    max: usize,
+    window: Duration,
+    sent: VecDeque<Instant>,
+}
+
+impl RateLimiter {
+    #[must_use]
+    pub fn new(max: usize, window: Duration) -> Self {
+        Self {
+            max,
+            window,
+            sent: VecDeque::with_capacity(max),
+        }
+    }
+
+    /// Registers an event at `now` if it is still allowed
+    pub fn allow(&mut self, now: Instant) -> bool {
+        self.forget(now);
+        if self.sent.len() >= self.max {
+            return false;
+        }
+        self.sent.push_back(now);
+        true
+    }
+
+    fn forget(&mut self, now: Instant) {
+        while self
+            .sent
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= self.window)
+        {
+            self.sent.pop_front();
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.sent.is_empty()
+    }
+}
+
+/// One [`RateLimiter`] per key, for example per IP
+#[derive(Clone, Debug)]
+pub struct KeyedRateLimiter<K> {
+    max: usize,
+    window: Duration,
+    limiters: HashMap<K, RateLimiter>,
+}
+
+impl<K: Eq + Hash> KeyedRateLimiter<K> {
+    #[must_use]
+    pub fn new(max: usize, window: Duration) -> Self {
+        Self {
+            max,
+            window,
+            limiters: HashMap::new(),
+        }
+    }
+
+    /// Registers an event of `key` at `now` if it is still allowed
+    pub fn allow(&mut self, key: K, now: Instant) -> bool {
+        // Keys without recent events are dropped so the map does not grow forever
+        self.limiters.retain(|_, l| {
+            l.forget(now);
+            !l.is_idle()
+        });
+        let (max, window) = (self.max, self.window);
+        self.limiters
+            .entry(key)
+            .or_insert_with(|| RateLimiter::new(max, window))
+            .allow(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use std::time::{Duration, Instant};
+
+    use super::{KeyedRateLimiter, RateLimiter};
+
+    #[rstest]
+    fn rate_limiter_frees_slots_after_window() {
+        let mut limiter = RateLimiter::new(2, Duration::from_secs(10));
+        let start = Instant::now();
+
+        assert!(limiter.allow(start));
+        assert!(limiter.allow(start + Duration::from_secs(1)));
+        assert!(!limiter.allow(start + Duration::from_secs(2)));
+        assert!(limiter.allow(start + Duration::from_secs(10)));
+        assert!(!limiter.allow(start + Duration::from_secs(10)));
+    }
+
+    #[rstest]
+    fn keys_are_limited_independently() {
+        let mut limiter = KeyedRateLimiter::new(1, Duration::from_secs(10));
+        let start = Instant::now();
+
+        assert!(limiter.allow("a", start));
+        assert!(!limiter.allow("a", start));
+        assert!(limiter.allow("b", start));
+        assert!(limiter.allow("a", start + Duration::from_secs(10)));
+    }
+}
@@ -0,0 +1,259 @@
+//! CSV file store, compatible with the old crowdfunding alert file.
+//!
+//! Each line is `created_at,email,token,confirmed_at,unsubscribed_at` with
+//! RFC 3339 dates and empty fields for missing ones. Lines of the old
+//! `date,email` format are read as confirmed subscriptions, as those people
+//! already asked to be told about the crowdfunding. Lines that are neither
+//! are kept as they are at the end of the file, so no signup gets lost.
+
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::warn;
+
+use super::normalize_email;
+use super::ssr::{new_token, upsert, Result, Subscription, SubscriptionError, SubscriptionStore};
+
+/// Keeps every subscription in memory and rewrites the whole file on changes.
+/// Fine for the thousands of emails of a board game.
+#[derive(Debug)]
+pub struct FileStore {
+    path: PathBuf,
+    subscriptions: Mutex<Vec<Subscription>>,
+    /// Lines that could not be read, written back untouched
+    unparsed: Vec<String>,
+}
+
+impl FileStore {
+    /// Reads the subscriptions in `path`. A missing file is an empty store.
+    ///
+    /// # Errors
+    ///
+    /// If the file exists but cannot be read
+    pub fn open(path: &Path) -> Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(storage(path, &e)),
+        };
+
+        let mut subscriptions = vec![];
+        let mut unparsed = vec![];
+        for (num, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match parse_line(line) {
+                Some(s) => upsert(&mut subscriptions, s),
+                None => {
+                    warn!("Keeping malformed line {} of {path:?} as is", num + 1);
+                    unparsed.push(line.to_string());
+                }
+            }
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            subscriptions: Mutex::new(subscriptions),
+            unparsed,
+        })
+    }
+
+    /// Writes all the subscriptions to a temporary file and moves it over the
+    /// old one, so a crash never leaves half a file behind.
+    fn write(&self, subscriptions: &[Subscription]) -> Result<()> {
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(|e| storage(dir, &e))?;
+        }
+        let tmp = self.path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp).map_err(|e| storage(&tmp, &e))?;
+        for s in subscriptions {
+            writeln!(file, "{}", format_line(s)).map_err(|e| storage(&tmp, &e))?;
+        }
+        for line in &self.unparsed {
+            writeln!(file, "{line}").map_err(|e| storage(&tmp, &e))?;
+        }
+        file.sync_all().map_err(|e| storage(&tmp, &e))?;
+        fs::rename(&tmp, &self.path).map_err(|e| storage(&self.path, &e))
+    }
+
+    fn with<T>(&self, f: impl FnOnce(&mut Vec<Subscription>) -> T) -> Result<T> {
+        let mut subs = self
+            .subscriptions
+            .lock()
+            .map_err(|e| SubscriptionError::Storage(e.to_string()))?;
+        Ok(f(&mut subs))
+    }
+}
+
+impl SubscriptionStore for FileStore {
+    fn by_email(&self, email: &str) -> Result<Option<Subscription>> {
+        self.with(|subs| subs.iter().find(|s| s.email == email).cloned())
+    }
+
+    fn by_token(&self, token: &str) -> Result<Option<Subscription>> {
+        self.with(|subs| subs.iter().find(|s| s.token == token).cloned())
+    }
+
+    fn save(&self, subscription: Subscription) -> Result<()> {
+        let mut subs = self
+            .subscriptions
+            .lock()
+            .map_err(|e| SubscriptionError::Storage(e.to_string()))?;
+        let mut updated = subs.clone();
+        upsert(&mut updated, subscription);
+        // Only keep the change if it reached the disk
+        self.write(&updated)?;
+        *subs = updated;
+        drop(subs);
+        Ok(())
+    }
+
+    fn all(&self) -> Result<Vec<Subscription>> {
+        self.with(|subs| subs.clone())
+    }
+}
+
+fn storage(path: &Path, e: &std::io::Error) -> SubscriptionError {
+    SubscriptionError::Storage(format!("{path:?}: {e}"))
+}
+
+fn format_line(s: &Subscription) -> String {
+    let date = |d: Option<DateTime<Utc>>| d.map(|d| d.to_rfc3339()).unwrap_or_default();
+    format!(
+        "{},{},{},{},{}",
+        s.created_at.to_rfc3339(),
+        s.email,
+        s.token,
+        date(s.confirmed_at),
+        date(s.unsubscribed_at)
+    )
+}
+
+fn parse_line(line: &str) -> Option<Subscription> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    match fields[..] {
+        [created_at, email, token, confirmed_at, unsubscribed_at] => Some(Subscription {
+            email: normalize_email(email)?,
+            token: Some(token).filter(|t| !t.is_empty())?.into(),
+            created_at: parse_date(created_at)?,
+            confirmed_at: parse_optional_date(confirmed_at)?,
+            unsubscribed_at: parse_optional_date(unsubscribed_at)?,
+        }),
+        // Old crowdfunding alert, written with `Utc::now().to_string()`
+        [date, email] => {
+            let created_at = parse_date(date)?;
+            Some(Subscription {
+                email: normalize_email(email)?,
+                token: new_token(),
+                created_at,
+                confirmed_at: Some(created_at),
+                unsubscribed_at: None,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    date.parse().ok()
+}
+
+/// `None` if the date is malformed, `Some(None)` if it is missing
+#[allow(clippy::option_option)]
+fn parse_optional_date(date: &str) -> Option<Option<DateTime<Utc>>> {
+    if date.is_empty() {
+        Some(None)
+    } else {
+        parse_date(date).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use rstest::{fixture, rstest};
+    use std::path::PathBuf;
+
+    use super::FileStore;
+    use crate::web::subscriptions::ssr::{Subscription, SubscriptionStore};
+
+    /// Path in a fresh temporary directory
+    #[fixture]
+    fn path() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("plaicards-{}", uuid::Uuid::new_v4()));
+        dir.join("emails.csv")
+    }
+
+    #[rstest]
+    fn missing_file_is_empty(path: PathBuf) {
+        let store = FileStore::open(&path).expect("Missing file is fine");
+        assert!(store.all().expect("Store works").is_empty());
+    }
+
+    #[rstest]
+    fn subscriptions_survive_a_restart(path: PathBuf) {
+        let now = Utc
+            .with_ymd_and_hms(2024, 5, 1, 12, 0, 0)
+            .single()
+            .expect("Valid date");
+        let mut subscription = Subscription::new("ceo@metatrust.ai".into(), now);
+        subscription.confirmed_at = Some(now);
+
+        FileStore::open(&path)
+            .expect("Missing file is fine")
+            .save(subscription.clone())
+            .expect("Writable file");
+
+        let store = FileStore::open(&path).expect("Readable file");
+        assert_eq!(
+            store.all().expect("Store works"),
+            vec![subscription.clone()]
+        );
+        assert_eq!(
+            store.by_token(&subscription.token).expect("Store works"),
+            Some(subscription)
+        );
+    }
+
+    #[rstest]
+    fn legacy_lines_are_confirmed(path: PathBuf) {
+        std::fs::create_dir_all(path.parent().expect("Has a dir")).expect("Temp dir");
+        std::fs::write(
+            &path,
+            "2024-04-02 10:00:00.123456 UTC,CEO@MetaTrust.ai\n\
+             not a line\n\
+             2024-04-03 10:00:00 UTC,cto@metatrust.ai\n",
+        )
+        .expect("Writable file");
+
+        let store = FileStore::open(&path).expect("Readable file");
+        let all = store.all().expect("Store works");
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].email, "ceo@metatrust.ai");
+        assert!(all.iter().all(Subscription::is_active));
+        assert_ne!(all[0].token, all[1].token);
+    }
+
+    #[rstest]
+    fn malformed_lines_survive_a_save(path: PathBuf) {
+        std::fs::create_dir_all(path.parent().expect("Has a dir")).expect("Temp dir");
+        std::fs::write(
+            &path,
+            "2024-04-02 10:00:00 UTC,ceo@metatrust.ai\n\
+             2024-04-02 11:00:00 UTC,cto at metatrust\n",
+        )
+        .expect("Writable file");
+
+        let store = FileStore::open(&path).expect("Readable file");
+        let subscription = Subscription::new("cfo@metatrust.ai".into(), Utc::now());
+        store.save(subscription).expect("Writable file");
+
+        let content = std::fs::read_to_string(&path).expect("Readable file");
+        assert!(content.contains("2024-04-02 11:00:00 UTC,cto at metatrust\n"));
+        let store = FileStore::open(&path).expect("Readable file");
+        assert_eq!(store.all().expect("Store works").len(), 2);
+    }
+}
@@ -0,0 +1,170 @@
+//! Newsletter subscriptions with double opt-in.
+//!
+//! Subscribing stores a pending [`ssr::Subscription`] and mails a link with a
+//! secret token. Only after following it the email is confirmed. The same
+//! token is used in the unsubscribe link.
+
+use cfg_if::cfg_if;
+use leptos::*;
+use leptos_router::{use_params_map, ActionForm};
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        pub mod ssr;
+        mod file_store;
+        pub use self::file_store::FileStore;
+        mod sendmail;
+        pub use self::sendmail::SendmailMailer;
+
+        #[cfg(feature = "sqlite")]
+        mod sqlite_store;
+        #[cfg(feature = "sqlite")]
+        pub use self::sqlite_store::SqliteStore;
+    }
+}
+
+/// Maximum length of an email address
+const MAX_EMAIL_LEN: usize = 254;
+const MAX_LOCAL_LEN: usize = 64;
+const MAX_LABEL_LEN: usize = 63;
+
+/// Validates an email and returns it in the form used to detect duplicates.
+///
+/// This is on purpose stricter than the RFC: no quoted local parts, comments
+/// or IP domains. Nobody subscribes to a board game newsletter with those.
+#[must_use]
+pub fn normalize_email(raw: &str) -> Option<String> {
+    let email = raw.trim().to_lowercase();
+    if email.len() > MAX_EMAIL_LEN {
+        return None;
+    }
+    let (local, domain) = email.split_once('@')?;
+
+    let valid_local = !local.is_empty()
+        && local.len() <= MAX_LOCAL_LEN
+        && !local.starts_with('.')
+        && !local.ends_with('.')
+        && !local.contains("..")
+        && local
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~.-".contains(c));
+
+    let labels: Vec<&str> = domain.split('.').collect();
+    let valid_domain = labels.len() >= 2
+        && labels.iter().all(|l| {
+            !l.is_empty()
+                && l.len() <= MAX_LABEL_LEN
+                && !l.starts_with('-')
+                && !l.ends_with('-')
+                && l.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+
+    (valid_local && valid_domain).then_some(email)
+}
+
+#[server(ConfirmSubscription, "/api/newsletter")]
+pub async fn confirm_subscription(token: String) -> Result<(), ServerFnError> {
+    ssr::subscriptions()?.confirm(&token, chrono::Utc::now())?;
+    Ok(())
+}
+
+#[server(Unsubscribe, "/api/newsletter")]
+pub async fn unsubscribe(token: String) -> Result<(), ServerFnError> {
+    ssr::subscriptions()?.unsubscribe(&token, chrono::Utc::now())?;
+    Ok(())
+}
+
+fn token_param() -> String {
+    use_params_map().with_untracked(|ps| ps.get("token").cloned().unwrap_or_default())
+}
+
+/// Landing of the link in the confirmation email
+#[component]
+pub fn ConfirmPage() -> impl IntoView {
+    let token = token_param();
+    let confirmed = create_resource(move || token.clone(), confirm_subscription);
+
+    view! {
+      <div class="my-20 mx-auto max-w-xl text-center">
+        <Suspense fallback=|| view! { <p>"Confirming your email..."</p> }>
+          {move || {
+              confirmed
+                  .get()
+                  .map(|res| match res {
+                      Ok(()) => view! { <h2 class="text-2xl">"You are in! We will keep you posted 🚀"</h2> }.into_view(),
+                      Err(e) => view! { <h2 class="text-2xl">"This link does not work anymore"</h2> }.into_view(),
+                  })
+          }}
+        </Suspense>
+      </div>
+    }
+}
+
+/// Landing of the unsubscribe link.
+///
+/// It asks before doing anything, as some email clients open every link.
+#[component]
+pub fn UnsubscribePage() -> impl IntoView {
+    let token = token_param();
+    let unsubscribe = create_server_action::<Unsubscribe>();
+    let value = unsubscribe.value();
+
+    view! {
+      <div class="my-20 mx-auto max-w-xl text-center">
+        <Show
+          when=move || value.with(|v| matches!(v, Some(Ok(()))))
+          fallback=move || {
+              view! {
+                <h2 class="text-2xl">"Sad to see you go"</h2>
+                <ActionForm action=unsubscribe>
+                  <input type="hidden" name="token" value=token.clone()/>
+                  <button type="submit" class="py-2 px-3 mt-6 text-white bg-gray-700 rounded-md">
+                    "Unsubscribe"
+                  </button>
+                </ActionForm>
+                <Show when=move || value.with(|v| matches!(v, Some(Err(_)))) fallback=|| ()>
+                  <p class="mt-4">"This link does not work anymore"</p>
+                </Show>
+              }
+          }
+        >
+          <h2 class="text-2xl">"You will not hear from us again"</h2>
+        </Show>
+      </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::normalize_email;
+
+    #[rstest]
+    #[case("ceo@metatrust.ai", "ceo@metatrust.ai")]
+    #[case("  CEO@MetaTrust.AI ", "ceo@metatrust.ai")]
+    #[case("first.last+plai@mail.example.com", "first.last+plai@mail.example.com")]
+    fn valid_emails_are_normalized(#[case] raw: &str, #[case] expected: &str) {
+        assert_eq!(normalize_email(raw).as_deref(), Some(expected));
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::no_at("metatrust.ai")]
+    #[case::two_at("a@b@metatrust.ai")]
+    #[case::no_tld("ceo@localhost")]
+    #[case::empty_label("ceo@metatrust..ai")]
+    #[case::dot_start(".ceo@metatrust.ai")]
+    #[case::spaces("c eo@metatrust.ai")]
+    #[case::comma("ceo,cto@metatrust.ai")]
+    #[case::hyphen_label("ceo@-metatrust.ai")]
+    fn invalid_emails_are_rejected(#[case] raw: &str) {
+        assert_eq!(normalize_email(raw), None);
+    }
+
+    #[rstest]
+    fn long_emails_are_rejected() {
+        let local = "a".repeat(65);
+        assert_eq!(normalize_email(&format!("{local}@metatrust.ai")), None);
+    }
+}
@@ -0,0 +1,125 @@
+//! Mailer that hands the emails to the `sendmail` program of the host.
+//!
+//! Postfix, exim, msmtp and friends all ship one, so this needs no SMTP
+//! settings in the server. The message goes through stdin with `-t -i`: the
+//! recipient is read from the headers and a line with a dot does not end it.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use super::ssr::{Mailer, Result, SubscriptionError};
+
+#[derive(Debug)]
+pub struct SendmailMailer {
+    program: PathBuf,
+    /// `From` header, like `PLAI <newsletter@get.plai.cards>`
+    from: String,
+}
+
+impl SendmailMailer {
+    #[must_use]
+    pub const fn new(program: PathBuf, from: String) -> Self {
+        Self { program, from }
+    }
+
+    fn message(&self, email: &str, confirm_url: &str, unsubscribe_url: &str) -> String {
+        format!(
+            "From: {from}\r\n\
+             To: {email}\r\n\
+             Subject: Confirm your subscription to the PLAI newsletter\r\n\
+             List-Unsubscribe: <{unsubscribe_url}>\r\n\
+             Content-Type: text/plain; charset=utf-8\r\n\
+             \r\n\
+             Someone, hopefully you, asked to hear about PLAI at this address.\r\n\
+             \r\n\
+             Confirm it here: {confirm_url}\r\n\
+             \r\n\
+             If it was not you, ignore this email. To stop the emails at any\r\n\
+             time: {unsubscribe_url}\r\n",
+            from = self.from,
+        )
+    }
+}
+
+impl Mailer for SendmailMailer {
+    fn send_confirmation(
+        &self,
+        email: &str,
+        confirm_url: &str,
+        unsubscribe_url: &str,
+    ) -> Result<()> {
+        let failed =
+            |e: &dyn std::fmt::Display| SubscriptionError::Mail(format!("{:?}: {e}", self.program));
+        let mut child = Command::new(&self.program)
+            .args(["-t", "-i"])
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| failed(&e))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(self.message(email, confirm_url, unsubscribe_url).as_bytes())
+                .map_err(|e| failed(&e))?;
+        }
+        let status = child.wait().map_err(|e| failed(&e))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(failed(&status))
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use rstest::{fixture, rstest};
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
+
+    use super::SendmailMailer;
+    use crate::web::subscriptions::ssr::{Mailer, SubscriptionError};
+
+    /// Fake `sendmail` in a fresh temporary directory that keeps the message
+    /// next to itself
+    #[fixture]
+    fn sendmail() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("plaicards-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("Temp dir");
+        let program = dir.join("sendmail");
+        std::fs::write(&program, "#!/bin/sh\ncat > \"$0.eml\"\n").expect("Writable dir");
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755))
+            .expect("Executable script");
+        program
+    }
+
+    #[rstest]
+    fn message_goes_to_sendmail(sendmail: PathBuf) {
+        let mailer = SendmailMailer::new(sendmail.clone(), "PLAI <newsletter@plai.cards>".into());
+
+        mailer
+            .send_confirmation(
+                "ceo@metatrust.ai",
+                "https://plai.cards/newsletter/confirm/abc",
+                "https://plai.cards/newsletter/unsubscribe/abc",
+            )
+            .expect("Fake sendmail works");
+
+        let message = std::fs::read_to_string(sendmail.with_extension("eml")).expect("Sent");
+        assert!(
+            message.starts_with("From: PLAI <newsletter@plai.cards>\r\nTo: ceo@metatrust.ai\r\n")
+        );
+        assert!(message.contains("https://plai.cards/newsletter/confirm/abc"));
+        assert!(
+            message.contains("List-Unsubscribe: <https://plai.cards/newsletter/unsubscribe/abc>")
+        );
+    }
+
+    #[rstest]
+    fn missing_sendmail_is_an_error(sendmail: PathBuf) {
+        let mailer = SendmailMailer::new(sendmail.with_extension("missing"), "x@plai.cards".into());
+
+        let res = mailer.send_confirmation("ceo@metatrust.ai", "confirm", "unsubscribe");
+
+        assert!(matches!(res, Err(SubscriptionError::Mail(_))));
+    }
+}
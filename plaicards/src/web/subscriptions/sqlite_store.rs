@@ -0,0 +1,149 @@
+//! SQLite store, for when the CSV file grows too much
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::path::Path;
+use std::sync::Mutex;
+
+use super::ssr::{Result, Subscription, SubscriptionError, SubscriptionStore};
+
+const COLUMNS: &str = "email, token, created_at, confirmed_at, unsubscribed_at";
+
+#[derive(Debug)]
+pub struct SqliteStore {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteStore {
+    /// Opens the database in `path`, creating it if needed
+    ///
+    /// # Errors
+    ///
+    /// If the database cannot be opened or migrated
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| SubscriptionError::Storage(format!("{dir:?}: {e}")))?;
+        }
+        let conn = Connection::open(path).map_err(storage)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS subscriptions (
+                email TEXT PRIMARY KEY,
+                token TEXT NOT NULL UNIQUE,
+                created_at TEXT NOT NULL,
+                confirmed_at TEXT,
+                unsubscribed_at TEXT
+            );",
+        )
+        .map_err(storage)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn with<T>(&self, f: impl FnOnce(&Connection) -> rusqlite::Result<T>) -> Result<T> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| SubscriptionError::Storage(e.to_string()))?;
+        f(&conn).map_err(storage)
+    }
+
+    fn find(&self, column: &str, value: &str) -> Result<Option<Subscription>> {
+        self.with(|conn| {
+            conn.query_row(
+                &format!("SELECT {COLUMNS} FROM subscriptions WHERE {column} = ?1"),
+                params![value],
+                from_row,
+            )
+            .optional()
+        })
+    }
+}
+
+impl SubscriptionStore for SqliteStore {
+    fn by_email(&self, email: &str) -> Result<Option<Subscription>> {
+        self.find("email", email)
+    }
+
+    fn by_token(&self, token: &str) -> Result<Option<Subscription>> {
+        self.find("token", token)
+    }
+
+    fn save(&self, s: Subscription) -> Result<()> {
+        self.with(|conn| {
+            conn.execute(
+                &format!(
+                    "INSERT INTO subscriptions ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5)
+                     ON CONFLICT(email) DO UPDATE SET
+                        token = excluded.token,
+                        created_at = excluded.created_at,
+                        confirmed_at = excluded.confirmed_at,
+                        unsubscribed_at = excluded.unsubscribed_at"
+                ),
+                params![
+                    s.email,
+                    s.token,
+                    s.created_at,
+                    s.confirmed_at,
+                    s.unsubscribed_at
+                ],
+            )
+            .map(|_| ())
+        })
+    }
+
+    fn all(&self) -> Result<Vec<Subscription>> {
+        self.with(|conn| {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {COLUMNS} FROM subscriptions ORDER BY created_at"
+            ))?;
+            let rows = stmt.query_map([], from_row)?;
+            rows.collect()
+        })
+    }
+}
+
+fn from_row(row: &Row) -> rusqlite::Result<Subscription> {
+    Ok(Subscription {
+        email: row.get(0)?,
+        token: row.get(1)?,
+        created_at: row.get::<_, DateTime<Utc>>(2)?,
+        confirmed_at: row.get(3)?,
+        unsubscribed_at: row.get(4)?,
+    })
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn storage(e: rusqlite::Error) -> SubscriptionError {
+    SubscriptionError::Storage(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use rstest::rstest;
+
+    use super::SqliteStore;
+    use crate::web::subscriptions::ssr::{Subscription, SubscriptionStore};
+
+    #[rstest]
+    fn save_is_an_upsert() {
+        let store = SqliteStore::open(std::path::Path::new(":memory:")).expect("In memory db");
+        let now = Utc
+            .with_ymd_and_hms(2024, 5, 1, 12, 0, 0)
+            .single()
+            .expect("Valid date");
+        let mut subscription = Subscription::new("ceo@metatrust.ai".into(), now);
+        store.save(subscription.clone()).expect("Insert");
+
+        subscription.confirmed_at = Some(now);
+        store.save(subscription.clone()).expect("Update");
+
+        assert_eq!(store.all().expect("Query"), vec![subscription.clone()]);
+        assert_eq!(
+            store.by_token(&subscription.token).expect("Query"),
+            Some(subscription)
+        );
+    }
+}
@@ -0,0 +1,490 @@
+use chrono::{DateTime, Utc};
+use data_encoding::BASE64URL_NOPAD;
+use leptos::{use_context, ServerFnError};
+use rand::RngCore;
+use std::fmt::Debug;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tracing::{info, warn};
+
+use super::normalize_email;
+use super::FileStore;
+use crate::config::{MailerKind, StoreKind, SubscriptionsConfig};
+use crate::web::rate_limit::KeyedRateLimiter;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum SubscriptionError {
+    #[error("This does not look like an email")]
+    InvalidEmail,
+    #[error("Too many attempts. Try again in a few minutes")]
+    RateLimited,
+    #[error("This link is not valid anymore")]
+    UnknownToken,
+    #[error("Cannot store the subscription right now")]
+    Storage(String),
+    #[error("Cannot send the confirmation email right now")]
+    Mail(String),
+}
+
+pub type Result<T> = core::result::Result<T, SubscriptionError>;
+
+pub fn subscriptions() -> core::result::Result<Subscriptions, ServerFnError> {
+    use_context::<Subscriptions>()
+        .ok_or_else(|| ServerFnError::ServerError("Subscriptions missing".into()))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Subscription {
+    /// Normalized with [`normalize_email`]
+    pub email: String,
+    /// Secret of the confirmation and unsubscribe links
+    pub token: String,
+    pub created_at: DateTime<Utc>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub unsubscribed_at: Option<DateTime<Utc>>,
+}
+
+impl Subscription {
+    #[must_use]
+    pub fn new(email: String, now: DateTime<Utc>) -> Self {
+        Self {
+            email,
+            token: new_token(),
+            created_at: now,
+            confirmed_at: None,
+            unsubscribed_at: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_pending(&self) -> bool {
+        self.confirmed_at.is_none() && self.unsubscribed_at.is_none()
+    }
+
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.confirmed_at.is_some() && self.unsubscribed_at.is_none()
+    }
+}
+
+#[must_use]
+pub fn new_token() -> String {
+    let mut bytes = [0_u8; 24];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    BASE64URL_NOPAD.encode(&bytes)
+}
+
+/// Where subscriptions are kept. Emails are unique.
+pub trait SubscriptionStore: Send + Sync + Debug {
+    /// # Errors
+    ///
+    /// If the storage fails
+    fn by_email(&self, email: &str) -> Result<Option<Subscription>>;
+
+    /// # Errors
+    ///
+    /// If the storage fails
+    fn by_token(&self, token: &str) -> Result<Option<Subscription>>;
+
+    /// Inserts the subscription or replaces the one with the same email
+    ///
+    /// # Errors
+    ///
+    /// If the storage fails
+    fn save(&self, subscription: Subscription) -> Result<()>;
+
+    /// # Errors
+    ///
+    /// If the storage fails
+    fn all(&self) -> Result<Vec<Subscription>>;
+}
+
+/// Opens the store selected in the configuration
+///
+/// # Errors
+///
+/// If the store cannot be opened
+pub fn open_store(config: &SubscriptionsConfig) -> Result<Arc<dyn SubscriptionStore>> {
+    match config.store {
+        StoreKind::File => Ok(Arc::new(FileStore::open(&config.path)?)),
+        #[cfg(feature = "sqlite")]
+        StoreKind::Sqlite => Ok(Arc::new(super::SqliteStore::open(&config.path)?)),
+        #[cfg(not(feature = "sqlite"))]
+        StoreKind::Sqlite => Err(SubscriptionError::Storage(
+            "SQLite store needs the sqlite feature".into(),
+        )),
+    }
+}
+
+/// Opens the mailer selected in the configuration
+#[must_use]
+pub fn open_mailer(config: &SubscriptionsConfig) -> Arc<dyn Mailer> {
+    match config.mailer {
+        MailerKind::Log => {
+            if !cfg!(debug_assertions) {
+                warn!(
+                    "Confirmation emails are only written to the log, so no subscription \
+                     can be confirmed. Set subscriptions.mailer to send them"
+                );
+            }
+            Arc::new(LogMailer)
+        }
+        MailerKind::Sendmail => Arc::new(super::SendmailMailer::new(
+            config.sendmail.clone(),
+            config.from.clone(),
+        )),
+    }
+}
+
+/// Store that forgets everything on restart. Useful for tests.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    subscriptions: Mutex<Vec<Subscription>>,
+}
+
+impl MemoryStore {
+    fn with<T>(&self, f: impl FnOnce(&mut Vec<Subscription>) -> T) -> Result<T> {
+        let mut subs = self
+            .subscriptions
+            .lock()
+            .map_err(|e| SubscriptionError::Storage(e.to_string()))?;
+        Ok(f(&mut subs))
+    }
+}
+
+impl SubscriptionStore for MemoryStore {
+    fn by_email(&self, email: &str) -> Result<Option<Subscription>> {
+        self.with(|subs| subs.iter().find(|s| s.email == email).cloned())
+    }
+
+    fn by_token(&self, token: &str) -> Result<Option<Subscription>> {
+        self.with(|subs| subs.iter().find(|s| s.token == token).cloned())
+    }
+
+    fn save(&self, subscription: Subscription) -> Result<()> {
+        self.with(|subs| upsert(subs, subscription))
+    }
+
+    fn all(&self) -> Result<Vec<Subscription>> {
+        self.with(|subs| subs.clone())
+    }
+}
+
+/// Replaces the subscription with the same email or adds it
+pub(super) fn upsert(subs: &mut Vec<Subscription>, subscription: Subscription) {
+    match subs.iter_mut().find(|s| s.email == subscription.email) {
+        Some(s) => *s = subscription,
+        None => subs.push(subscription),
+    }
+}
+
+/// Sends the emails of the subscription flow
+pub trait Mailer: Send + Sync + Debug {
+    /// # Errors
+    ///
+    /// If the email cannot be sent
+    fn send_confirmation(
+        &self,
+        email: &str,
+        confirm_url: &str,
+        unsubscribe_url: &str,
+    ) -> Result<()>;
+}
+
+/// Writes the emails to the log instead of sending them
+#[derive(Debug, Default)]
+pub struct LogMailer;
+
+impl Mailer for LogMailer {
+    fn send_confirmation(
+        &self,
+        email: &str,
+        confirm_url: &str,
+        unsubscribe_url: &str,
+    ) -> Result<()> {
+        info!("Confirmation for {email}: {confirm_url} (unsubscribe: {unsubscribe_url})");
+        Ok(())
+    }
+}
+
+/// Subscription flow: validation, deduplication, double opt-in and
+/// unsubscribe, with a rate limit per IP.
+#[derive(Clone, Debug)]
+pub struct Subscriptions {
+    store: Arc<dyn SubscriptionStore>,
+    mailer: Arc<dyn Mailer>,
+    limiter: Arc<Mutex<KeyedRateLimiter<IpAddr>>>,
+    /// Base of the links in the emails
+    public_url: String,
+}
+
+impl Subscriptions {
+    #[must_use]
+    pub fn new(
+        store: Arc<dyn SubscriptionStore>,
+        mailer: Arc<dyn Mailer>,
+        public_url: &str,
+        config: &SubscriptionsConfig,
+    ) -> Self {
+        Self {
+            store,
+            mailer,
+            limiter: Arc::new(Mutex::new(KeyedRateLimiter::new(
+                config.max_per_ip,
+                Duration::from_secs(config.window_secs),
+            ))),
+            public_url: public_url.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn store(&self) -> &dyn SubscriptionStore {
+        self.store.as_ref()
+    }
+
+    /// Starts the subscription of `email` and mails the confirmation link.
+    ///
+    /// Subscribing an email that is already active does nothing and answers
+    /// the same, so nobody can find out who is subscribed.
+    ///
+    /// # Errors
+    ///
+    /// * `RateLimited` if `ip` tried too many times
+    /// * `InvalidEmail`
+    /// * `Storage`
+    pub fn subscribe(&self, email: &str, ip: IpAddr, now: DateTime<Utc>) -> Result<()> {
+        let allowed = self
+            .limiter
+            .lock()
+            .map_err(|e| SubscriptionError::Storage(e.to_string()))?
+            .allow(ip, Instant::now());
+        if !allowed {
+            warn!("Too many subscriptions from {ip}");
+            return Err(SubscriptionError::RateLimited);
+        }
+        let email = normalize_email(email).ok_or(SubscriptionError::InvalidEmail)?;
+
+        let subscription = match self.store.by_email(&email)? {
+            Some(s) if s.is_active() => return Ok(()),
+            // Send the same link again
+            Some(s) if s.is_pending() => s,
+            _ => Subscription::new(email, now),
+        };
+        self.store.save(subscription.clone())?;
+
+        self.mailer.send_confirmation(
+            &subscription.email,
+            &self.link("confirm", &subscription.token),
+            &self.link("unsubscribe", &subscription.token),
+        )
+    }
+
+    /// # Errors
+    ///
+    /// * `UnknownToken` if there is no pending or active subscription for it
+    /// * `Storage`
+    pub fn confirm(&self, token: &str, now: DateTime<Utc>) -> Result<()> {
+        let mut subscription = self.by_token(token)?;
+        if subscription.unsubscribed_at.is_some() {
+            return Err(SubscriptionError::UnknownToken);
+        }
+        if subscription.confirmed_at.is_none() {
+            subscription.confirmed_at = Some(now);
+            self.store.save(subscription)?;
+        }
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// * `UnknownToken`
+    /// * `Storage`
+    pub fn unsubscribe(&self, token: &str, now: DateTime<Utc>) -> Result<()> {
+        let mut subscription = self.by_token(token)?;
+        if subscription.unsubscribed_at.is_none() {
+            subscription.unsubscribed_at = Some(now);
+            self.store.save(subscription)?;
+        }
+        Ok(())
+    }
+
+    fn by_token(&self, token: &str) -> Result<Subscription> {
+        if token.is_empty() {
+            return Err(SubscriptionError::UnknownToken);
+        }
+        self.store
+            .by_token(token)?
+            .ok_or(SubscriptionError::UnknownToken)
+    }
+
+    fn link(&self, action: &str, token: &str) -> String {
+        format!("{}/newsletter/{action}/{token}", self.public_url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use rstest::{fixture, rstest};
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::sync::{Arc, Mutex};
+
+    use super::{Mailer, MemoryStore, Result, SubscriptionError, SubscriptionStore, Subscriptions};
+    use crate::config::SubscriptionsConfig;
+
+    /// Keeps the confirmation links instead of sending them
+    #[derive(Debug, Default)]
+    struct Outbox(Mutex<Vec<(String, String)>>);
+
+    impl Outbox {
+        fn sent(&self) -> Vec<(String, String)> {
+            self.0.lock().expect("Outbox poisoned").clone()
+        }
+    }
+
+    impl Mailer for Outbox {
+        fn send_confirmation(&self, email: &str, confirm_url: &str, _: &str) -> Result<()> {
+            self.0
+                .lock()
+                .expect("Outbox poisoned")
+                .push((email.into(), confirm_url.into()));
+            Ok(())
+        }
+    }
+
+    const IP: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+    #[fixture]
+    fn outbox() -> Arc<Outbox> {
+        Arc::default()
+    }
+
+    fn service(outbox: &Arc<Outbox>) -> Subscriptions {
+        Subscriptions::new(
+            Arc::new(MemoryStore::default()),
+            outbox.clone(),
+            "https://get.plai.cards",
+            &SubscriptionsConfig::default(),
+        )
+    }
+
+    fn token(url: &str) -> &str {
+        url.rsplit('/').next().expect("Link with token")
+    }
+
+    #[rstest]
+    fn subscribe_mails_confirmation(outbox: Arc<Outbox>) {
+        let subs = service(&outbox);
+        subs.subscribe(" CEO@MetaTrust.ai", IP, Utc::now())
+            .expect("Valid email");
+
+        let sent = outbox.sent();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, "ceo@metatrust.ai");
+        assert!(sent[0]
+            .1
+            .starts_with("https://get.plai.cards/newsletter/confirm/"));
+        let stored = subs
+            .store()
+            .by_email("ceo@metatrust.ai")
+            .expect("Store works")
+            .expect("Subscription stored");
+        assert!(stored.is_pending());
+    }
+
+    #[rstest]
+    fn invalid_email_is_rejected(outbox: Arc<Outbox>) {
+        let subs = service(&outbox);
+        assert_eq!(
+            subs.subscribe("not an email", IP, Utc::now()),
+            Err(SubscriptionError::InvalidEmail)
+        );
+        assert!(outbox.sent().is_empty());
+    }
+
+    #[rstest]
+    fn pending_duplicate_gets_same_link(outbox: Arc<Outbox>) {
+        let subs = service(&outbox);
+        subs.subscribe("ceo@metatrust.ai", IP, Utc::now())
+            .expect("Valid email");
+        subs.subscribe("CEO@metatrust.ai", IP, Utc::now())
+            .expect("Valid email");
+
+        let sent = outbox.sent();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0], sent[1]);
+        assert_eq!(subs.store().all().expect("Store works").len(), 1);
+    }
+
+    #[rstest]
+    fn confirmed_duplicate_is_not_mailed(outbox: Arc<Outbox>) {
+        let subs = service(&outbox);
+        subs.subscribe("ceo@metatrust.ai", IP, Utc::now())
+            .expect("Valid email");
+        let link = outbox.sent()[0].1.clone();
+        subs.confirm(token(&link), Utc::now()).expect("Valid token");
+
+        subs.subscribe("ceo@metatrust.ai", IP, Utc::now())
+            .expect("Same answer for active emails");
+        assert_eq!(outbox.sent().len(), 1);
+    }
+
+    #[rstest]
+    fn unsubscribe_disables_the_token(outbox: Arc<Outbox>) {
+        let subs = service(&outbox);
+        subs.subscribe("ceo@metatrust.ai", IP, Utc::now())
+            .expect("Valid email");
+        let link = outbox.sent()[0].1.clone();
+        subs.confirm(token(&link), Utc::now()).expect("Valid token");
+        subs.unsubscribe(token(&link), Utc::now())
+            .expect("Valid token");
+
+        assert_eq!(
+            subs.confirm(token(&link), Utc::now()),
+            Err(SubscriptionError::UnknownToken)
+        );
+
+        // Subscribing again needs a new confirmation
+        subs.subscribe("ceo@metatrust.ai", IP, Utc::now())
+            .expect("Valid email");
+        let sent = outbox.sent();
+        assert_eq!(sent.len(), 2);
+        assert_ne!(token(&sent[0].1), token(&sent[1].1));
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::random("not-a-token")]
+    fn unknown_token_is_rejected(outbox: Arc<Outbox>, #[case] token: &str) {
+        let subs = service(&outbox);
+        assert_eq!(
+            subs.confirm(token, Utc::now()),
+            Err(SubscriptionError::UnknownToken)
+        );
+        assert_eq!(
+            subs.unsubscribe(token, Utc::now()),
+            Err(SubscriptionError::UnknownToken)
+        );
+    }
+
+    #[rstest]
+    fn subscriptions_are_rate_limited_per_ip(outbox: Arc<Outbox>) {
+        let subs = service(&outbox);
+        let max = SubscriptionsConfig::default().max_per_ip;
+        for i in 0..max {
+            subs.subscribe(&format!("ceo{i}@metatrust.ai"), IP, Utc::now())
+                .expect("Under the limit");
+        }
+
+        assert_eq!(
+            subs.subscribe("late@metatrust.ai", IP, Utc::now()),
+            Err(SubscriptionError::RateLimited)
+        );
+        let other = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        assert!(subs
+            .subscribe("late@metatrust.ai", other, Utc::now())
+            .is_ok());
+    }
+}